@@ -1,7 +1,8 @@
 use chrono::NaiveDate;
 use clap::Parser;
 use islamic_prayer_times::{
-    DateRange, Elevation, Gmt, Latitude, Location, Longitude, Method, Params,
+    DateRange, Elevation, Ephemeris, Gmt, Latitude, Location, Longitude, Method, Params,
+    RefractionModel, WeatherSeries,
 };
 use serde::{Deserialize, Serialize};
 
@@ -10,6 +11,8 @@ pub struct ParamsConfig {
     pub params: Params,
     pub location: Location,
     pub date_range: Option<DateRange>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weather_series: Option<WeatherSeries>,
 }
 
 #[derive(Debug, Parser)]
@@ -31,10 +34,37 @@ pub struct CliArgs {
     #[arg(short, long, value_parser = clap::value_parser!(Elevation), default_value = "0")]
     pub elevation: Elevation,
 
+    // Optional height of the terrain surrounding the geographical location to correct Shurooq and Maghrib for the
+    // dip of the horizon seen from the elevation above it.
+    #[arg(short = 'r', long, value_parser = clap::value_parser!(Elevation))]
+    pub terrain_height: Option<Elevation>,
+
     // Optional calculation method the default value of which is Method::Isna.
     #[arg(short, long, value_enum, default_value_t = Method::Isna)]
     pub method: Method,
 
+    // Optional atmospheric refraction model the default value of which is RefractionModel::Itl.
+    #[arg(short = 'f', long, value_enum, default_value_t = RefractionModel::Itl)]
+    pub refraction_model: RefractionModel,
+
+    // Optional flag to derive the weather from the elevation of the geographical location using the International
+    // Standard Atmosphere.
+    #[arg(short = 'a', long)]
+    pub standard_atmosphere: bool,
+
+    // Optional path to the file to read the CSV monthly or daily pressure and temperature of the geographical location
+    // when the --input_file or -i command line parameter is not specified.
+    #[arg(short, long)]
+    pub weather_file_path: Option<String>,
+
+    // Optional difference in seconds between dynamical and universal time which overrides the Espenak-Meeus model.
+    #[arg(short, long, allow_negative_numbers = true)]
+    pub delta_t: Option<f64>,
+
+    // Optional precision of the position of the Sun the default value of which is Ephemeris::Vsop87.
+    #[arg(short = 'c', long, value_enum, default_value_t = Ephemeris::Vsop87)]
+    pub ephemeris: Ephemeris,
+
     // Optional start date the default value of which is today.
     #[arg(short, long, value_parser = clap::value_parser!(NaiveDate))]
     pub start_date: Option<NaiveDate>,
@@ -51,6 +81,21 @@ pub struct CliArgs {
     #[arg(short, long)]
     pub output_file_path: Option<String>,
 
+    // Optional flag to also calculate the solar summary (solar noon, sunrise, sunset, day length, equation of time,
+    // declination, maximum altitude, and twilight bands) of each date alongside its prayer times.
+    #[arg(short = 'u', long)]
+    pub solar_day: bool,
+
+    // Optional flag to also report the spans of dates in which a prayer time cannot be calculated conventionally,
+    // the adjustment it falls back to, and the discontinuities of the prayer time at the span boundaries.
+    #[arg(short = 'x', long)]
+    pub extreme_latitude_spans: bool,
+
+    // Optional path to the file to write the great-circle path and bearing line of the Qibla to as KML when
+    // the file extension is .kml, and as GeoJSON otherwise.
+    #[arg(short, long)]
+    pub qibla_file_path: Option<String>,
+
     // Optional path to the file to write the geographical location and calculation parameters to as JSON
     // when the --input_file or -i command line parameter is not specified.
     #[arg(short, long)]
@@ -1,4 +1,5 @@
 use core::fmt;
+use serde::{Deserialize, Serialize};
 use std::{
     error::Error,
     fmt::{Debug, Display, Formatter, Result},
@@ -33,3 +34,42 @@ impl Display for ParseError {
 }
 
 impl Error for ParseError {}
+
+/// The error type for when values are not in their required order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OrderError(pub(crate) String);
+
+impl Display for OrderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for OrderError {}
+
+/// The error type for when the Sun does not cross an altitude on a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CrossingError {
+    /// The Sun stays above the altitude all day.
+    AlwaysAbove,
+    /// The Sun stays below the altitude all day.
+    AlwaysBelow,
+    /// The Sun crosses the altitude on the day, but only in the other direction, such as a
+    /// sunset that moves past midnight.
+    NotOnDay,
+}
+
+impl Display for CrossingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            CrossingError::AlwaysAbove => write!(f, "The Sun stays above the altitude all day"),
+            CrossingError::AlwaysBelow => write!(f, "The Sun stays below the altitude all day"),
+            CrossingError::NotOnDay => write!(
+                f,
+                "The Sun crosses the altitude on the day only in the other direction"
+            ),
+        }
+    }
+}
+
+impl Error for CrossingError {}
@@ -1,8 +1,15 @@
-use std::f64::consts::PI;
+use std::{cell::RefCell, collections::BTreeMap, f64::consts::PI};
+
+use chrono::{Days, NaiveDate};
 
 use crate::angle::LimitAngle;
 
-use super::{coordinates::Coordinates, julian_day::JulianDay};
+use super::{
+    coordinates::{Coordinates, Gmt},
+    delta_t::DeltaT,
+    ephemeris::Ephemeris,
+    julian_day::JulianDay,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Astro {
@@ -11,6 +18,8 @@ pub struct Astro {
     ra: f64,       // Right Ascension
     rsum: f64,     // Sum of periodic values for radius vector R
     sid_time: f64, // Sidereal time
+    obl: f64,      // True obliquity of the ecliptic
+    nut_lon: f64,  // Nutation in longitude
 }
 
 impl Astro {
@@ -387,6 +396,8 @@ impl Astro {
         (2, -1, 0, 2, 2),
     ];
 
+    pub(crate) const SECS_PER_DAY: f64 = 86400.;
+
     pub fn dra(&self) -> f64 {
         self.dra
     }
@@ -403,9 +414,61 @@ impl Astro {
         self.sid_time
     }
 
-    fn new(julian_day: f64) -> Self {
+    pub fn rsum(&self) -> f64 {
+        self.rsum
+    }
+
+    pub fn obl(&self) -> f64 {
+        self.obl
+    }
+
+    pub fn nut_lon(&self) -> f64 {
+        self.nut_lon
+    }
+
+    // Returns the topocentric `Astro` (with its declination in degrees) for geographical coordinates.
+    pub(crate) fn topocentric(&self, coords: Coordinates) -> Self {
+        let (p_sin_phi, p_cos_phi) = TopAstroDay::geocentric_terms(coords);
+        // Astronomical Algorithms pg. 279 (40.1)
+        let earth_dist = 3600. * self.rsum;
+        let pi = (8.794 / earth_dist).to_radians();
+        let hours = (self.sid_time + f64::from(coords.longitude) - self.ra)
+            .cap_angle_360()
+            .to_radians();
+        // Astronomical Algorithms pg. 279 (40.2)
+        let dra = -p_cos_phi * pi.sin() * hours.sin();
+        let dra = dra.atan2(self.dec.cos() - p_cos_phi * pi.sin() * hours.cos());
+        // Astronomical Algorithms pg. 279 (40.3)
+        let dec = (self.dec.sin() - p_sin_phi * pi.sin()) * dra.cos();
+        let dec = dec
+            .atan2(self.dec.cos() - p_cos_phi * pi.sin() * hours.cos())
+            .to_degrees();
+
+        Self {
+            ra: self.ra + dra.to_degrees(),
+            sid_time: self.sid_time,
+            dra,
+            rsum: self.rsum,
+            dec,
+            obl: self.obl,
+            nut_lon: self.nut_lon,
+        }
+    }
+
+    // Returns the `Astro` at a Julian day in UT with the position of the Sun of an `Ephemeris`.
+    pub(crate) fn from_ephemeris(julian_day: f64, delta_t: f64, ephemeris: Ephemeris) -> Self {
+        match ephemeris {
+            Ephemeris::Vsop87 => Self::new(julian_day, delta_t),
+            Ephemeris::LowPrecision => Self::low_precision(julian_day, delta_t),
+        }
+    }
+
+    // Returns the `Astro` at a Julian day in UT, evaluating the position of the Sun in TT which
+    // is delta_t seconds ahead of it.
+    pub(crate) fn new(julian_day: f64, delta_t: f64) -> Self {
         // Astronomical Algorithms (AA) pg. 143-148
-        let j = julian_day - 2451545.;
+        // Astronomical Algorithms pg. 77 (Julian ephemeris day)
+        let j = julian_day + delta_t / Self::SECS_PER_DAY - 2451545.;
         // Astronomical Algorithms pg. 143 (22.1)
         let jc = j / 36525.;
         let jm = jc / 10.;
@@ -476,10 +539,58 @@ impl Astro {
         // Astronomical Algorithms pg. 93 (13.4)
         let dec = (b.sin() * e.cos() + b.cos() * e.sin() * l.sin()).asin();
 
-        // Astronomical Algorithms pg. 88 (Mean sidereal time)
-        let v0 = 280.46061837 + 360.98564736629 * j + 0.000387933 * jc2 - jc3 / 38710000.;
-        // Apparent sidereal time
-        let sid_time = v0.cap_angle_360() + delta_psi * e.cos();
+        let sid_time = Self::calc_sid_time(julian_day, delta_psi, e);
+
+        Self {
+            dec,
+            ra,
+            sid_time,
+            rsum,
+            dra: 0.,
+            obl: e,
+            nut_lon: delta_psi,
+        }
+    }
+
+    // Returns the `Astro` at a Julian day in UT like `new`, but with the low precision position
+    // of the Sun within 0.01 degrees instead of the VSOP87 series.
+    pub(crate) fn low_precision(julian_day: f64, delta_t: f64) -> Self {
+        // Astronomical Algorithms pg. 163-164
+        let jc = (julian_day + delta_t / Self::SECS_PER_DAY - 2451545.) / 36525.;
+        let jc2 = jc * jc;
+
+        // Astronomical Algorithms pg. 163 (25.2) (Geometric mean longitude of the Sun)
+        let l0 = 280.46646 + 36000.76983 * jc + 0.0003032 * jc2;
+        // Astronomical Algorithms pg. 163 (25.3) (Mean anomaly of the Sun)
+        let m = (357.52911 + 35999.05029 * jc - 0.0001537 * jc2).to_radians();
+        // Astronomical Algorithms pg. 163 (25.4) (Eccentricity of the Earth's orbit)
+        let ecc = 0.016708634 - 0.000042037 * jc - 0.0000001267 * jc2;
+        // Astronomical Algorithms pg. 164 (Equation of the center)
+        let c = (1.914602 - 0.004817 * jc - 0.000014 * jc2) * m.sin()
+            + (0.019993 - 0.000101 * jc) * (2. * m).sin()
+            + 0.000289 * (3. * m).sin();
+        // Astronomical Algorithms pg. 164 (25.5) (Radius vector)
+        let v = m + c.to_radians();
+        let rsum = 1.000001018 * (1. - ecc * ecc) / (1. + ecc * v.cos());
+
+        // Astronomical Algorithms pg. 164 (Apparent longitude)
+        let om = (125.04 - 1934.136 * jc).to_radians();
+        let delta_psi = -0.00478 * om.sin();
+        let l = (l0 + c - 0.00569 + delta_psi).to_radians();
+
+        // Astronomical Algorithms pg. 147 (22.2) (Mean obliquity of the ecliptic)
+        let e0 = 84381.448 - 46.8150 * jc - 0.00059 * jc2 + 0.001813 * jc2 * jc;
+        // Astronomical Algorithms pg. 165 (Obliquity corrected for the apparent position)
+        let e = (e0 / 3600. + 0.00256 * om.cos()).to_radians();
+
+        // Astronomical Algorithms pg. 165 (25.6, 25.7)
+        let ra = (e.cos() * l.sin())
+            .atan2(l.cos())
+            .to_degrees()
+            .cap_angle_360();
+        let dec = (e.sin() * l.sin()).asin();
+
+        let sid_time = Self::calc_sid_time(julian_day, delta_psi, e);
 
         Self {
             dec,
@@ -487,9 +598,22 @@ impl Astro {
             sid_time,
             rsum,
             dra: 0.,
+            obl: e,
+            nut_lon: delta_psi,
         }
     }
 
+    // Returns the apparent sidereal time in degrees at a Julian day in UT.
+    fn calc_sid_time(julian_day: f64, delta_psi: f64, obl: f64) -> f64 {
+        // Astronomical Algorithms pg. 88 (Mean sidereal time)
+        let j = julian_day - 2451545.;
+        let jc = j / 36525.;
+        let jc2 = jc * jc;
+        let v0 = 280.46061837 + 360.98564736629 * j + 0.000387933 * jc2 - jc2 * jc / 38710000.;
+        // Apparent sidereal time
+        v0.cap_angle_360() + delta_psi * obl.cos()
+    }
+
     fn calc_total(elems: &[(f64, f64, f64)], jm: f64) -> f64 {
         elems
             .iter()
@@ -540,22 +664,65 @@ impl Astro {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct AstroDay {
-    astros: Vec<Astro>,
+    astros: [Astro; 3],
     julian_day: JulianDay,
 }
 
 impl AstroDay {
-    pub fn new(julian_day: JulianDay) -> Self {
-        let jd_val = f64::from(julian_day);
-        let astros = vec![
-            Astro::new(jd_val - 1.),
-            Astro::new(jd_val),
-            Astro::new(jd_val + 1.),
+    pub fn new(julian_day: JulianDay, delta_t: DeltaT, ephemeris: Ephemeris) -> Self {
+        AstroCache::new(julian_day.gmt, delta_t, ephemeris).astro_day(julian_day)
+    }
+}
+
+// Geocentric `Astro`s at local midnight of dates for a Gmt, shared by the `AstroDay`s of a range
+// of dates so that each date is evaluated once rather than for itself and both its neighbours.
+#[derive(Debug)]
+pub struct AstroCache {
+    gmt: Gmt,
+    delta_t: DeltaT,
+    ephemeris: Ephemeris,
+    astros: RefCell<BTreeMap<NaiveDate, Astro>>,
+}
+
+impl AstroCache {
+    // Days kept before the current date when the window slides, which covers the year searched
+    // back for the nearest good day.
+    const WINDOW_DAYS: u64 = 366;
+
+    pub fn new(gmt: Gmt, delta_t: DeltaT, ephemeris: Ephemeris) -> Self {
+        Self {
+            gmt,
+            delta_t,
+            ephemeris,
+            astros: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn astro_day(&self, julian_day: JulianDay) -> AstroDay {
+        let date = julian_day.date;
+        let astros = [
+            self.astro(date - Days::new(1)),
+            self.astro(date),
+            self.astro(date + Days::new(1)),
         ];
 
-        Self { astros, julian_day }
+        AstroDay { astros, julian_day }
+    }
+
+    // Drops the `Astro`s of the dates before the window ending at a date.
+    pub fn slide(&self, date: NaiveDate) {
+        let start_date = date - Days::new(Self::WINDOW_DAYS);
+        let mut astros = self.astros.borrow_mut();
+        *astros = astros.split_off(&start_date);
+    }
+
+    fn astro(&self, date: NaiveDate) -> Astro {
+        *self.astros.borrow_mut().entry(date).or_insert_with(|| {
+            let jd_val = f64::from(JulianDay::new(date, self.gmt));
+            Astro::from_ephemeris(jd_val, self.delta_t.seconds(date), self.ephemeris)
+        })
     }
 }
 
@@ -563,52 +730,27 @@ impl AstroDay {
 pub struct TopAstroDay {
     astro_day: AstroDay,
     coords: Coordinates,
-    astros: Vec<Astro>,
+    astros: [Astro; 3],
 }
 
 impl TopAstroDay {
-    const EARTH_RADIUS: f64 = 6378140.;
+    pub(crate) const EARTH_RADIUS: f64 = 6378140.;
+
+    pub fn from_jd(
+        julian_day: JulianDay,
+        coords: Coordinates,
+        delta_t: DeltaT,
+        ephemeris: Ephemeris,
+    ) -> Self {
+        Self::from_ad(AstroDay::new(julian_day, delta_t, ephemeris), coords)
+    }
 
-    pub fn from_jd(julian_day: JulianDay, coords: Coordinates) -> Self {
-        Self::from_ad(AstroDay::new(julian_day), coords)
+    pub fn from_cache(cache: &AstroCache, julian_day: JulianDay, coords: Coordinates) -> Self {
+        Self::from_ad(cache.astro_day(julian_day), coords)
     }
 
     fn from_ad(astro_day: AstroDay, coords: Coordinates) -> Self {
-        let mut astros = Vec::new();
-
-        for astro in astro_day.astros.iter() {
-            // Astronomical Algorithms pg. 82
-            let b_a = 0.99664719;
-            let lat_rads = f64::from(coords.latitude).to_radians();
-            let u = (b_a * lat_rads.tan()).atan();
-            let elev = f64::from(coords.elevation);
-            let p_sin_phi = b_a * u.sin() + elev / Self::EARTH_RADIUS * lat_rads.sin();
-            let p_cos_phi = u.cos() + elev / Self::EARTH_RADIUS * lat_rads.cos();
-            // Astronomical Algorithms pg. 279 (40.1)
-            let earth_dist = 3600. * astro.rsum;
-            let pi = (8.794 / earth_dist).to_radians();
-            let hours = (astro.sid_time + f64::from(coords.longitude) - astro.ra)
-                .cap_angle_360()
-                .to_radians();
-            // Astronomical Algorithms pg. 279 (40.2)
-            let dra = -p_cos_phi * pi.sin() * hours.sin();
-            let dra = dra.atan2(astro.dec.cos() - p_cos_phi * pi.sin() * hours.cos());
-            // Astronomical Algorithms pg. 279 (40.3)
-            let dec = (astro.dec.sin() - p_sin_phi * pi.sin()) * dra.cos();
-            let dec = dec
-                .atan2(astro.dec.cos() - p_cos_phi * pi.sin() * hours.cos())
-                .to_degrees();
-
-            let top_astro = Astro {
-                ra: astro.ra + dra.to_degrees(),
-                sid_time: astro.sid_time,
-                dra,
-                rsum: astro.rsum,
-                dec,
-            };
-
-            astros.push(top_astro);
-        }
+        let astros = astro_day.astros.map(|x| x.topocentric(coords));
 
         Self {
             astro_day,
@@ -617,8 +759,22 @@ impl TopAstroDay {
         }
     }
 
+    // Returns the quantities p sin(phi') and p cos(phi') of the observer's geocentric position.
+    pub(crate) fn geocentric_terms(coords: Coordinates) -> (f64, f64) {
+        // Astronomical Algorithms pg. 82
+        let b_a = 0.99664719;
+        let lat_rads = f64::from(coords.latitude).to_radians();
+        let u = (b_a * lat_rads.tan()).atan();
+        let elev = f64::from(coords.elevation);
+        let p_sin_phi = b_a * u.sin() + elev / Self::EARTH_RADIUS * lat_rads.sin();
+        let p_cos_phi = u.cos() + elev / Self::EARTH_RADIUS * lat_rads.cos();
+        (p_sin_phi, p_cos_phi)
+    }
+
+    // Returns the `TopAstroDay` of other coordinates from the same geocentric `Astro`s, which are
+    // only made topocentric again.
     pub fn new_coords(&self, coords: Coordinates) -> Self {
-        Self::from_ad(self.astro_day.clone(), coords)
+        Self::from_ad(self.astro_day, coords)
     }
 
     pub fn coords(&self) -> Coordinates {
@@ -656,7 +812,7 @@ mod tests {
         let date = NaiveDate::from_ymd_opt(2023, 1, 25).unwrap();
         let julian_day = JulianDay::new(date, Gmt::try_from(-5.).unwrap());
         // Act
-        let astro = Astro::new(f64::from(julian_day));
+        let astro = Astro::new(f64::from(julian_day), 0.);
         // Assert
         assert_approx_eq!(f64, 307.28605197, astro.ra, epsilon = EPSILON_TEST);
         assert_approx_eq!(f64, 0., astro.dra, epsilon = EPSILON_TEST);
@@ -665,13 +821,45 @@ mod tests {
         assert_approx_eq!(f64, 199.24752575, astro.sid_time, epsilon = EPSILON_TEST);
     }
 
+    #[test]
+    fn should_new_astro_low_precision() {
+        // Arrange
+        // Astronomical Algorithms pg. 165 (Example 25.a)
+        let julian_day = 2448908.5;
+        // Act
+        let astro = Astro::low_precision(julian_day, 0.);
+        // Assert
+        assert_approx_eq!(f64, 198.38083, astro.ra, epsilon = 0.00001);
+        assert_approx_eq!(f64, -7.78507, astro.dec.to_degrees(), epsilon = 0.00001);
+        assert_approx_eq!(f64, 0.99766, astro.rsum, epsilon = 0.00001);
+    }
+
+    #[test]
+    fn should_new_astro_low_precision_near_vsop87() {
+        // Arrange
+        let date = NaiveDate::from_ymd_opt(2023, 1, 25).unwrap();
+        let julian_day = JulianDay::new(date, Gmt::try_from(-5.).unwrap());
+        // Act
+        let astro = Astro::new(f64::from(julian_day), 0.);
+        let low_astro = Astro::low_precision(f64::from(julian_day), 0.);
+        // Assert
+        assert_approx_eq!(f64, astro.ra, low_astro.ra, epsilon = 0.01);
+        assert_approx_eq!(
+            f64,
+            astro.dec,
+            low_astro.dec,
+            epsilon = 0.01_f64.to_radians()
+        );
+        assert_approx_eq!(f64, astro.sid_time, low_astro.sid_time, epsilon = 0.01);
+    }
+
     #[test]
     fn should_new_astro_day() {
         // Arrange
         let date = NaiveDate::from_ymd_opt(2023, 1, 25).unwrap();
         let julian_day = JulianDay::new(date, Gmt::try_from(-5.).unwrap());
         // Act
-        let astro_day = AstroDay::new(julian_day);
+        let astro_day = AstroDay::new(julian_day, DeltaT::None, Ephemeris::Vsop87);
         // Assert
         assert_eq!(3, astro_day.astros.len());
         assert_eq!(julian_day, astro_day.julian_day);
@@ -757,7 +945,7 @@ mod tests {
         // Arrange
         let date = NaiveDate::from_ymd_opt(2023, 1, 25).unwrap();
         let julian_day = JulianDay::new(date, Gmt::try_from(-5.).unwrap());
-        let astro_day = AstroDay::new(julian_day);
+        let astro_day = AstroDay::new(julian_day, DeltaT::None, Ephemeris::Vsop87);
         let coords = Coordinates::new(
             Latitude::try_from(39.0181651).unwrap(),
             Longitude::try_from(-77.2085914).unwrap(),
@@ -860,4 +1048,46 @@ mod tests {
             epsilon = EPSILON_TEST
         );
     }
+
+    #[test]
+    fn should_evaluate_cached_astro_once_per_day() {
+        // Arrange
+        let gmt = Gmt::try_from(-5.).unwrap();
+        let cache = AstroCache::new(gmt, DeltaT::default(), Ephemeris::Vsop87);
+        let start_date = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+        // Act
+        let astro_days: Vec<AstroDay> = start_date
+            .iter_days()
+            .take(30)
+            .map(|x| cache.astro_day(JulianDay::new(x, gmt)))
+            .collect();
+        // Assert
+        assert_eq!(32, cache.astros.borrow().len());
+        let julian_day = JulianDay::new(NaiveDate::from_ymd_opt(2023, 1, 15).unwrap(), gmt);
+        let astro_day = AstroDay::new(julian_day, DeltaT::default(), Ephemeris::Vsop87);
+        for (astro, cached_astro) in astro_day.astros.iter().zip(&astro_days[14].astros) {
+            assert_eq!(astro.ra, cached_astro.ra);
+            assert_eq!(astro.dec, cached_astro.dec);
+            assert_eq!(astro.sid_time, cached_astro.sid_time);
+        }
+    }
+
+    #[test]
+    fn should_slide_cached_astro_window() {
+        // Arrange
+        let gmt = Gmt::try_from(-5.).unwrap();
+        let cache = AstroCache::new(gmt, DeltaT::default(), Ephemeris::Vsop87);
+        let start_date = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+        // Act
+        for date in start_date.iter_days().take(1000) {
+            cache.astro_day(JulianDay::new(date, gmt));
+            cache.slide(date);
+        }
+        // Assert
+        // The window before the last date, the last date, and the day after it
+        assert_eq!(
+            AstroCache::WINDOW_DAYS as usize + 2,
+            cache.astros.borrow().len()
+        );
+    }
 }
@@ -34,6 +34,12 @@ impl TryFrom<f64> for Gmt {
     }
 }
 
+impl Default for Gmt {
+    fn default() -> Self {
+        Self(0.)
+    }
+}
+
 impl From<Gmt> for f64 {
     fn from(value: Gmt) -> Self {
         value.0
@@ -0,0 +1,159 @@
+//! The difference ΔT between dynamical and universal time.
+//!
+//! Civil dates and times, and so the calculated prayer times, are kept in Universal Time (UT),
+//! which follows the irregular rotation of the Earth. The positions of the Sun and the Moon are
+//! calculated in Terrestrial (dynamical) Time (TT), which is uniform, so a Julian day in UT is
+//! converted to TT by adding ΔT = TT - UT before evaluating them, while the sidereal time which
+//! relates them to a geographical location is evaluated in UT.
+//!
+//! ΔT is about a minute today but grew to hours in antiquity and is only extrapolated for the
+//! future. Type [`DeltaT`] represents how it is determined:
+//!
+//! * [`EspenakMeeus`](DeltaT::EspenakMeeus) is the polynomial expressions of Espenak and Meeus
+//!   for the years -500 to 2150 and their long-term parabola outside them.
+//! * [`Seconds`](DeltaT::Seconds) is a fixed value in seconds, such as a published value for a
+//!   historical date or a forecast for a future date.
+//! * [`None`](DeltaT::None) is no correction, which treats UT as TT.
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// The `DeltaT` type. See [the module level documentation](self) for more.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum DeltaT {
+    /// The polynomial expressions of Espenak and Meeus.
+    #[default]
+    EspenakMeeus,
+    /// A fixed ΔT in seconds.
+    Seconds(f64),
+    /// No correction.
+    None,
+}
+
+impl DeltaT {
+    // Julian day of J2000.0 and days per Julian year
+    const J2000: f64 = 2451545.;
+    const DAYS_PER_YEAR: f64 = 365.25;
+
+    /// Returns ΔT in seconds on a date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use islamic_prayer_times::*;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+    ///
+    /// assert!((DeltaT::EspenakMeeus.seconds(date) - 63.9).abs() < 0.1);
+    /// assert_eq!(69.2, DeltaT::Seconds(69.2).seconds(date));
+    /// assert_eq!(0., DeltaT::None.seconds(date));
+    /// ```
+    pub fn seconds(&self, date: NaiveDate) -> f64 {
+        // Decimal year of the middle of the month
+        let year = date.year() as f64 + (date.month0() as f64 + 0.5) / 12.;
+        self.seconds_in_year(year)
+    }
+
+    // Returns ΔT in seconds at a Julian day.
+    pub(crate) fn seconds_at(&self, julian_day: f64) -> f64 {
+        let year = 2000. + (julian_day - Self::J2000) / Self::DAYS_PER_YEAR;
+        self.seconds_in_year(year)
+    }
+
+    fn seconds_in_year(&self, year: f64) -> f64 {
+        match self {
+            DeltaT::EspenakMeeus => Self::espenak_meeus(year),
+            DeltaT::Seconds(seconds) => *seconds,
+            DeltaT::None => 0.,
+        }
+    }
+
+    // Five Millennium Canon of Solar Eclipses (Espenak and Meeus)
+    fn espenak_meeus(y: f64) -> f64 {
+        let poly = |t: f64, coeffs: &[f64]| coeffs.iter().rev().fold(0., |acc, c| acc * t + c);
+        // Long-term parabola
+        let parabola = |y: f64| {
+            let u = (y - 1820.) / 100.;
+            -20. + 32. * u * u
+        };
+
+        if y < -500. {
+            parabola(y)
+        } else if y < 500. {
+            let coeffs = [
+                10583.6,
+                -1014.41,
+                33.78311,
+                -5.952053,
+                -0.1798452,
+                0.022174192,
+                0.0090316521,
+            ];
+            poly(y / 100., &coeffs)
+        } else if y < 1600. {
+            let coeffs = [
+                1574.2,
+                -556.01,
+                71.23472,
+                0.319781,
+                -0.8503463,
+                -0.005050998,
+                0.0083572073,
+            ];
+            poly((y - 1000.) / 100., &coeffs)
+        } else if y < 1700. {
+            poly(y - 1600., &[120., -0.9808, -0.01532, 1. / 7129.])
+        } else if y < 1800. {
+            let coeffs = [8.83, 0.1603, -0.0059285, 0.00013336, -1. / 1174000.];
+            poly(y - 1700., &coeffs)
+        } else if y < 1860. {
+            let coeffs = [
+                13.72,
+                -0.332447,
+                0.0068612,
+                0.0041116,
+                -0.00037436,
+                0.0000121272,
+                -0.0000001699,
+                0.000000000875,
+            ];
+            poly(y - 1800., &coeffs)
+        } else if y < 1900. {
+            let coeffs = [
+                7.62,
+                0.5737,
+                -0.251754,
+                0.01680668,
+                -0.0004473624,
+                1. / 233174.,
+            ];
+            poly(y - 1860., &coeffs)
+        } else if y < 1920. {
+            let coeffs = [-2.79, 1.494119, -0.0598939, 0.0061966, -0.000197];
+            poly(y - 1900., &coeffs)
+        } else if y < 1941. {
+            poly(y - 1920., &[21.20, 0.84493, -0.076100, 0.0020936])
+        } else if y < 1961. {
+            poly(y - 1950., &[29.07, 0.407, -1. / 233., 1. / 2547.])
+        } else if y < 1986. {
+            poly(y - 1975., &[45.45, 1.067, -1. / 260., -1. / 718.])
+        } else if y < 2005. {
+            let coeffs = [
+                63.86,
+                0.3345,
+                -0.060374,
+                0.0017275,
+                0.000651814,
+                0.00002373599,
+            ];
+            poly(y - 2000., &coeffs)
+        } else if y < 2050. {
+            poly(y - 2000., &[62.92, 0.32217, 0.005589])
+        } else if y < 2150. {
+            parabola(y) - 0.5628 * (2150. - y)
+        } else {
+            parabola(y)
+        }
+    }
+}
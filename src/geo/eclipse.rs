@@ -0,0 +1,527 @@
+//! Solar and lunar [`Eclipse`] circumstances for a geographical [`Location`], such as
+//! needed to announce Salat al-Kusuf (the solar eclipse prayer) and Salat al-Khusuf
+//! (the lunar eclipse prayer).
+//!
+//! Eclipses are found near each new and full Moon of a [`DateRange`] by evaluating the
+//! positions of the Sun and the Moon numerically. The times of a solar eclipse are the
+//! local circumstances (first contact, maximum, and last contact) at the [`Location`]
+//! when the Moon's penumbra reaches it, and the circumstances of the eclipse on the
+//! whole Earth otherwise. The times of a lunar eclipse are the penumbral contacts for
+//! a [`Penumbral`](EclipseType::Penumbral) eclipse and the umbral contacts for any
+//! other type.
+
+use std::fmt::Display;
+
+use chrono::{Datelike, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::DateRange;
+
+use super::{
+    astro::{Astro, TopAstroDay},
+    coordinates::{Coordinates, Location},
+    delta_t::DeltaT,
+    julian_day::JulianDay,
+    moon::Moon,
+    root::bisect,
+};
+
+/// An enumeration of the kinds of eclipses.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EclipseKind {
+    /// The Moon passes in front of the Sun (Kusuf).
+    Solar,
+    /// The Moon passes through the shadow of the Earth (Khusuf).
+    Lunar,
+}
+
+impl Display for EclipseKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// An enumeration of the types of eclipses.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EclipseType {
+    /// The Moon only enters the penumbra of the Earth (lunar eclipses only).
+    Penumbral,
+    /// The Sun or the Moon is only partly eclipsed.
+    Partial,
+    /// The Moon is too small to cover the Sun entirely (solar eclipses only).
+    Annular,
+    /// The Sun or the Moon is entirely eclipsed.
+    Total,
+}
+
+impl Display for EclipseType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// The circumstances of a solar or lunar eclipse at a [`Location`].
+///
+/// See [the module level documentation](self) for more.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Eclipse {
+    /// Solar or lunar eclipse.
+    pub kind: EclipseKind,
+    /// The type of the eclipse.
+    pub eclipse_type: EclipseType,
+    /// The fraction of the diameter of the Sun (solar) or the Moon (lunar) that is
+    /// eclipsed at maximum. It is 0 for a solar eclipse that does not reach the location.
+    pub magnitude: f64,
+    /// The local date and time the eclipse begins.
+    pub begin: NaiveDateTime,
+    /// The local date and time of the maximum eclipse.
+    pub maximum: NaiveDateTime,
+    /// The local date and time the eclipse ends.
+    pub end: NaiveDateTime,
+    /// The eclipsed body is above the horizon of the location at some time during the eclipse.
+    pub visible: bool,
+}
+
+impl Display for Eclipse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fmt_time = "%l:%M %p";
+        write!(
+            f,
+            "{} {} eclipse ({:.3}): {} - {} - {}{}",
+            self.eclipse_type,
+            self.kind,
+            self.magnitude,
+            self.begin.format(fmt_time),
+            self.maximum.format(fmt_time),
+            self.end.format(fmt_time),
+            if self.visible { "" } else { " (not visible)" }
+        )
+    }
+}
+
+/// Returns the solar and lunar [`Eclipse`]s, ordered by their maximum, whose (local)
+/// maximum falls within a [`DateRange`] at a [`Location`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// use chrono::NaiveDate;
+/// use islamic_prayer_times::*;
+///
+/// let latitude = Latitude::try_from(39.)?;
+/// let longitude = Longitude::try_from(-77.)?;
+/// let elevation = Elevation::try_from(0.)?;
+/// let coords = Coordinates::new(latitude, longitude, elevation);
+/// let gmt = Gmt::try_from(-5.)?;
+/// let location = Location { coords, gmt };
+/// let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+/// let end_date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+/// let date_range = DateRange::from(start_date..=end_date);
+///
+/// let eclipses = eclipses_dt_rng(location, &date_range);
+///
+/// assert_eq!(4, eclipses.len());
+/// # Ok::<(), Box<dyn Error>>(())
+/// ```
+pub fn eclipses_dt_rng(location: Location, date_range: &DateRange) -> Vec<Eclipse> {
+    let start_jd = f64::from(JulianDay::new(*date_range.start_date(), location.gmt));
+    let end_jd = f64::from(JulianDay::new(*date_range.end_date(), location.gmt)) + 1.;
+
+    // Astronomical Algorithms pg. 349 (49.2) (Approximate number of lunations since 2000)
+    let start_date = date_range.start_date();
+    let year = start_date.year() as f64 + start_date.ordinal0() as f64 / 365.25;
+    let mut k = ((year - 2000.) * 12.3685).floor() - 1.;
+
+    let mut eclipses = Vec::new();
+    loop {
+        let mean_jd = mean_phase(k);
+        if mean_jd > end_jd + 1. {
+            break;
+        }
+
+        let eclipse = if k.fract() == 0. {
+            solar_eclipse(location, mean_jd)
+        } else {
+            lunar_eclipse(location, mean_jd)
+        };
+
+        if let Some((eclipse, max_jd)) = eclipse {
+            if (start_jd..end_jd).contains(&max_jd) {
+                eclipses.push(eclipse);
+            }
+        }
+
+        k += 0.5;
+    }
+    eclipses
+}
+
+// Mean radius of the Sun in kilometers.
+const SUN_RADIUS: f64 = 696000.;
+// Mean radius of the Moon in kilometers.
+const MOON_RADIUS: f64 = 1737.4;
+// Astronomical unit in kilometers.
+const AU: f64 = 149597870.7;
+// Enlargement of the shadow of the Earth by its atmosphere (Danjon).
+const SHADOW_ENLARGEMENT: f64 = 1.02;
+// Astronomical Algorithms pg. 381 (Ratio of the mean to the equatorial radius of the Earth)
+const EARTH_FLATTENING: f64 = 0.998340;
+// Astronomical Algorithms pg. 382 (Shadow axis reaches the surface of the Earth)
+const CENTRAL_GAMMA: f64 = 0.9972;
+// Astronomical Algorithms pg. 102 (Standard altitude of the Sun)
+const SUN_STD_ALT: f64 = -0.8333;
+// Days searched either side of a mean phase for the true syzygy.
+const SYZYGY_WINDOW: f64 = 1.5;
+// Days searched either side of the maximum for the contacts.
+const CONTACT_WINDOW: f64 = 0.5;
+// Days between samples of the altitude of the eclipsed body.
+const VISIBILITY_STEP: f64 = 5. / 1440.;
+// Tolerance in days of the numerical solutions.
+const TOLERANCE: f64 = 1e-6;
+
+// Geocentric equatorial rectangular positions of the Sun and the Moon in kilometers.
+#[derive(Debug, Clone, Copy)]
+struct Positions {
+    sun: [f64; 3],
+    moon: [f64; 3],
+    sid_time: f64,
+}
+
+impl Positions {
+    fn new(julian_day: f64) -> Self {
+        let delta_t = DeltaT::default().seconds_at(julian_day);
+        let astro = Astro::new(julian_day, delta_t);
+        let moon = Moon::new(julian_day, delta_t);
+        Self {
+            sun: rect(astro.ra(), astro.dec().to_degrees(), astro.rsum() * AU),
+            moon: rect(moon.ra(), moon.dec(), moon.dist()),
+            sid_time: astro.sid_time(),
+        }
+    }
+
+    // Returns the positions as seen by an observer at geographical coordinates.
+    fn topocentric(&self, coords: Coordinates) -> Self {
+        let obs = observer(coords, self.sid_time);
+        Self {
+            sun: sub(self.sun, obs),
+            moon: sub(self.moon, obs),
+            sid_time: self.sid_time,
+        }
+    }
+}
+
+fn mean_phase(k: f64) -> f64 {
+    // Astronomical Algorithms pg. 349 (49.1)
+    let jc = k / 1236.85;
+    let jc2 = jc * jc;
+    2451550.09766 + 29.530588861 * k + 0.00015437 * jc2 - 0.00000015 * jc2 * jc
+        + 0.00000000073 * jc2 * jc2
+}
+
+fn solar_eclipse(location: Location, mean_jd: f64) -> Option<(Eclipse, f64)> {
+    // The Moon's penumbra touches the Earth when the geocentric separation is less than the limit.
+    let geo_sep = |jd: f64| {
+        let pos = Positions::new(jd);
+        let sep = separation(pos.sun, pos.moon);
+        let limit = parallax(pos.moon) - parallax(pos.sun)
+            + semi_diameter(SUN_RADIUS, pos.sun)
+            + semi_diameter(MOON_RADIUS, pos.moon);
+        (sep, limit)
+    };
+
+    let max_jd = minimize(
+        |jd| geo_sep(jd).0,
+        mean_jd - SYZYGY_WINDOW,
+        mean_jd + SYZYGY_WINDOW,
+    );
+    let (sep, limit) = geo_sep(max_jd);
+    if sep >= limit {
+        return None;
+    }
+
+    let coords = location.coords;
+    let top_sep = |jd: f64| {
+        let pos = Positions::new(jd).topocentric(coords);
+        let sun_semi = semi_diameter(SUN_RADIUS, pos.sun);
+        let moon_semi = semi_diameter(MOON_RADIUS, pos.moon);
+        (separation(pos.sun, pos.moon), sun_semi, moon_semi)
+    };
+
+    let top_max_jd = minimize(
+        |jd| top_sep(jd).0,
+        max_jd - CONTACT_WINDOW,
+        max_jd + CONTACT_WINDOW,
+    );
+    let (sep, sun_semi, moon_semi) = top_sep(top_max_jd);
+
+    let (eclipse_type, magnitude, times) = if sep < sun_semi + moon_semi {
+        // Local circumstances
+        let eclipse_type = if sep <= (moon_semi - sun_semi).abs() {
+            if moon_semi > sun_semi {
+                EclipseType::Total
+            } else {
+                EclipseType::Annular
+            }
+        } else {
+            EclipseType::Partial
+        };
+        let magnitude = (sun_semi + moon_semi - sep) / (2. * sun_semi);
+        let contact = |jd: f64| {
+            let (sep, sun_semi, moon_semi) = top_sep(jd);
+            sep - sun_semi - moon_semi
+        };
+        let times = contacts(contact, top_max_jd);
+        (eclipse_type, magnitude, times)
+    } else {
+        // Circumstances on the whole Earth
+        let contact = |jd: f64| {
+            let (sep, limit) = geo_sep(jd);
+            sep - limit
+        };
+        let times = contacts(contact, max_jd);
+        (global_solar_type(max_jd), 0., times)
+    };
+
+    let visible = magnitude > 0.
+        && is_visible(times.0, times.2, |jd| {
+            let pos = Positions::new(jd).topocentric(coords);
+            altitude(pos.sun, coords, pos.sid_time) > SUN_STD_ALT
+        });
+
+    Some((
+        new_eclipse(
+            location,
+            EclipseKind::Solar,
+            eclipse_type,
+            magnitude,
+            times,
+            visible,
+        ),
+        times.1,
+    ))
+}
+
+fn global_solar_type(max_jd: f64) -> EclipseType {
+    let pos = Positions::new(max_jd);
+    let earth_radius = TopAstroDay::EARTH_RADIUS / 1000.;
+    // Astronomical Algorithms pg. 381 (Distance of the shadow axis from the center of the Earth)
+    let axis = unit(sub(pos.moon, pos.sun));
+    let along = dot(pos.moon, axis);
+    let gamma = norm(sub(pos.moon, scale(axis, along))) / earth_radius;
+    if gamma < CENTRAL_GAMMA {
+        // Compare the apparent radii seen from where the shadow axis meets the surface of the Earth.
+        let depth = (1. - gamma * gamma).sqrt() * earth_radius;
+        let moon_dist = -along - depth;
+        let sun_dist = norm(pos.sun) - depth;
+        if (MOON_RADIUS / moon_dist).asin() > (SUN_RADIUS / sun_dist).asin() {
+            EclipseType::Total
+        } else {
+            EclipseType::Annular
+        }
+    } else {
+        EclipseType::Partial
+    }
+}
+
+fn lunar_eclipse(location: Location, mean_jd: f64) -> Option<(Eclipse, f64)> {
+    // Astronomical Algorithms pg. 381 (Radii of the umbra and penumbra of the Earth)
+    let shadow = |jd: f64| {
+        let pos = Positions::new(jd);
+        let sep = separation(scale(pos.sun, -1.), pos.moon);
+        let moon_par = EARTH_FLATTENING * parallax(pos.moon) + parallax(pos.sun);
+        let sun_semi = semi_diameter(SUN_RADIUS, pos.sun);
+        let umbra = SHADOW_ENLARGEMENT * (moon_par - sun_semi);
+        let penumbra = SHADOW_ENLARGEMENT * (moon_par + sun_semi);
+        (sep, semi_diameter(MOON_RADIUS, pos.moon), umbra, penumbra)
+    };
+
+    let max_jd = minimize(
+        |jd| shadow(jd).0,
+        mean_jd - SYZYGY_WINDOW,
+        mean_jd + SYZYGY_WINDOW,
+    );
+    let (sep, moon_semi, umbra, penumbra) = shadow(max_jd);
+    if sep >= penumbra + moon_semi {
+        return None;
+    }
+
+    let (eclipse_type, magnitude, times) = if sep < umbra + moon_semi {
+        let eclipse_type = if sep + moon_semi <= umbra {
+            EclipseType::Total
+        } else {
+            EclipseType::Partial
+        };
+        let magnitude = (umbra + moon_semi - sep) / (2. * moon_semi);
+        let contact = |jd: f64| {
+            let (sep, moon_semi, umbra, _) = shadow(jd);
+            sep - umbra - moon_semi
+        };
+        (eclipse_type, magnitude, contacts(contact, max_jd))
+    } else {
+        let magnitude = (penumbra + moon_semi - sep) / (2. * moon_semi);
+        let contact = |jd: f64| {
+            let (sep, moon_semi, _, penumbra) = shadow(jd);
+            sep - penumbra - moon_semi
+        };
+        (EclipseType::Penumbral, magnitude, contacts(contact, max_jd))
+    };
+
+    let coords = location.coords;
+    let visible = is_visible(times.0, times.2, |jd| {
+        let pos = Positions::new(jd).topocentric(coords);
+        // Astronomical Algorithms pg. 102 (Standard altitude of the Moon)
+        altitude(pos.moon, coords, pos.sid_time) > 0.7275 * parallax(pos.moon) - 0.5667
+    });
+
+    Some((
+        new_eclipse(
+            location,
+            EclipseKind::Lunar,
+            eclipse_type,
+            magnitude,
+            times,
+            visible,
+        ),
+        times.1,
+    ))
+}
+
+fn new_eclipse(
+    location: Location,
+    kind: EclipseKind,
+    eclipse_type: EclipseType,
+    magnitude: f64,
+    times: (f64, f64, f64),
+    visible: bool,
+) -> Eclipse {
+    Eclipse {
+        kind,
+        eclipse_type,
+        magnitude,
+        begin: JulianDay::to_date_time(times.0, location.gmt),
+        maximum: JulianDay::to_date_time(times.1, location.gmt),
+        end: JulianDay::to_date_time(times.2, location.gmt),
+        visible,
+    }
+}
+
+// Returns the Julian days of the contact before, the maximum, and the contact after the maximum
+// where the contact function is negative at the maximum and increases away from it.
+fn contacts<F: Fn(f64) -> f64>(contact: F, max_jd: f64) -> (f64, f64, f64) {
+    let begin = bisect(&contact, max_jd - CONTACT_WINDOW, max_jd, TOLERANCE);
+    let end = bisect(&contact, max_jd + CONTACT_WINDOW, max_jd, TOLERANCE);
+    (begin, max_jd, end)
+}
+
+fn is_visible<F: Fn(f64) -> bool>(begin_jd: f64, end_jd: f64, above_horizon: F) -> bool {
+    let mut jd = begin_jd;
+    while jd < end_jd {
+        if above_horizon(jd) {
+            return true;
+        }
+        jd += VISIBILITY_STEP;
+    }
+    above_horizon(end_jd)
+}
+
+// Golden section search of the minimum of a unimodal function.
+fn minimize<F: Fn(f64) -> f64>(f: F, mut lo: f64, mut hi: f64) -> f64 {
+    let ratio = (5f64.sqrt() - 1.) / 2.;
+    let mut x1 = hi - ratio * (hi - lo);
+    let mut x2 = lo + ratio * (hi - lo);
+    let mut f1 = f(x1);
+    let mut f2 = f(x2);
+    while hi - lo > TOLERANCE {
+        if f1 < f2 {
+            hi = x2;
+            x2 = x1;
+            f2 = f1;
+            x1 = hi - ratio * (hi - lo);
+            f1 = f(x1);
+        } else {
+            lo = x1;
+            x1 = x2;
+            f1 = f2;
+            x2 = lo + ratio * (hi - lo);
+            f2 = f(x2);
+        }
+    }
+    (lo + hi) / 2.
+}
+
+// Geocentric equatorial rectangular position of the observer in kilometers.
+fn observer(coords: Coordinates, sid_time: f64) -> [f64; 3] {
+    let (p_sin_phi, p_cos_phi) = TopAstroDay::geocentric_terms(coords);
+    let earth_radius = TopAstroDay::EARTH_RADIUS / 1000.;
+    let loc_sid_time = (sid_time + f64::from(coords.longitude)).to_radians();
+    [
+        earth_radius * p_cos_phi * loc_sid_time.cos(),
+        earth_radius * p_cos_phi * loc_sid_time.sin(),
+        earth_radius * p_sin_phi,
+    ]
+}
+
+// Returns the altitude in degrees of a topocentric position.
+fn altitude(pos: [f64; 3], coords: Coordinates, sid_time: f64) -> f64 {
+    let lat_rads = f64::from(coords.latitude).to_radians();
+    let loc_sid_time = (sid_time + f64::from(coords.longitude)).to_radians();
+    let zenith = [
+        lat_rads.cos() * loc_sid_time.cos(),
+        lat_rads.cos() * loc_sid_time.sin(),
+        lat_rads.sin(),
+    ];
+    dot(unit(pos), zenith).asin().to_degrees()
+}
+
+fn rect(ra: f64, dec: f64, dist: f64) -> [f64; 3] {
+    let ra_rads = ra.to_radians();
+    let dec_rads = dec.to_radians();
+    [
+        dist * dec_rads.cos() * ra_rads.cos(),
+        dist * dec_rads.cos() * ra_rads.sin(),
+        dist * dec_rads.sin(),
+    ]
+}
+
+// Returns the angle in degrees between two positions.
+fn separation(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let cross = [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ];
+    norm(cross).atan2(dot(a, b)).to_degrees()
+}
+
+// Returns the apparent semi-diameter in degrees of a body of a radius at a position.
+fn semi_diameter(radius: f64, pos: [f64; 3]) -> f64 {
+    (radius / norm(pos)).asin().to_degrees()
+}
+
+// Returns the equatorial horizontal parallax in degrees of a body at a position.
+fn parallax(pos: [f64; 3]) -> f64 {
+    (TopAstroDay::EARTH_RADIUS / 1000. / norm(pos))
+        .asin()
+        .to_degrees()
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn norm(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: [f64; 3], factor: f64) -> [f64; 3] {
+    [a[0] * factor, a[1] * factor, a[2] * factor]
+}
+
+fn unit(a: [f64; 3]) -> [f64; 3] {
+    scale(a, 1. / norm(a))
+}
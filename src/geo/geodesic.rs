@@ -0,0 +1,721 @@
+// Geodesics on the WGS84 ellipsoid after C. F. F. Karney, Algorithms for geodesics,
+// J. Geodesy 87, 43-55 (2013), as implemented by GeographicLib, to the sixth order in the
+// third flattening. Unlike Vincenty's method, the inverse problem converges for all pairs
+// of points, including nearly antipodal ones.
+
+use crate::angle::{LimitAngle, PI_DEG, RIGHT_ANG_DEG, TWO_PI_DEG};
+
+// Result of the inverse geodesic problem
+#[derive(Debug, Clone, Copy)]
+pub struct Inverse {
+    pub dist: f64, // Distance in meters
+    pub azi1: f64, // Azimuth at the first point in degrees clockwise from North
+}
+
+pub struct Geodesic;
+
+impl Geodesic {
+    // WGS84 semi-major axis in meters
+    pub const A: f64 = 6378137.;
+    // WGS84 flattening
+    pub const F: f64 = 1. / 298.257223563;
+    // IUGG mean radius of the Earth in meters
+    pub const MEAN_RADIUS: f64 = 6371008.8;
+
+    const F1: f64 = 1. - Self::F;
+    const B: f64 = Self::A * Self::F1;
+    const E2: f64 = Self::F * (2. - Self::F);
+    const EP2: f64 = Self::E2 / (Self::F1 * Self::F1);
+    const N: f64 = Self::F / (2. - Self::F);
+
+    const ORDER: usize = 6;
+    const NC3X: usize = Self::ORDER * (Self::ORDER - 1) / 2;
+    const MAXIT1: usize = 20;
+    const MAXIT2: usize = Self::MAXIT1 + f64::MANTISSA_DIGITS as usize + 10;
+    const TINY: f64 = 1.4916681462400413e-154; // sqrt(f64::MIN_POSITIVE)
+    const TOL0: f64 = f64::EPSILON;
+    const TOL1: f64 = 200. * Self::TOL0;
+
+    // Solves the inverse geodesic problem between two points given in degrees.
+    pub fn inverse(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> Inverse {
+        let tol2 = Self::TOL0.sqrt();
+        let tolb = Self::TOL0 * tol2;
+        let xthresh = 1000. * tol2;
+        let etol2 =
+            0.1 * tol2 / (Self::F.abs().max(0.001) * (1. - Self::F / 2.).min(1.) / 2.).sqrt();
+
+        let lon12 = (lon2 - lon1).cap_angle_between_180();
+        let mut lonsign = if lon12 >= 0. { 1. } else { -1. };
+        let lon12 = lonsign * ang_round(lon12);
+        let lon12s = ang_round(PI_DEG - lon12);
+        let lam12 = lon12.to_radians();
+        let (slam12, clam12) = if lon12 > RIGHT_ANG_DEG {
+            let (s, c) = sincosd(lon12s);
+            (s, -c)
+        } else {
+            sincosd(lon12)
+        };
+
+        // Swap the points so that |lat1| >= |lat2| and make lat1 <= 0.
+        let mut lat1 = ang_round(lat1);
+        let mut lat2 = ang_round(lat2);
+        let swapp = if lat1.abs() < lat2.abs() { -1. } else { 1. };
+        if swapp < 0. {
+            lonsign = -lonsign;
+            std::mem::swap(&mut lat1, &mut lat2);
+        }
+        let latsign = if lat1 < 0. { 1. } else { -1. };
+        lat1 *= latsign;
+        lat2 *= latsign;
+
+        // Reduced latitudes
+        let (sbet1, cbet1) = sincosd(lat1);
+        let (sbet1, cbet1) = norm(Self::F1 * sbet1, cbet1);
+        let cbet1 = cbet1.max(Self::TINY);
+        let (sbet2, cbet2) = sincosd(lat2);
+        let (mut sbet2, mut cbet2) = norm(Self::F1 * sbet2, cbet2);
+        cbet2 = cbet2.max(Self::TINY);
+        if cbet1 < -sbet1 {
+            if cbet2 == cbet1 {
+                sbet2 = sbet1.copysign(sbet2);
+            }
+        } else if sbet2.abs() == -sbet1 {
+            cbet2 = cbet1;
+        }
+
+        let dn1 = (1. + Self::EP2 * sbet1 * sbet1).sqrt();
+        let dn2 = (1. + Self::EP2 * sbet2 * sbet2).sqrt();
+
+        let mut c1a = [0.; Self::ORDER + 1];
+        let mut c2a = [0.; Self::ORDER + 1];
+        let mut c3a = [0.; Self::ORDER];
+
+        let (mut salp1, mut calp1, mut salp2, mut calp2) = (0., 0., 0., 0.);
+        let mut s12x = 0.;
+
+        let mut meridian = lat1 == -RIGHT_ANG_DEG || slam12 == 0.;
+        if meridian {
+            // Endpoints on a single full meridian
+            calp1 = clam12;
+            salp1 = slam12;
+            calp2 = 1.;
+            salp2 = 0.;
+            let ssig1 = sbet1;
+            let csig1 = calp1 * cbet1;
+            let ssig2 = sbet2;
+            let csig2 = calp2 * cbet2;
+            let sig12 = (csig1 * ssig2 - ssig1 * csig2)
+                .max(0.)
+                .atan2(csig1 * csig2 + ssig1 * ssig2);
+            let (s12b, m12b) = Self::lengths(
+                Self::N,
+                sig12,
+                ssig1,
+                csig1,
+                dn1,
+                ssig2,
+                csig2,
+                dn2,
+                &mut c1a,
+                &mut c2a,
+            );
+            if sig12 < 1. || m12b >= 0. {
+                s12x = if sig12 < 3. * Self::TINY
+                    || (sig12 < Self::TOL0 && (s12b < 0. || m12b < 0.))
+                {
+                    0.
+                } else {
+                    s12b * Self::B
+                };
+            } else {
+                // The shortest path is not along the meridian (only for prolate ellipsoids).
+                meridian = false;
+            }
+        }
+
+        if !meridian && sbet1 == 0. && lon12s >= Self::F * PI_DEG {
+            // Geodesic along the equator
+            calp1 = 0.;
+            calp2 = 0.;
+            salp1 = 1.;
+            salp2 = 1.;
+            s12x = Self::A * lam12;
+        } else if !meridian {
+            let (sig12, salp1_0, calp1_0, salp2_0, calp2_0, dnm) = Self::inverse_start(
+                sbet1, cbet1, sbet2, cbet2, lam12, slam12, clam12, etol2, xthresh,
+            );
+            salp1 = salp1_0;
+            calp1 = calp1_0;
+            if sig12 >= 0. {
+                // Short lines
+                salp2 = salp2_0;
+                calp2 = calp2_0;
+                s12x = sig12 * Self::B * dnm;
+            } else {
+                // Newton's method on the longitude difference, with a bracket for bisection
+                let mut numit = 0;
+                let mut tripn = false;
+                let mut tripb = false;
+                let (mut salp1a, mut calp1a) = (Self::TINY, 1.);
+                let (mut salp1b, mut calp1b) = (Self::TINY, -1.);
+                let mut lambda;
+                loop {
+                    lambda = Self::lambda12(
+                        sbet1,
+                        cbet1,
+                        dn1,
+                        sbet2,
+                        cbet2,
+                        dn2,
+                        salp1,
+                        calp1,
+                        slam12,
+                        clam12,
+                        numit < Self::MAXIT1,
+                        &mut c1a,
+                        &mut c2a,
+                        &mut c3a,
+                    );
+                    let v = lambda.lam12;
+                    if numit >= Self::MAXIT2
+                        || tripb
+                        || v.abs() < (if tripn { 8. } else { 1. }) * Self::TOL0
+                    {
+                        break;
+                    }
+                    if v > 0. && (numit > Self::MAXIT1 || calp1 / salp1 > calp1b / salp1b) {
+                        salp1b = salp1;
+                        calp1b = calp1;
+                    } else if v < 0. && (numit > Self::MAXIT1 || calp1 / salp1 < calp1a / salp1a) {
+                        salp1a = salp1;
+                        calp1a = calp1;
+                    }
+                    numit += 1;
+                    if numit < Self::MAXIT1 && lambda.dlam12 > 0. {
+                        let dalp1 = -v / lambda.dlam12;
+                        if dalp1.abs() < std::f64::consts::PI {
+                            let (sdalp1, cdalp1) = dalp1.sin_cos();
+                            let nsalp1 = salp1 * cdalp1 + calp1 * sdalp1;
+                            if nsalp1 > 0. {
+                                (salp1, calp1) = norm(nsalp1, calp1 * cdalp1 - salp1 * sdalp1);
+                                tripn = v.abs() <= 16. * Self::TOL0;
+                                continue;
+                            }
+                        }
+                    }
+                    // Bisection when Newton's method fails
+                    (salp1, calp1) = norm((salp1a + salp1b) / 2., (calp1a + calp1b) / 2.);
+                    tripn = false;
+                    tripb = (salp1a - salp1).abs() + (calp1a - calp1) < tolb
+                        || (salp1 - salp1b).abs() + (calp1 - calp1b) < tolb;
+                }
+                salp2 = lambda.salp2;
+                calp2 = lambda.calp2;
+                let (s12b, _) = Self::lengths(
+                    lambda.eps,
+                    lambda.sig12,
+                    lambda.ssig1,
+                    lambda.csig1,
+                    dn1,
+                    lambda.ssig2,
+                    lambda.csig2,
+                    dn2,
+                    &mut c1a,
+                    &mut c2a,
+                );
+                s12x = s12b * Self::B;
+            }
+        }
+
+        // Undo the swap of the points and the signs of the latitudes and longitudes.
+        if swapp < 0. {
+            std::mem::swap(&mut salp1, &mut salp2);
+            std::mem::swap(&mut calp1, &mut calp2);
+        }
+        salp1 *= swapp * lonsign;
+        calp1 *= swapp * latsign;
+
+        Inverse {
+            dist: 0. + s12x,
+            azi1: salp1.atan2(calp1).to_degrees(),
+        }
+    }
+
+    // Returns the great-circle distance in meters between two points given in degrees on a
+    // sphere of the mean radius of the Earth.
+    pub fn great_circle_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+        let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+        let dlat = lat2 - lat1;
+        let dlon = (lon2 - lon1).to_radians();
+        // Haversine formula
+        let h = (dlat / 2.).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.).sin().powi(2);
+        2. * Self::MEAN_RADIUS * h.sqrt().min(1.).asin()
+    }
+
+    // Returns the distance and reduced length scaled by the semi-minor axis.
+    #[allow(clippy::too_many_arguments)]
+    fn lengths(
+        eps: f64,
+        sig12: f64,
+        ssig1: f64,
+        csig1: f64,
+        dn1: f64,
+        ssig2: f64,
+        csig2: f64,
+        dn2: f64,
+        c1a: &mut [f64; Self::ORDER + 1],
+        c2a: &mut [f64; Self::ORDER + 1],
+    ) -> (f64, f64) {
+        let a1 = a1m1f(eps);
+        c1f(eps, c1a);
+        let a2 = a2m1f(eps);
+        c2f(eps, c2a);
+        let m0x = a1 - a2;
+        let a1 = 1. + a1;
+        let a2 = 1. + a2;
+        let b1 = sin_cos_series(ssig2, csig2, c1a) - sin_cos_series(ssig1, csig1, c1a);
+        let s12b = a1 * (sig12 + b1);
+        let b2 = sin_cos_series(ssig2, csig2, c2a) - sin_cos_series(ssig1, csig1, c2a);
+        let j12 = m0x * sig12 + (a1 * b1 - a2 * b2);
+        let m12b = dn2 * (csig1 * ssig2) - dn1 * (ssig1 * csig2) - csig1 * csig2 * j12;
+        (s12b, m12b)
+    }
+
+    // Returns a starting point for Newton's method, or the solution for short lines
+    // (when sig12 >= 0).
+    #[allow(clippy::too_many_arguments)]
+    fn inverse_start(
+        sbet1: f64,
+        cbet1: f64,
+        sbet2: f64,
+        cbet2: f64,
+        lam12: f64,
+        slam12: f64,
+        clam12: f64,
+        etol2: f64,
+        xthresh: f64,
+    ) -> (f64, f64, f64, f64, f64, f64) {
+        let mut sig12 = -1.;
+        let (mut salp2, mut calp2, mut dnm) = (f64::NAN, f64::NAN, f64::NAN);
+        let sbet12 = sbet2 * cbet1 - cbet2 * sbet1;
+        let cbet12 = cbet2 * cbet1 + sbet2 * sbet1;
+        let sbet12a = sbet2 * cbet1 + cbet2 * sbet1;
+
+        let shortline = cbet12 >= 0. && sbet12 < 0.5 && cbet2 * lam12 < 0.5;
+        let (mut somg12, mut comg12) = if shortline {
+            let sbetm2 = (sbet1 + sbet2).powi(2);
+            let sbetm2 = sbetm2 / (sbetm2 + (cbet1 + cbet2).powi(2));
+            dnm = (1. + Self::EP2 * sbetm2).sqrt();
+            (lam12 / (Self::F1 * dnm)).sin_cos()
+        } else {
+            (slam12, clam12)
+        };
+
+        let mut salp1 = cbet2 * somg12;
+        let mut calp1 = if comg12 >= 0. {
+            sbet12 + cbet2 * sbet1 * somg12 * somg12 / (1. + comg12)
+        } else {
+            sbet12a - cbet2 * sbet1 * somg12 * somg12 / (1. - comg12)
+        };
+
+        let ssig12 = salp1.hypot(calp1);
+        let csig12 = sbet1 * sbet2 + cbet1 * cbet2 * comg12;
+
+        if shortline && ssig12 < etol2 {
+            // Really short lines
+            salp2 = cbet1 * somg12;
+            calp2 = sbet12
+                - cbet1
+                    * sbet2
+                    * if comg12 >= 0. {
+                        somg12 * somg12 / (1. + comg12)
+                    } else {
+                        1. - comg12
+                    };
+            (salp2, calp2) = norm(salp2, calp2);
+            sig12 = ssig12.atan2(csig12);
+        } else if Self::N.abs() >= 0.1
+            || csig12 >= 0.
+            || ssig12 >= 6. * Self::N.abs() * std::f64::consts::PI * cbet1 * cbet1
+        {
+            // Nothing to do, the zeroth order spherical approximation is good enough.
+        } else {
+            // Nearly antipodal points, scaled so that the antipode is at the origin
+            let lam12x = (-slam12).atan2(-clam12);
+            let k2 = sbet1 * sbet1 * Self::EP2;
+            let eps = k2 / (2. * (1. + (1. + k2).sqrt()) + k2);
+            let lamscale = Self::F * cbet1 * a3f(eps) * std::f64::consts::PI;
+            let betscale = lamscale * cbet1;
+            let x = lam12x / lamscale;
+            let y = sbet12a / betscale;
+
+            if y > -Self::TOL1 && x > -1. - xthresh {
+                salp1 = (-x).min(1.);
+                calp1 = -(1. - salp1 * salp1).sqrt();
+            } else {
+                let k = astroid(x, y);
+                let omg12a = lamscale * (-x * k / (1. + k));
+                (somg12, comg12) = omg12a.sin_cos();
+                comg12 = -comg12;
+                salp1 = cbet2 * somg12;
+                calp1 = sbet12a - cbet2 * sbet1 * somg12 * somg12 / (1. - comg12);
+            }
+        }
+
+        if salp1 > 0. {
+            (salp1, calp1) = norm(salp1, calp1);
+        } else {
+            salp1 = 1.;
+            calp1 = 0.;
+        }
+        (sig12, salp1, calp1, salp2, calp2, dnm)
+    }
+
+    // Returns the difference of the longitude reached by the geodesic with an initial azimuth
+    // from the target longitude, and its derivative with respect to the azimuth.
+    #[allow(clippy::too_many_arguments)]
+    fn lambda12(
+        sbet1: f64,
+        cbet1: f64,
+        dn1: f64,
+        sbet2: f64,
+        cbet2: f64,
+        dn2: f64,
+        salp1: f64,
+        calp1: f64,
+        slam120: f64,
+        clam120: f64,
+        diffp: bool,
+        c1a: &mut [f64; Self::ORDER + 1],
+        c2a: &mut [f64; Self::ORDER + 1],
+        c3a: &mut [f64; Self::ORDER],
+    ) -> Lambda12 {
+        let calp1 = if sbet1 == 0. && calp1 == 0. {
+            -Self::TINY
+        } else {
+            calp1
+        };
+        // Azimuth of the geodesic at the equator
+        let salp0 = salp1 * cbet1;
+        let calp0 = calp1.hypot(salp1 * sbet1);
+
+        let somg1 = salp0 * sbet1;
+        let comg1 = calp1 * cbet1;
+        let (ssig1, csig1) = norm(sbet1, comg1);
+
+        let salp2 = if cbet2 != cbet1 { salp0 / cbet2 } else { salp1 };
+        let calp2 = if cbet2 != cbet1 || sbet2.abs() != -sbet1 {
+            ((calp1 * cbet1).powi(2)
+                + if cbet1 < -sbet1 {
+                    (cbet2 - cbet1) * (cbet1 + cbet2)
+                } else {
+                    (sbet1 - sbet2) * (sbet1 + sbet2)
+                })
+            .sqrt()
+                / cbet2
+        } else {
+            calp1.abs()
+        };
+
+        let somg2 = salp0 * sbet2;
+        let comg2 = calp2 * cbet2;
+        let (ssig2, csig2) = norm(sbet2, comg2);
+
+        let sig12 = (csig1 * ssig2 - ssig1 * csig2)
+            .max(0.)
+            .atan2(csig1 * csig2 + ssig1 * ssig2);
+        let somg12 = (comg1 * somg2 - somg1 * comg2).max(0.);
+        let comg12 = comg1 * comg2 + somg1 * somg2;
+        let eta = (somg12 * clam120 - comg12 * slam120).atan2(comg12 * clam120 + somg12 * slam120);
+
+        let k2 = calp0 * calp0 * Self::EP2;
+        let eps = k2 / (2. * (1. + (1. + k2).sqrt()) + k2);
+        c3f(eps, c3a);
+        let b312 = sin_cos_series(ssig2, csig2, c3a) - sin_cos_series(ssig1, csig1, c3a);
+        let domg12 = -Self::F * a3f(eps) * salp0 * (sig12 + b312);
+        let lam12 = eta + domg12;
+
+        let dlam12 = if !diffp {
+            f64::NAN
+        } else if calp2 == 0. {
+            -2. * Self::F1 * dn1 / sbet1
+        } else {
+            let (_, m12b) =
+                Self::lengths(eps, sig12, ssig1, csig1, dn1, ssig2, csig2, dn2, c1a, c2a);
+            m12b * Self::F1 / (calp2 * cbet2)
+        };
+
+        Lambda12 {
+            lam12,
+            salp2,
+            calp2,
+            sig12,
+            ssig1,
+            csig1,
+            ssig2,
+            csig2,
+            eps,
+            dlam12,
+        }
+    }
+}
+
+struct Lambda12 {
+    lam12: f64,
+    salp2: f64,
+    calp2: f64,
+    sig12: f64,
+    ssig1: f64,
+    csig1: f64,
+    ssig2: f64,
+    csig2: f64,
+    eps: f64,
+    dlam12: f64,
+}
+
+// Evaluates the polynomial with the coefficients (highest order first) at x.
+fn polyval(coeffs: &[f64], x: f64) -> f64 {
+    coeffs.iter().fold(0., |y, c| y * x + c)
+}
+
+// Rounds tiny angles so that the algorithm is symmetric in the order of the points.
+fn ang_round(x: f64) -> f64 {
+    const Z: f64 = 1. / 16.;
+    let y = x.abs();
+    let y = if y < Z { Z - (Z - y) } else { y };
+    0. + y.copysign(x)
+}
+
+// Sine and cosine of an angle in degrees, exact for multiples of 90 degrees.
+fn sincosd(x: f64) -> (f64, f64) {
+    let r = x % TWO_PI_DEG;
+    let q = (r / RIGHT_ANG_DEG).round();
+    let (s, c) = (r - q * RIGHT_ANG_DEG).to_radians().sin_cos();
+    match (q as i64).rem_euclid(4) {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    }
+}
+
+fn norm(x: f64, y: f64) -> (f64, f64) {
+    let r = x.hypot(y);
+    (x / r, y / r)
+}
+
+// Clenshaw summation of a sine series with the coefficients c[1..].
+fn sin_cos_series(sinx: f64, cosx: f64, c: &[f64]) -> f64 {
+    let mut k = c.len();
+    let n = k - 1;
+    let ar = 2. * (cosx - sinx) * (cosx + sinx);
+    let mut y1 = 0.;
+    let mut y0 = if n & 1 == 1 {
+        k -= 1;
+        c[k]
+    } else {
+        0.
+    };
+    for _ in 0..n / 2 {
+        k -= 1;
+        y1 = ar * y0 - y1 + c[k];
+        k -= 1;
+        y0 = ar * y1 - y0 + c[k];
+    }
+    2. * sinx * cosx * y0
+}
+
+// Karney (2013) eq. 17
+fn a1m1f(eps: f64) -> f64 {
+    let t = polyval(&[1., 4., 64., 0.], eps * eps) / 256.;
+    (t + eps) / (1. - eps)
+}
+
+// Karney (2013) eq. 18
+fn c1f(eps: f64, c: &mut [f64]) {
+    const COEFFS: [&[f64]; 6] = [
+        &[-1., 6., -16., 32.],
+        &[-9., 64., -128., 2048.],
+        &[9., -16., 768.],
+        &[3., -5., 512.],
+        &[-7., 1280.],
+        &[-7., 2048.],
+    ];
+    series(eps, &COEFFS, c);
+}
+
+// Karney (2013) eq. 42
+fn a2m1f(eps: f64) -> f64 {
+    let t = polyval(&[-11., -28., -192., 0.], eps * eps) / 256.;
+    (t - eps) / (1. + eps)
+}
+
+// Karney (2013) eq. 43
+fn c2f(eps: f64, c: &mut [f64]) {
+    const COEFFS: [&[f64]; 6] = [
+        &[1., 2., 16., 32.],
+        &[35., 64., 384., 2048.],
+        &[15., 80., 768.],
+        &[7., 35., 512.],
+        &[63., 1280.],
+        &[77., 2048.],
+    ];
+    series(eps, &COEFFS, c);
+}
+
+// Sets c[l] to eps^l times the even polynomial in eps with the coefficients (divisor last).
+fn series(eps: f64, coeffs: &[&[f64]], c: &mut [f64]) {
+    let eps2 = eps * eps;
+    let mut d = eps;
+    for (l, coeff) in coeffs.iter().enumerate() {
+        let (divisor, poly) = coeff.split_last().unwrap();
+        c[l + 1] = d * polyval(poly, eps2) / divisor;
+        d *= eps;
+    }
+}
+
+// Karney (2013) eq. 24 coefficients in the third flattening, highest order in eps first
+fn a3x() -> [f64; Geodesic::ORDER] {
+    const COEFFS: [&[f64]; 6] = [
+        &[-3., 128.],
+        &[-2., -3., 64.],
+        &[-1., -3., -1., 16.],
+        &[3., -1., -2., 8.],
+        &[1., -1., 2.],
+        &[1., 1.],
+    ];
+    let mut a3x = [0.; Geodesic::ORDER];
+    for (k, coeff) in COEFFS.iter().enumerate() {
+        let (divisor, poly) = coeff.split_last().unwrap();
+        a3x[k] = polyval(poly, Geodesic::N) / divisor;
+    }
+    a3x
+}
+
+fn a3f(eps: f64) -> f64 {
+    polyval(&a3x(), eps)
+}
+
+// Karney (2013) eq. 25 coefficients in the third flattening
+fn c3x() -> [f64; Geodesic::NC3X] {
+    const COEFFS: [&[f64]; Geodesic::NC3X] = [
+        &[3., 128.],
+        &[2., 5., 128.],
+        &[-1., 3., 3., 64.],
+        &[-1., 0., 1., 8.],
+        &[-1., 1., 4.],
+        &[5., 256.],
+        &[1., 3., 128.],
+        &[-3., -2., 3., 64.],
+        &[1., -3., 2., 32.],
+        &[7., 512.],
+        &[-10., 9., 384.],
+        &[5., -9., 5., 192.],
+        &[7., 512.],
+        &[-14., 7., 512.],
+        &[21., 2560.],
+    ];
+    let mut c3x = [0.; Geodesic::NC3X];
+    for (k, coeff) in COEFFS.iter().enumerate() {
+        let (divisor, poly) = coeff.split_last().unwrap();
+        c3x[k] = polyval(poly, Geodesic::N) / divisor;
+    }
+    c3x
+}
+
+fn c3f(eps: f64, c: &mut [f64]) {
+    let c3x = c3x();
+    let mut mult = 1.;
+    let mut o = 0;
+    for (l, cl) in c.iter_mut().enumerate().skip(1) {
+        let m = Geodesic::ORDER - l;
+        mult *= eps;
+        *cl = mult * polyval(&c3x[o..o + m], eps);
+        o += m;
+    }
+}
+
+// Solves the astroid equation k^4 + 2k^3 - (x^2 + y^2 - 1)k^2 - 2y^2k - y^2 = 0 for the
+// positive root (Karney (2013) eq. 55).
+fn astroid(x: f64, y: f64) -> f64 {
+    let p = x * x;
+    let q = y * y;
+    let r = (p + q - 1.) / 6.;
+    if q == 0. && r <= 0. {
+        return 0.;
+    }
+    let s = p * q / 4.;
+    let r2 = r * r;
+    let r3 = r * r2;
+    let disc = s * (s + 2. * r3);
+    let mut u = r;
+    if disc >= 0. {
+        let mut t3 = s + r3;
+        t3 += if t3 < 0. { -disc.sqrt() } else { disc.sqrt() };
+        let t = t3.cbrt();
+        u += t + if t != 0. { r2 / t } else { 0. };
+    } else {
+        let ang = (-disc).sqrt().atan2(-(s + r3));
+        u += 2. * r * (ang / 3.).cos();
+    }
+    let v = (u * u + q).sqrt();
+    let uv = if u < 0. { q / (v - u) } else { u + v };
+    let w = (uv - q) / (2. * v);
+    uv / ((uv + w * w).sqrt() + w)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn should_inverse() {
+        // Arrange
+        // JFK to CDG, which agrees with Vincenty's method
+        let (lat1, lon1, lat2, lon2) = (40.6, -73.8, 49.01666667, 2.55);
+        // Act
+        let inverse = Geodesic::inverse(lat1, lon1, lat2, lon2);
+        // Assert
+        assert_approx_eq!(f64, 53.47021824, inverse.azi1, epsilon = 0.00000001);
+        assert_approx_eq!(f64, 5853226.256, inverse.dist, epsilon = 0.001);
+    }
+
+    #[test]
+    fn should_inverse_nearly_antipodal() {
+        // Arrange
+        // Karney (2013) Table 3, where Vincenty's method fails to converge
+        let (lat1, lon1, lat2, lon2) = (-30., 0., 29.9, 179.8);
+        // Act
+        let inverse = Geodesic::inverse(lat1, lon1, lat2, lon2);
+        // Assert
+        assert_approx_eq!(f64, 161.890524736, inverse.azi1, epsilon = 0.000000001);
+        assert_approx_eq!(f64, 19989832.82761, inverse.dist, epsilon = 0.00001);
+    }
+
+    #[test]
+    fn should_inverse_meridian() {
+        // Arrange
+        let (lat1, lon1, lat2, lon2) = (0., 10., 90., 10.);
+        // Act
+        let inverse = Geodesic::inverse(lat1, lon1, lat2, lon2);
+        // Assert
+        assert_approx_eq!(f64, 0., inverse.azi1, epsilon = 0.000000001);
+        assert_approx_eq!(f64, 10001965.729, inverse.dist, epsilon = 0.001);
+    }
+
+    #[test]
+    fn should_great_circle_distance() {
+        // Arrange
+        let (lat1, lon1, lat2, lon2) = (0., 0., 0., 90.);
+        // Act
+        let dist = Geodesic::great_circle_distance(lat1, lon1, lat2, lon2);
+        // Assert
+        assert_approx_eq!(
+            f64,
+            Geodesic::MEAN_RADIUS * std::f64::consts::FRAC_PI_2,
+            dist,
+            epsilon = 0.000001
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Obstructed horizon profiles.
+//!
+//! A [`HorizonProfile`] records the elevation angle of the visible horizon (mountains,
+//! buildings) at azimuths around a geographical location, such as in a valley where the Sun
+//! disappears behind the mountains well before it sets astronomically. It is loaded from CSV
+//! lines of an azimuth in degrees clockwise from true North followed by the elevation angle in
+//! degrees of the horizon at that azimuth, and is linearly interpolated between azimuths.
+//!
+//! ```text
+//! # azimuth,elevation
+//! 0,2.5
+//! 90,8
+//! 180,4
+//! 270,12.5
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    angle::{LimitAngle, RIGHT_ANG_DEG, TWO_PI_DEG},
+    ParseError,
+};
+
+/// The elevation angles of the visible horizon at azimuths around a geographical location.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HorizonProfile {
+    // (azimuth, elevation angle) pairs in degrees sorted by azimuth
+    points: Vec<(f64, f64)>,
+}
+
+impl HorizonProfile {
+    /// Creates a new `HorizonProfile` from (azimuth, elevation angle) pairs in degrees, or a
+    /// [`ParseError`] when there are no pairs or an elevation angle is not within
+    /// [-90. ..=90.].
+    pub fn new(points: Vec<(f64, f64)>) -> Result<Self, ParseError> {
+        if points.is_empty() {
+            return Err(ParseError("The horizon profile has no points".to_string()));
+        }
+        let mut points = points
+            .into_iter()
+            .map(|(azimuth, elevation)| {
+                if elevation.is_finite() && elevation.abs() <= RIGHT_ANG_DEG && azimuth.is_finite()
+                {
+                    Ok((azimuth.cap_angle_360(), elevation))
+                } else {
+                    Err(ParseError(format!(
+                        "Invalid horizon profile point {},{}",
+                        azimuth, elevation
+                    )))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        points.sort_by(|x, y| x.0.total_cmp(&y.0));
+        Ok(Self { points })
+    }
+
+    /// Creates a new `HorizonProfile` from CSV lines of an azimuth followed by an elevation
+    /// angle in degrees, ignoring blank lines, lines starting with `#`, and a header line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use islamic_prayer_times::*;
+    ///
+    /// let csv = "azimuth,elevation\n0,2.5\n90,8\n180,4\n270,12.5\n";
+    ///
+    /// let horizon_profile = HorizonProfile::from_csv(csv)?;
+    ///
+    /// assert_eq!(8.25, horizon_profile.elevation_at(225.));
+    /// # Ok::<(), Box<dyn Error>>(())
+    /// ```
+    pub fn from_csv(csv: &str) -> Result<Self, ParseError> {
+        let mut points = Vec::new();
+        let mut header = false;
+        for (index, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let values = line
+                .split(',')
+                .map(|x| x.trim().parse::<f64>())
+                .collect::<Result<Vec<_>, _>>();
+            match values.as_deref() {
+                Ok([azimuth, elevation]) => points.push((*azimuth, *elevation)),
+                // A header line before any points
+                Err(_) if points.is_empty() && !header => header = true,
+                _ => {
+                    return Err(ParseError(format!(
+                        "Invalid horizon profile line {}: {}",
+                        index + 1,
+                        line
+                    )))
+                }
+            }
+        }
+        Self::new(points)
+    }
+
+    /// Returns the elevation angle in degrees of the horizon at an azimuth in degrees
+    /// clockwise from true North, linearly interpolated between the nearest azimuths of
+    /// the profile on either side.
+    pub fn elevation_at(&self, azimuth: f64) -> f64 {
+        let azimuth = azimuth.cap_angle_360();
+        let next = self.points.partition_point(|x| x.0 <= azimuth);
+        // Neighbouring points wrapping around North
+        let (prev_az, prev_elev) = if next == 0 {
+            let (az, elev) = self.points[self.points.len() - 1];
+            (az - TWO_PI_DEG, elev)
+        } else {
+            self.points[next - 1]
+        };
+        let (next_az, next_elev) = if next == self.points.len() {
+            let (az, elev) = self.points[0];
+            (az + TWO_PI_DEG, elev)
+        } else {
+            self.points[next]
+        };
+        if next_az == prev_az {
+            prev_elev
+        } else {
+            prev_elev + (next_elev - prev_elev) * (azimuth - prev_az) / (next_az - prev_az)
+        }
+    }
+}
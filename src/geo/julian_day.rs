@@ -1,6 +1,6 @@
 use std::ops::{Add, Sub};
 
-use chrono::{Datelike, Days, NaiveDate};
+use chrono::{Datelike, Days, Duration, NaiveDate, NaiveDateTime, Timelike};
 
 use super::coordinates::Gmt;
 
@@ -12,6 +12,13 @@ pub struct JulianDay {
 }
 
 impl JulianDay {
+    // Julian day of the Unix epoch (1970-01-01 00:00:00 UT).
+    const UNIX_EPOCH: f64 = 2440587.5;
+    const SECS_PER_DAY: f64 = 86400.;
+
+    // Returns the `JulianDay` at local midnight of a date for a Gmt. The date is in the proleptic
+    // Gregorian calendar with astronomical year numbering of `NaiveDate`, also before the
+    // Gregorian reform of 1582.
     pub fn new(date: NaiveDate, gmt: Gmt) -> Self {
         let mut new_year = date.year() as f64;
         let mut new_month = date.month() as f64;
@@ -20,17 +27,9 @@ impl JulianDay {
             new_month += 12.;
         }
 
-        if date.year() < 1 {
-            new_year += 1.;
-        }
-
-        let mut b = 0.;
-        if date.year() > 1582
-            || date.year() == 1582 && (date.month() > 10 || date.month() == 10 && date.day() > 15)
-        {
-            let a = (new_year / 100.).floor();
-            b = 2. - a + (a / 4.).floor();
-        }
+        // Astronomical Algorithms pg. 61 (7.1)
+        let a = (new_year / 100.).floor();
+        let b = 2. - a + (a / 4.).floor();
 
         let c = (365.25 * (new_year + 4716.)).floor();
         let d = (30.6001 * (new_month + 1.)).floor();
@@ -39,6 +38,18 @@ impl JulianDay {
         Self { date, gmt, value }
     }
 
+    // Returns the Julian day value of a local date and time for a Gmt.
+    pub fn from_date_time(date_time: NaiveDateTime, gmt: Gmt) -> f64 {
+        let julian_day = Self::new(date_time.date(), gmt);
+        julian_day.value + date_time.num_seconds_from_midnight() as f64 / Self::SECS_PER_DAY
+    }
+
+    // Returns the local date and time (to the nearest second) of a Julian day value for a Gmt.
+    pub fn to_date_time(value: f64, gmt: Gmt) -> NaiveDateTime {
+        let secs = ((value - Self::UNIX_EPOCH + f64::from(gmt) / 24.) * Self::SECS_PER_DAY).round();
+        NaiveDateTime::UNIX_EPOCH + Duration::seconds(secs as i64)
+    }
+
     pub fn sub(&self, days: u64) -> Self {
         Self {
             date: self.date.sub(Days::new(days)),
@@ -85,6 +96,60 @@ mod tests {
         assert_eq!(date, julian_day.date);
     }
 
+    #[test]
+    fn should_return_date_time() {
+        // Arrange
+        let date = NaiveDate::from_ymd_opt(2022, 12, 4).unwrap();
+        let gmt = Gmt::try_from(-4.).unwrap();
+        let julian_day = JulianDay::new(date, gmt);
+        // Act
+        let date_time = JulianDay::to_date_time(julian_day.value + 0.25, gmt);
+        // Assert
+        assert_eq!(date.and_hms_opt(6, 0, 0).unwrap(), date_time);
+    }
+
+    #[test]
+    fn should_round_trip_proleptic_gregorian_date() {
+        // Arrange
+        let gmt = Gmt::try_from(3.).unwrap();
+        let dates = [
+            NaiveDate::from_ymd_opt(-500, 2, 28).unwrap(),
+            NaiveDate::from_ymd_opt(0, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(1024, 3, 15).unwrap(),
+            NaiveDate::from_ymd_opt(1582, 10, 10).unwrap(),
+            NaiveDate::from_ymd_opt(2022, 12, 4).unwrap(),
+        ];
+        // Act
+        // Assert
+        for date in dates {
+            let julian_day = JulianDay::new(date, gmt);
+            let date_time = JulianDay::to_date_time(julian_day.value, gmt);
+            assert_eq!(date.and_hms_opt(0, 0, 0).unwrap(), date_time);
+        }
+    }
+
+    #[test]
+    fn should_new_before_gregorian_reform() {
+        // Arrange
+        let date = NaiveDate::from_ymd_opt(1024, 3, 15).unwrap();
+        // Act
+        let julian_day = JulianDay::new(date, Gmt::default());
+        // Assert
+        // The proleptic Gregorian 1024-03-15 is the Julian 1024-03-09.
+        assert_approx_eq!(f64, 2095141.5, julian_day.value, epsilon = EPSILON_TEST);
+    }
+
+    #[test]
+    fn should_return_julian_day_value_from_date_time() {
+        // Arrange
+        let date = NaiveDate::from_ymd_opt(2022, 12, 4).unwrap();
+        let gmt = Gmt::try_from(-4.).unwrap();
+        // Act
+        let value = JulianDay::from_date_time(date.and_hms_opt(6, 0, 0).unwrap(), gmt);
+        // Assert
+        assert_approx_eq!(f64, 2459917.91666667, value, epsilon = EPSILON_TEST);
+    }
+
     #[test]
     fn should_return_prev_julian_day() {
         // Arrange
@@ -0,0 +1,245 @@
+//! Magnetic declination of geographical [`Coordinates`] from the World Magnetic Model.
+//!
+//! A compass points to magnetic North, which differs from true North by the magnetic
+//! declination (positive when magnetic North is East of true North). The declination is
+//! computed from the embedded coefficients of the World Magnetic Model (WMM) 2025 of the US
+//! National Geospatial-Intelligence Agency and the UK Defence Geographic Centre, which are
+//! extrapolated with their secular variation from the epoch 2025.0. The model is only valid
+//! from 2025 to 2030, so no declination is given for a date outside of it.
+
+use chrono::{Datelike, NaiveDate};
+
+use super::{coordinates::Coordinates, geodesic::Geodesic};
+
+/// Returns the magnetic declination in degrees (positive East) at geographical
+/// [`Coordinates`] on a [`NaiveDate`], or `None` when the date is outside the validity window
+/// of the World Magnetic Model.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// use chrono::NaiveDate;
+/// use islamic_prayer_times::*;
+///
+/// let latitude = Latitude::try_from(39.)?;
+/// let longitude = Longitude::try_from(-77.)?;
+/// let elevation = Elevation::try_from(0.)?;
+/// let coords = Coordinates::new(latitude, longitude, elevation);
+/// let date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+///
+/// let declination = magnetic_declination(coords, date).unwrap();
+///
+/// assert!(declination < -10. && declination > -11.);
+/// assert!(magnetic_declination(coords, NaiveDate::from_ymd_opt(2031, 1, 1).unwrap()).is_none());
+/// # Ok::<(), Box<dyn Error>>(())
+/// ```
+pub fn magnetic_declination(coords: Coordinates, date: NaiveDate) -> Option<f64> {
+    let days_in_year = NaiveDate::from_ymd_opt(date.year(), 12, 31)
+        .unwrap()
+        .ordinal() as f64;
+    let year = date.year() as f64 + date.ordinal0() as f64 / days_in_year;
+    if (MagneticModel::EPOCH..MagneticModel::EPOCH + MagneticModel::VALID_YEARS).contains(&year) {
+        Some(MagneticModel::declination(coords, year))
+    } else {
+        None
+    }
+}
+
+struct MagneticModel;
+
+impl MagneticModel {
+    const EPOCH: f64 = 2025.;
+    // Years from the epoch the model is valid for
+    const VALID_YEARS: f64 = 5.;
+    const DEGREE: usize = 12;
+    // Geomagnetic reference radius in kilometers
+    const REFERENCE_RADIUS: f64 = 6371.2;
+
+    // WMM2025 Schmidt semi-normalized spherical harmonic coefficients
+    // Degree n, order m, g and h in nT, and their secular variation in nT per year
+    const COEFFS: [(usize, usize, f64, f64, f64, f64); 90] = [
+        (1, 0, -29351.8, 0., 12., 0.),
+        (1, 1, -1410.8, 4545.4, 9.7, -21.5),
+        (2, 0, -2556.6, 0., -11.6, 0.),
+        (2, 1, 2951.1, -3133.6, -5.2, -27.7),
+        (2, 2, 1649.3, -815.1, -8., -12.1),
+        (3, 0, 1361., 0., -1.3, 0.),
+        (3, 1, -2404.1, -56.6, -4.2, 4.),
+        (3, 2, 1243.8, 237.5, 0.4, -0.3),
+        (3, 3, 453.6, -549.5, -15.6, -4.1),
+        (4, 0, 895., 0., -1.6, 0.),
+        (4, 1, 799.5, 278.6, -2.4, -1.1),
+        (4, 2, 55.7, -133.9, -6., 4.1),
+        (4, 3, -281.1, 212., 5.6, 1.6),
+        (4, 4, 12.1, -375.6, -7., -4.4),
+        (5, 0, -233.2, 0., 0.6, 0.),
+        (5, 1, 368.9, 45.4, 1.4, -0.5),
+        (5, 2, 187.2, 220.2, 0., 2.2),
+        (5, 3, -138.7, -122.9, 0.6, 0.4),
+        (5, 4, -142., 43., 2.2, 1.7),
+        (5, 5, 20.9, 106.1, 0.9, 1.9),
+        (6, 0, 64.4, 0., -0.2, 0.),
+        (6, 1, 63.8, -18.4, -0.4, 0.3),
+        (6, 2, 76.9, 16.8, 0.9, -1.6),
+        (6, 3, -115.7, 48.8, 1.2, -0.4),
+        (6, 4, -40.9, -59.8, -0.9, 0.9),
+        (6, 5, 14.9, 10.9, 0.3, 0.7),
+        (6, 6, -60.7, 72.7, 0.9, 0.9),
+        (7, 0, 79.5, 0., 0., 0.),
+        (7, 1, -77., -48.9, -0.1, 0.6),
+        (7, 2, -8.8, -14.4, -0.1, 0.5),
+        (7, 3, 59.3, -1., 0.5, -0.8),
+        (7, 4, 15.8, 23.4, -0.1, 0.),
+        (7, 5, 2.5, -7.4, -0.8, -1.),
+        (7, 6, -11.1, -25.1, -0.8, 0.6),
+        (7, 7, 14.2, -2.3, 0.8, -0.2),
+        (8, 0, 23.2, 0., -0.1, 0.),
+        (8, 1, 10.8, 7.1, 0.2, -0.2),
+        (8, 2, -17.5, -12.6, 0., 0.5),
+        (8, 3, 2., 11.4, 0.5, -0.4),
+        (8, 4, -21.7, -9.7, -0.1, 0.4),
+        (8, 5, 16.9, 12.7, 0.3, -0.5),
+        (8, 6, 15., 0.7, 0.2, -0.6),
+        (8, 7, -16.8, -5.2, 0., 0.3),
+        (8, 8, 0.9, 3.9, 0.2, 0.2),
+        (9, 0, 4.6, 0., 0., 0.),
+        (9, 1, 7.8, -24.8, -0.1, -0.3),
+        (9, 2, 3., 12.2, 0.1, 0.3),
+        (9, 3, -0.2, 8.3, 0.3, -0.3),
+        (9, 4, -2.5, -3.4, -0.3, 0.3),
+        (9, 5, -13.1, -5.3, 0., 0.2),
+        (9, 6, 2.4, 7.2, 0.3, -0.1),
+        (9, 7, 8.6, -0.6, -0.1, -0.2),
+        (9, 8, -8.7, 0.8, 0.1, 0.4),
+        (9, 9, -12.9, 10., -0.1, 0.1),
+        (10, 0, -1.3, 0., 0.1, 0.),
+        (10, 1, -6.4, 3.3, 0., 0.),
+        (10, 2, 0.2, 0., 0., 0.),
+        (10, 3, 2., 2.4, 0.1, -0.2),
+        (10, 4, -1., 5.3, 0., 0.1),
+        (10, 5, -0.6, -9.1, -0.3, -0.1),
+        (10, 6, -0.9, 0.4, 0., 0.1),
+        (10, 7, 1.5, -4.2, -0.1, 0.),
+        (10, 8, 0.9, -3.8, -0.1, -0.1),
+        (10, 9, -2.7, 0.9, 0., 0.2),
+        (10, 10, -3.9, -9.1, 0., 0.),
+        (11, 0, 2.9, 0., 0., 0.),
+        (11, 1, -1.5, 0., 0., 0.),
+        (11, 2, -2.5, 2.9, 0., 0.1),
+        (11, 3, 2.4, -0.6, 0., 0.),
+        (11, 4, -0.6, 0.2, 0., 0.1),
+        (11, 5, -0.1, 0.5, -0.1, 0.),
+        (11, 6, -0.6, -0.3, 0., 0.),
+        (11, 7, -0.1, -1.2, 0., 0.1),
+        (11, 8, 1.1, -1.7, -0.1, 0.),
+        (11, 9, -1., -2.9, -0.1, 0.),
+        (11, 10, -0.2, -1.8, -0.1, 0.),
+        (11, 11, 2.6, -2.3, -0.1, 0.),
+        (12, 0, -2., 0., 0., 0.),
+        (12, 1, -0.2, -1.3, 0., 0.),
+        (12, 2, 0.3, 0.7, 0., 0.),
+        (12, 3, 1.2, 1., 0., -0.1),
+        (12, 4, -1.3, -1.4, 0., 0.1),
+        (12, 5, 0.6, 0., 0., 0.),
+        (12, 6, 0.6, 0.6, 0.1, 0.),
+        (12, 7, 0.5, -0.1, 0., 0.),
+        (12, 8, -0.1, 0.8, 0., 0.),
+        (12, 9, -0.4, 0.1, 0., 0.),
+        (12, 10, -0.2, -1., -0.1, 0.),
+        (12, 11, -1.3, 0.1, 0., 0.),
+        (12, 12, -0.7, 0.2, -0.1, -0.1),
+    ];
+
+    // Returns the declination in degrees for a decimal year.
+    // The World Magnetic Model 2025 Technical Report, section 1.2
+    fn declination(coords: Coordinates, year: f64) -> f64 {
+        let lat_rads = f64::from(coords.latitude).to_radians();
+        let lon_rads = f64::from(coords.longitude).to_radians();
+        let height = f64::from(coords.elevation) / 1000.;
+
+        // Geodetic to geocentric spherical coordinates (eq. 17)
+        let a = Geodesic::A / 1000.;
+        let e2 = Geodesic::F * (2. - Geodesic::F);
+        let rc = a / (1. - e2 * lat_rads.sin().powi(2)).sqrt();
+        let p = (rc + height) * lat_rads.cos();
+        let z = (rc * (1. - e2) + height) * lat_rads.sin();
+        let r = p.hypot(z);
+        let geocentric_lat_rads = (z / r).asin();
+
+        // Colatitude
+        let theta = std::f64::consts::FRAC_PI_2 - geocentric_lat_rads;
+        let (legendre, legendre_deriv) = Self::legendre(theta);
+
+        // Field components in geocentric spherical coordinates (eq. 10-12)
+        let dt = year - Self::EPOCH;
+        let (mut x, mut y, mut z) = (0., 0., 0.);
+        for (n, m, g, h, g_dot, h_dot) in Self::COEFFS {
+            let g = g + dt * g_dot;
+            let h = h + dt * h_dot;
+            let ratio = (Self::REFERENCE_RADIUS / r).powi(n as i32 + 2);
+            let (sin_ml, cos_ml) = (m as f64 * lon_rads).sin_cos();
+            x += ratio * (g * cos_ml + h * sin_ml) * legendre_deriv[n][m];
+            y += ratio * m as f64 * (g * sin_ml - h * cos_ml) * legendre[n][m];
+            z -= ratio * (n as f64 + 1.) * (g * cos_ml + h * sin_ml) * legendre[n][m];
+        }
+        let y = y / theta.sin();
+
+        // Rotation of the northward component to geodetic coordinates (eq. 17)
+        let psi = geocentric_lat_rads - lat_rads;
+        let x = x * psi.cos() - z * psi.sin();
+
+        y.atan2(x).to_degrees()
+    }
+
+    // Returns the Schmidt semi-normalized associated Legendre functions of the cosine of
+    // the colatitude and their derivatives with respect to the colatitude.
+    #[allow(clippy::type_complexity)]
+    fn legendre(
+        theta: f64,
+    ) -> (
+        [[f64; Self::DEGREE + 1]; Self::DEGREE + 1],
+        [[f64; Self::DEGREE + 1]; Self::DEGREE + 1],
+    ) {
+        let (sin_t, cos_t) = theta.sin_cos();
+        let mut p = [[0.; Self::DEGREE + 1]; Self::DEGREE + 1];
+        let mut dp = [[0.; Self::DEGREE + 1]; Self::DEGREE + 1];
+        let mut schmidt = [[0.; Self::DEGREE + 1]; Self::DEGREE + 1];
+        p[0][0] = 1.;
+        schmidt[0][0] = 1.;
+
+        for n in 1..=Self::DEGREE {
+            schmidt[n][0] = schmidt[n - 1][0] * (2 * n - 1) as f64 / n as f64;
+            for m in 1..=n {
+                let delta = if m == 1 { 2. } else { 1. };
+                schmidt[n][m] =
+                    schmidt[n][m - 1] * ((n - m + 1) as f64 * delta / (n + m) as f64).sqrt();
+            }
+            for m in 0..=n {
+                if n == m {
+                    p[n][m] = sin_t * p[n - 1][m - 1];
+                    dp[n][m] = sin_t * dp[n - 1][m - 1] + cos_t * p[n - 1][m - 1];
+                } else {
+                    // Gauss normalized recursion
+                    let (k, p2, dp2) = if n >= m + 2 {
+                        let k =
+                            ((n - 1).pow(2) - m.pow(2)) as f64 / ((2 * n - 1) * (2 * n - 3)) as f64;
+                        (k, p[n - 2][m], dp[n - 2][m])
+                    } else {
+                        (0., 0., 0.)
+                    };
+                    p[n][m] = cos_t * p[n - 1][m] - k * p2;
+                    dp[n][m] = cos_t * dp[n - 1][m] - sin_t * p[n - 1][m] - k * dp2;
+                }
+            }
+        }
+
+        for n in 0..=Self::DEGREE {
+            for m in 0..=n {
+                p[n][m] *= schmidt[n][m];
+                dp[n][m] *= schmidt[n][m];
+            }
+        }
+        (p, dp)
+    }
+}
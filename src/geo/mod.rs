@@ -1,10 +1,25 @@
 pub mod coordinates;
+pub mod delta_t;
+pub mod eclipse;
+pub mod ephemeris;
+pub mod horizon;
+pub mod magnetic;
 pub mod qibla;
+pub mod sun;
 pub mod weather;
 
 pub use coordinates::*;
+pub use delta_t::*;
+pub use eclipse::*;
+pub use ephemeris::*;
+pub use horizon::*;
+pub use magnetic::*;
 pub use qibla::*;
+pub use sun::*;
 pub use weather::*;
 
 pub(crate) mod astro;
+pub(crate) mod geodesic;
 pub(crate) mod julian_day;
+pub(crate) mod moon;
+pub(crate) mod root;
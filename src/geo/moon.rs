@@ -0,0 +1,290 @@
+use crate::angle::LimitAngle;
+
+use super::astro::Astro;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Moon {
+    dist: f64, // Distance between the centers of the Earth and the Moon in kilometers
+    dec: f64,  // Declination
+    ra: f64,   // Right Ascension
+}
+
+impl Moon {
+    const TEN_POW_SIX: f64 = 10_i32.pow(6) as f64;
+
+    // Astronomical Algorithms pg. 339-340 (Table 47.A)
+    // Multiples of D, M, M', F and coefficients of the sine (longitude) and cosine (distance)
+    const LR: [(i8, i8, i8, i8, f64, f64); 60] = [
+        (0, 0, 1, 0, 6288774., -20905355.),
+        (2, 0, -1, 0, 1274027., -3699111.),
+        (2, 0, 0, 0, 658314., -2955968.),
+        (0, 0, 2, 0, 213618., -569925.),
+        (0, 1, 0, 0, -185116., 48888.),
+        (0, 0, 0, 2, -114332., -3149.),
+        (2, 0, -2, 0, 58793., 246158.),
+        (2, -1, -1, 0, 57066., -152138.),
+        (2, 0, 1, 0, 53322., -170733.),
+        (2, -1, 0, 0, 45758., -204586.),
+        (0, 1, -1, 0, -40923., -129620.),
+        (1, 0, 0, 0, -34720., 108743.),
+        (0, 1, 1, 0, -30383., 104755.),
+        (2, 0, 0, -2, 15327., 10321.),
+        (0, 0, 1, 2, -12528., 0.),
+        (0, 0, 1, -2, 10980., 79661.),
+        (4, 0, -1, 0, 10675., -34782.),
+        (0, 0, 3, 0, 10034., -23210.),
+        (4, 0, -2, 0, 8548., -21636.),
+        (2, 1, -1, 0, -7888., 24208.),
+        (2, 1, 0, 0, -6766., 30824.),
+        (1, 0, -1, 0, -5163., -8379.),
+        (1, 1, 0, 0, 4987., -16675.),
+        (2, -1, 1, 0, 4036., -12831.),
+        (2, 0, 2, 0, 3994., -10445.),
+        (4, 0, 0, 0, 3861., -11650.),
+        (2, 0, -3, 0, 3665., 14403.),
+        (0, 1, -2, 0, -2689., -7003.),
+        (2, 0, -1, 2, -2602., 0.),
+        (2, -1, -2, 0, 2390., 10056.),
+        (1, 0, 1, 0, -2348., 6322.),
+        (2, -2, 0, 0, 2236., -9884.),
+        (0, 1, 2, 0, -2120., 5751.),
+        (0, 2, 0, 0, -2069., 0.),
+        (2, -2, -1, 0, 2048., -4950.),
+        (2, 0, 1, -2, -1773., 4130.),
+        (2, 0, 0, 2, -1595., 0.),
+        (4, -1, -1, 0, 1215., -3958.),
+        (0, 0, 2, 2, -1110., 0.),
+        (3, 0, -1, 0, -892., 3258.),
+        (2, 1, 1, 0, -810., 2616.),
+        (4, -1, -2, 0, 759., -1897.),
+        (0, 2, -1, 0, -713., -2117.),
+        (2, 2, -1, 0, -700., 2354.),
+        (2, 1, -2, 0, 691., 0.),
+        (2, -1, 0, -2, 596., 0.),
+        (4, 0, 1, 0, 549., -1423.),
+        (0, 0, 4, 0, 537., -1117.),
+        (4, -1, 0, 0, 520., -1571.),
+        (1, 0, -2, 0, -487., -1739.),
+        (2, 1, 0, -2, -399., 0.),
+        (0, 0, 2, -2, -381., -4421.),
+        (1, 1, 1, 0, 351., 0.),
+        (3, 0, -2, 0, -340., 0.),
+        (4, 0, -3, 0, 330., 0.),
+        (2, -1, 2, 0, 327., 0.),
+        (0, 2, 1, 0, -323., 1165.),
+        (1, 1, -1, 0, 299., 0.),
+        (2, 0, 3, 0, 294., 0.),
+        (2, 0, -1, -2, 0., 8752.),
+    ];
+
+    // Astronomical Algorithms pg. 341 (Table 47.B)
+    // Multiples of D, M, M', F and coefficients of the sine (latitude)
+    const B: [(i8, i8, i8, i8, f64); 60] = [
+        (0, 0, 0, 1, 5128122.),
+        (0, 0, 1, 1, 280602.),
+        (0, 0, 1, -1, 277693.),
+        (2, 0, 0, -1, 173237.),
+        (2, 0, -1, 1, 55413.),
+        (2, 0, -1, -1, 46271.),
+        (2, 0, 0, 1, 32573.),
+        (0, 0, 2, 1, 17198.),
+        (2, 0, 1, -1, 9266.),
+        (0, 0, 2, -1, 8822.),
+        (2, -1, 0, -1, 8216.),
+        (2, 0, -2, -1, 4324.),
+        (2, 0, 1, 1, 4200.),
+        (2, 1, 0, -1, -3359.),
+        (2, -1, -1, 1, 2463.),
+        (2, -1, 0, 1, 2211.),
+        (2, -1, -1, -1, 2065.),
+        (0, 1, -1, -1, -1870.),
+        (4, 0, -1, -1, 1828.),
+        (0, 1, 0, 1, -1794.),
+        (0, 0, 0, 3, -1749.),
+        (0, 1, -1, 1, -1565.),
+        (1, 0, 0, 1, -1491.),
+        (0, 1, 1, 1, -1475.),
+        (0, 1, 1, -1, -1410.),
+        (0, 1, 0, -1, -1344.),
+        (1, 0, 0, -1, -1335.),
+        (0, 0, 3, 1, 1107.),
+        (4, 0, 0, -1, 1021.),
+        (4, 0, -1, 1, 833.),
+        (0, 0, 1, -3, 777.),
+        (4, 0, -2, 1, 671.),
+        (2, 0, 0, -3, 607.),
+        (2, 0, 2, -1, 596.),
+        (2, -1, 1, -1, 491.),
+        (2, 0, -2, 1, -451.),
+        (0, 0, 3, -1, 439.),
+        (2, 0, 2, 1, 422.),
+        (2, 0, -3, -1, 421.),
+        (2, 1, -1, 1, -366.),
+        (2, 1, 0, 1, -351.),
+        (4, 0, 0, 1, 331.),
+        (2, -1, 1, 1, 315.),
+        (2, -2, 0, -1, 302.),
+        (0, 0, 1, 3, -283.),
+        (2, 1, 1, -1, -229.),
+        (1, 1, 0, -1, 223.),
+        (1, 1, 0, 1, 223.),
+        (0, 1, -2, -1, -220.),
+        (2, 1, -1, -1, -220.),
+        (1, 0, 1, 1, -185.),
+        (2, -1, -2, -1, 181.),
+        (0, 1, 2, 1, -177.),
+        (4, 0, -2, -1, 176.),
+        (4, -1, -1, -1, 166.),
+        (1, 0, 1, -1, -164.),
+        (4, 0, 1, -1, 132.),
+        (1, 0, -1, -1, -119.),
+        (4, -1, 0, -1, 115.),
+        (2, -2, 0, 1, 107.),
+    ];
+
+    pub fn dist(&self) -> f64 {
+        self.dist
+    }
+
+    pub fn dec(&self) -> f64 {
+        self.dec
+    }
+
+    pub fn ra(&self) -> f64 {
+        self.ra
+    }
+
+    // Returns the `Moon` at a Julian day in UT, evaluating its position in TT which is delta_t
+    // seconds ahead of it.
+    pub fn new(julian_day: f64, delta_t: f64) -> Self {
+        let astro = Astro::new(julian_day, delta_t);
+        let (lon, lat, dist) = Self::calc_geometric(julian_day + delta_t / Astro::SECS_PER_DAY);
+
+        // Apparent ecliptical longitude
+        let lon = (lon + astro.nut_lon()).cap_angle_360();
+        let l = lon.to_radians();
+        let b = lat.to_radians();
+        let e = astro.obl();
+
+        // Astronomical Algorithms pg. 93 (13.3)
+        let ran = l.sin() * e.cos() - b.tan() * e.sin();
+        let ra = ran.atan2(l.cos()).to_degrees().cap_angle_360();
+
+        // Astronomical Algorithms pg. 93 (13.4)
+        let dec = (b.sin() * e.cos() + b.cos() * e.sin() * l.sin())
+            .asin()
+            .to_degrees();
+
+        Self { dist, dec, ra }
+    }
+
+    // Returns the geometric ecliptical longitude and latitude in degrees and the distance in kilometers.
+    fn calc_geometric(julian_day: f64) -> (f64, f64, f64) {
+        // Astronomical Algorithms pg. 337-342
+        let jc = (julian_day - 2451545.) / 36525.;
+        let jc2 = jc * jc;
+        let jc3 = jc2 * jc;
+        let jc4 = jc3 * jc;
+
+        // Astronomical Algorithms pg. 338 (47.1) (Mean longitude of the Moon)
+        let l_p =
+            218.3164477 + 481267.88123421 * jc - 0.0015786 * jc2 + jc3 / 538841. - jc4 / 65194000.;
+        // Astronomical Algorithms pg. 338 (47.2) (Mean elongation of the Moon)
+        let d =
+            297.8501921 + 445267.1114034 * jc - 0.0018819 * jc2 + jc3 / 545868. - jc4 / 113065000.;
+        // Astronomical Algorithms pg. 338 (47.3) (Mean anomaly of the Sun)
+        let m = 357.5291092 + 35999.0502909 * jc - 0.0001536 * jc2 + jc3 / 24490000.;
+        // Astronomical Algorithms pg. 338 (47.4) (Mean anomaly of the Moon)
+        let m_p =
+            134.9633964 + 477198.8675055 * jc + 0.0087414 * jc2 + jc3 / 69699. - jc4 / 14712000.;
+        // Astronomical Algorithms pg. 338 (47.5) (Moon's argument of latitude)
+        let f =
+            93.2720950 + 483202.0175233 * jc - 0.0036539 * jc2 - jc3 / 3526000. + jc4 / 863310000.;
+        // Astronomical Algorithms pg. 338 (Action of Venus, Jupiter and flattening of the Earth)
+        let a1 = (119.75 + 131.849 * jc).to_radians();
+        let a2 = (53.09 + 479264.290 * jc).to_radians();
+        let a3 = (313.45 + 481266.484 * jc).to_radians();
+        // Astronomical Algorithms pg. 338 (47.6) (Eccentricity of the Earth's orbit)
+        let ecc = 1. - 0.002516 * jc - 0.0000074 * jc2;
+
+        let l_p = l_p.cap_angle_360();
+        let xi_arr = [
+            d.cap_angle_360(),
+            m.cap_angle_360(),
+            m_p.cap_angle_360(),
+            f.cap_angle_360(),
+        ];
+        let arg = |cs: [i8; 4]| {
+            let xi_sum = xi_arr
+                .iter()
+                .enumerate()
+                .fold(0., |acc, (idx, xi)| acc + *xi * cs[idx] as f64);
+            // Terms containing M are multiplied by E for each multiple of M.
+            (xi_sum.to_radians(), ecc.powi(cs[1].abs() as i32))
+        };
+
+        let (mut lsum, rsum) =
+            Self::LR
+                .iter()
+                .fold((0., 0.), |acc, (cd, cm, cm_p, cf, l_coeff, r_coeff)| {
+                    let (xi_sum_rads, ecc_mult) = arg([*cd, *cm, *cm_p, *cf]);
+                    (
+                        acc.0 + l_coeff * ecc_mult * xi_sum_rads.sin(),
+                        acc.1 + r_coeff * ecc_mult * xi_sum_rads.cos(),
+                    )
+                });
+        let mut bsum = Self::B.iter().fold(0., |acc, (cd, cm, cm_p, cf, b_coeff)| {
+            let (xi_sum_rads, ecc_mult) = arg([*cd, *cm, *cm_p, *cf]);
+            acc + b_coeff * ecc_mult * xi_sum_rads.sin()
+        });
+
+        let l_p_rads = l_p.to_radians();
+        let f_rads = xi_arr[3].to_radians();
+        let m_p_rads = xi_arr[2].to_radians();
+        // Astronomical Algorithms pg. 338 (Additive terms)
+        lsum += 3958. * a1.sin() + 1962. * (l_p_rads - f_rads).sin() + 318. * a2.sin();
+        bsum += -2235. * l_p_rads.sin()
+            + 382. * a3.sin()
+            + 175. * (a1 - f_rads).sin()
+            + 175. * (a1 + f_rads).sin()
+            + 127. * (l_p_rads - m_p_rads).sin()
+            - 115. * (l_p_rads + m_p_rads).sin();
+
+        let lon = (l_p + lsum / Self::TEN_POW_SIX).cap_angle_360();
+        let lat = bsum / Self::TEN_POW_SIX;
+        let dist = 385000.56 + rsum / 1000.;
+        (lon, lat, dist)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn should_calc_geometric() {
+        // Arrange
+        // Astronomical Algorithms pg. 342 (Example 47.a)
+        let julian_day = 2448724.5;
+        // Act
+        let (lon, lat, dist) = Moon::calc_geometric(julian_day);
+        // Assert
+        assert_approx_eq!(f64, 133.162655, lon, epsilon = 0.000001);
+        assert_approx_eq!(f64, -3.229126, lat, epsilon = 0.000001);
+        assert_approx_eq!(f64, 368409.7, dist, epsilon = 0.1);
+    }
+
+    #[test]
+    fn should_new_moon() {
+        // Arrange
+        // Astronomical Algorithms pg. 342 (Example 47.a)
+        let julian_day = 2448724.5;
+        // Act
+        let moon = Moon::new(julian_day, 0.);
+        // Assert
+        assert_approx_eq!(f64, 134.688470, moon.ra(), epsilon = 0.01);
+        assert_approx_eq!(f64, 13.768368, moon.dec(), epsilon = 0.01);
+        assert_approx_eq!(f64, 368409.7, moon.dist(), epsilon = 0.1);
+    }
+}
@@ -1,16 +1,47 @@
 //! The [`Qibla`] (Arabic: قِبْلَة, romanized: qiblah, lit. 'direction') is the direction towards
 //! the Kaaba in the Sacred Mosque in Mecca, which is used by Muslims in various religious
 //! contexts, particularly the direction of prayer.
+//!
+//! The direction is the initial bearing of the great circle to the Kaaba on a spherical
+//! Earth, or of the geodesic on the WGS84 ellipsoid, which is computed after Karney's
+//! algorithm so that it is accurate even near the antipode of the Kaaba.
+//!
+//! A Qibla can be verified on site by Rasd al-Qibla (observation of the Qibla): noting the
+//! moments the Sun is in the direction of the Qibla or the shadow of a vertical object points
+//! to it. Twice a year the Sun passes directly over the Kaaba, when the Sun is in the direction
+//! of the Qibla from everywhere it is above the horizon. See [`Qibla::rasd_times`].
 
 use std::fmt::Display;
 
-use super::coordinates::Coordinates;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    angle::{LimitAngle, PI_DEG, TWO_PI_DEG},
+    DateRange,
+};
+
+use super::{
+    coordinates::{Coordinates, Elevation, Gmt, Latitude, Longitude},
+    delta_t::DeltaT,
+    ephemeris::Ephemeris,
+    geodesic::Geodesic,
+    julian_day::JulianDay,
+    magnetic::magnetic_declination,
+    root::bisect,
+    sun::SunPosition,
+    weather::{RefractionModel, Weather},
+};
 
 /// `Qibla` for geographical [`Coordinates`].
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Qibla {
     coords: Coordinates,
     degrees: f64,
+    geodesic_degrees: f64,
+    distance: f64,
+    great_circle_distance: f64,
 }
 
 /// An enumeration of rotation values.
@@ -32,9 +63,179 @@ impl Display for Rotation {
     }
 }
 
+/// An enumeration of the alignments of the Sun with the [`Qibla`].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QiblaAlignment {
+    /// The Sun is in the direction of the Qibla.
+    Sun,
+    /// The Sun is opposite to the direction of the Qibla, so that the shadow of a vertical
+    /// object points to it.
+    Shadow,
+    /// The Sun is directly over the Kaaba, so that it is in the direction of the Qibla from
+    /// everywhere it is above the horizon.
+    OverKaaba,
+}
+
+impl Display for QiblaAlignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A moment of Rasd al-Qibla when the Sun is aligned with the [`Qibla`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RasdQibla {
+    /// The local date and time of the alignment.
+    pub date_time: NaiveDateTime,
+    /// The alignment of the Sun with the Qibla.
+    pub alignment: QiblaAlignment,
+    /// The altitude of the Sun in degrees at the geographical coordinates of the Qibla.
+    pub sun_altitude: f64,
+}
+
+impl Display for RasdQibla {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} (altitude {:.1}°)",
+            self.date_time.format("%Y-%m-%d %l:%M:%S %p"),
+            self.alignment,
+            self.sun_altitude
+        )
+    }
+}
+
+/// The path from the geographical [`Coordinates`] of a [`Qibla`] to the Kaaba for display on
+/// a map, with points as (latitude, longitude) pairs in degrees.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QiblaPath {
+    /// The great circle to the Kaaba densified to a polyline.
+    pub great_circle: Vec<(f64, f64)>,
+    /// The line of the initial bearing of the great circle.
+    pub bearing_line: [(f64, f64); 2],
+    /// The azimuth in degrees clockwise from true North of the bearing line.
+    pub azimuth: f64,
+    /// The length in meters of the great circle.
+    pub distance: f64,
+}
+
+impl QiblaPath {
+    /// Returns the `QiblaPath` as a GeoJSON (RFC 7946) feature collection of its great circle,
+    /// split at the antimeridian, its bearing line, and the Kaaba.
+    pub fn to_geojson(&self) -> String {
+        let position = |(lat, lon): (f64, f64)| json!([lon, lat]);
+        let great_circle = Self::split_at_antimeridian(&self.great_circle);
+        let great_circle_geometry = if great_circle.len() == 1 {
+            json!({
+                "type": "LineString",
+                "coordinates": great_circle[0].iter().copied().map(position).collect::<Vec<_>>(),
+            })
+        } else {
+            json!({
+                "type": "MultiLineString",
+                "coordinates": great_circle
+                    .iter()
+                    .map(|line| line.iter().copied().map(position).collect::<Vec<_>>())
+                    .collect::<Vec<_>>(),
+            })
+        };
+
+        json!({
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "properties": { "name": "Great circle", "distance": self.distance },
+                    "geometry": great_circle_geometry,
+                },
+                {
+                    "type": "Feature",
+                    "properties": { "name": "Bearing line", "azimuth": self.azimuth },
+                    "geometry": {
+                        "type": "LineString",
+                        "coordinates": self.bearing_line.map(position),
+                    },
+                },
+                {
+                    "type": "Feature",
+                    "properties": { "name": "Kaaba" },
+                    "geometry": {
+                        "type": "Point",
+                        "coordinates": position((Qibla::KAABA_LATITUDE, Qibla::KAABA_LONGITUDE)),
+                    },
+                },
+            ],
+        })
+        .to_string()
+    }
+
+    /// Returns the `QiblaPath` as a KML 2.2 document of its great circle, its bearing line,
+    /// and the Kaaba.
+    pub fn to_kml(&self) -> String {
+        let coordinates = |points: &[(f64, f64)]| {
+            points
+                .iter()
+                .map(|(lat, lon)| format!("{},{}", lon, lat))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let placemark = |name: &str, geometry: &str, points: &[(f64, f64)]| {
+            [
+                "    <Placemark>".to_string(),
+                format!("      <name>{}</name>", name),
+                format!("      <{}>", geometry),
+                format!("        <coordinates>{}</coordinates>", coordinates(points)),
+                format!("      </{}>", geometry),
+                "    </Placemark>".to_string(),
+            ]
+        };
+
+        let mut lines = vec![
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+            r#"<kml xmlns="http://www.opengis.net/kml/2.2">"#.to_string(),
+            "  <Document>".to_string(),
+            "    <name>Qibla</name>".to_string(),
+        ];
+        lines.extend(placemark("Great circle", "LineString", &self.great_circle));
+        lines.extend(placemark("Bearing line", "LineString", &self.bearing_line));
+        lines.extend(placemark(
+            "Kaaba",
+            "Point",
+            &[(Qibla::KAABA_LATITUDE, Qibla::KAABA_LONGITUDE)],
+        ));
+        lines.push("  </Document>".to_string());
+        lines.push("</kml>".to_string());
+        lines.join("\n") + "\n"
+    }
+
+    // Splits a polyline where it crosses the antimeridian so that no segment wraps around
+    // the map.
+    fn split_at_antimeridian(points: &[(f64, f64)]) -> Vec<Vec<(f64, f64)>> {
+        let mut lines = vec![vec![points[0]]];
+        for pair in points.windows(2) {
+            let ((lat1, lon1), (lat2, lon2)) = (pair[0], pair[1]);
+            if (lon2 - lon1).abs() > PI_DEG {
+                let edge = PI_DEG.copysign(lon1);
+                let unwrapped_lon2 = lon2 + TWO_PI_DEG.copysign(lon1);
+                let lat = lat1 + (lat2 - lat1) * (edge - lon1) / (unwrapped_lon2 - lon1);
+                lines.last_mut().unwrap().push((lat, edge));
+                lines.push(vec![(lat, -edge)]);
+            }
+            lines.last_mut().unwrap().push((lat2, lon2));
+        }
+        lines
+    }
+}
+
 impl Qibla {
     const KAABA_LATITUDE: f64 = 21.423333;
     const KAABA_LONGITUDE: f64 = 39.823333;
+    // Local time zone of the Kaaba.
+    const KAABA_GMT: f64 = 3.;
+    // Days between samples of the azimuth of the Sun.
+    const RASD_STEP: f64 = 10. / 1440.;
+    // Tolerance in days of a Rasd al-Qibla time.
+    const RASD_TOLERANCE: f64 = 1. / 86400.;
 
     /// Constructs a new `Qibla` from geographical [`Coordinates`].
     pub fn new(coords: Coordinates) -> Self {
@@ -42,7 +243,28 @@ impl Qibla {
         let x = f64::from(coords.longitude).to_radians() - Self::KAABA_LONGITUDE.to_radians();
         let y = lat_rads.cos() * Self::KAABA_LATITUDE.to_radians().tan() - lat_rads.sin() * x.cos();
         let degrees = x.sin().atan2(y).to_degrees();
-        Self { coords, degrees }
+
+        let (latitude, longitude) = (f64::from(coords.latitude), f64::from(coords.longitude));
+        let inverse = Geodesic::inverse(
+            latitude,
+            longitude,
+            Self::KAABA_LATITUDE,
+            Self::KAABA_LONGITUDE,
+        );
+        let great_circle_distance = Geodesic::great_circle_distance(
+            latitude,
+            longitude,
+            Self::KAABA_LATITUDE,
+            Self::KAABA_LONGITUDE,
+        );
+
+        Self {
+            coords,
+            degrees,
+            geodesic_degrees: -inverse.azi1.cap_angle_between_180(),
+            distance: inverse.dist,
+            great_circle_distance,
+        }
     }
 
     /// Returns the `Qibla` geographical [`Coordinates`] passed to [`Qibla::new`].
@@ -50,11 +272,272 @@ impl Qibla {
         self.coords
     }
 
-    /// Returns the `Qibla` direction in degrees from North [-90. ..=90.].
+    /// Returns the `Qibla` direction in degrees from North [-180. ..=180.], which is
+    /// clockwise when negative and counterclockwise when positive (see [`Qibla::rotation`]).
+    ///
+    /// This is the initial bearing of the great circle to the Kaaba on a spherical Earth.
     pub fn degrees(&self) -> f64 {
         self.degrees
     }
 
+    /// Returns the `Qibla` azimuth in degrees clockwise from true North [0. ..360.].
+    ///
+    /// This is the initial bearing of the great circle to the Kaaba on a spherical Earth.
+    pub fn azimuth(&self) -> f64 {
+        (-self.degrees).cap_angle_360()
+    }
+
+    /// Returns the `Qibla` azimuth in degrees clockwise from true North [0. ..360.] as the
+    /// initial bearing of the geodesic to the Kaaba on the WGS84 ellipsoid.
+    pub fn geodesic_azimuth(&self) -> f64 {
+        (-self.geodesic_degrees).cap_angle_360()
+    }
+
+    /// Returns the `Qibla` azimuth in degrees clockwise from magnetic North [0. ..360.] on a
+    /// [`NaiveDate`], for use with a compass, or `None` when the date is outside the validity
+    /// window of the World Magnetic Model.
+    ///
+    /// This is the [`Qibla::azimuth`] corrected for the [`magnetic_declination`] of the World
+    /// Magnetic Model.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use chrono::NaiveDate;
+    /// use islamic_prayer_times::*;
+    ///
+    /// let latitude = Latitude::try_from(39.)?;
+    /// let longitude = Longitude::try_from(-77.)?;
+    /// let elevation = Elevation::try_from(0.)?;
+    /// let coords = Coordinates::new(latitude, longitude, elevation);
+    /// let date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+    ///
+    /// let qibla = Qibla::new(coords);
+    ///
+    /// // The declination is West, so the magnetic azimuth is larger than the true one.
+    /// assert!(qibla.magnetic_azimuth(date).unwrap() > qibla.azimuth());
+    /// # Ok::<(), Box<dyn Error>>(())
+    /// ```
+    pub fn magnetic_azimuth(&self, date: NaiveDate) -> Option<f64> {
+        magnetic_declination(self.coords, date).map(|x| (self.azimuth() - x).cap_angle_360())
+    }
+
+    /// Returns the `Qibla` direction in degrees from North in the same sense as
+    /// [`Qibla::degrees`], as the initial bearing of the geodesic to the Kaaba on the WGS84
+    /// ellipsoid.
+    ///
+    /// The geodesic bearing differs from the spherical one by up to a few tenths of a degree,
+    /// which matters for surveying the orientation of a mosque.
+    pub fn geodesic_degrees(&self) -> f64 {
+        self.geodesic_degrees
+    }
+
+    /// Returns the length in meters of the geodesic to the Kaaba on the WGS84 ellipsoid.
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+
+    /// Returns the length in meters of the great circle to the Kaaba on a sphere of the mean
+    /// radius of the Earth.
+    pub fn great_circle_distance(&self) -> f64 {
+        self.great_circle_distance
+    }
+
+    /// Returns the [`QiblaPath`] with the great circle to the Kaaba densified to a number of
+    /// segments and a bearing line of a length in meters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use islamic_prayer_times::*;
+    ///
+    /// let latitude = Latitude::try_from(39.)?;
+    /// let longitude = Longitude::try_from(-77.)?;
+    /// let elevation = Elevation::try_from(0.)?;
+    /// let coords = Coordinates::new(latitude, longitude, elevation);
+    ///
+    /// let qibla_path = Qibla::new(coords).path(100, 1000.);
+    ///
+    /// assert_eq!(101, qibla_path.great_circle.len());
+    /// assert!(qibla_path.to_geojson().contains("LineString"));
+    /// # Ok::<(), Box<dyn Error>>(())
+    /// ```
+    pub fn path(&self, segments: usize, bearing_line_length: f64) -> QiblaPath {
+        let origin = (
+            f64::from(self.coords.latitude),
+            f64::from(self.coords.longitude),
+        );
+        let azimuth = self.azimuth();
+        let segments = segments.max(1);
+
+        let mut great_circle = vec![origin];
+        great_circle.extend((1..segments).map(|i| {
+            let dist = self.great_circle_distance * i as f64 / segments as f64;
+            Self::destination(origin, azimuth, dist)
+        }));
+        great_circle.push((Self::KAABA_LATITUDE, Self::KAABA_LONGITUDE));
+
+        QiblaPath {
+            great_circle,
+            bearing_line: [
+                origin,
+                Self::destination(origin, azimuth, bearing_line_length),
+            ],
+            azimuth,
+            distance: self.great_circle_distance,
+        }
+    }
+
+    // Returns the point at a distance in meters along the great circle from an origin with
+    // an initial azimuth on a sphere of the mean radius of the Earth.
+    fn destination((lat, lon): (f64, f64), azimuth: f64, dist: f64) -> (f64, f64) {
+        let lat_rads = lat.to_radians();
+        let azimuth_rads = azimuth.to_radians();
+        let ang_dist = dist / Geodesic::MEAN_RADIUS;
+        let dest_lat_rads = (lat_rads.sin() * ang_dist.cos()
+            + lat_rads.cos() * ang_dist.sin() * azimuth_rads.cos())
+        .asin();
+        let dest_lon_diff = (azimuth_rads.sin() * ang_dist.sin() * lat_rads.cos())
+            .atan2(ang_dist.cos() - lat_rads.sin() * dest_lat_rads.sin())
+            .to_degrees();
+        (
+            dest_lat_rads.to_degrees(),
+            (lon + dest_lon_diff).cap_angle_between_180(),
+        )
+    }
+
+    /// Returns the [`RasdQibla`] moments, ordered by time, for the [`Qibla`] geographical
+    /// [`Coordinates`] in a [`Gmt`] time zone over a [`DateRange`].
+    ///
+    /// These are the moments when the Sun is above the horizon and in, or opposite to, the
+    /// direction of the Qibla, and the two moments of each year when the Sun passes directly
+    /// over the Kaaba (whether or not the Sun is above the horizon of the coordinates).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use chrono::NaiveDate;
+    /// use islamic_prayer_times::*;
+    ///
+    /// let latitude = Latitude::try_from(39.)?;
+    /// let longitude = Longitude::try_from(-77.)?;
+    /// let elevation = Elevation::try_from(0.)?;
+    /// let coords = Coordinates::new(latitude, longitude, elevation);
+    /// let gmt = Gmt::try_from(-5.)?;
+    /// let start_date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    /// let end_date = NaiveDate::from_ymd_opt(2024, 5, 31).unwrap();
+    /// let date_range = DateRange::from(start_date..=end_date);
+    ///
+    /// let rasd_times = Qibla::new(coords).rasd_times(gmt, &date_range);
+    ///
+    /// assert!(rasd_times.iter().any(|x| x.alignment == QiblaAlignment::OverKaaba));
+    /// # Ok::<(), Box<dyn Error>>(())
+    /// ```
+    pub fn rasd_times(&self, gmt: Gmt, date_range: &DateRange) -> Vec<RasdQibla> {
+        let bearing = self.azimuth();
+        let weather = Weather::default();
+        let sun_pos = |jd: f64| {
+            SunPosition::from_jd(
+                self.coords,
+                jd,
+                weather,
+                RefractionModel::Saemundsson,
+                DeltaT::default(),
+                Ephemeris::default(),
+            )
+        };
+        let new_rasd = |jd: f64, alignment: QiblaAlignment| RasdQibla {
+            date_time: JulianDay::to_date_time(jd, gmt),
+            alignment,
+            sun_altitude: sun_pos(jd).altitude,
+        };
+
+        let mut rasd_times = Vec::new();
+        let start_jd = f64::from(JulianDay::new(*date_range.start_date(), gmt));
+        let end_jd = start_jd + date_range.num_days() as f64;
+
+        // The Sun in or opposite to the direction of the Qibla.
+        for (target, alignment) in [
+            (bearing, QiblaAlignment::Sun),
+            ((bearing + PI_DEG).cap_angle_360(), QiblaAlignment::Shadow),
+        ] {
+            let diff = |jd: f64| (sun_pos(jd).azimuth - target).cap_angle_between_180();
+            let mut jd = start_jd;
+            let mut prev_diff = diff(jd);
+            while jd < end_jd {
+                let next_jd = (jd + Self::RASD_STEP).min(end_jd);
+                let next_diff = diff(next_jd);
+                // Ignore the azimuth wrapping around opposite to the target.
+                if prev_diff.signum() != next_diff.signum()
+                    && (prev_diff - next_diff).abs() < PI_DEG
+                {
+                    let rasd_jd = bisect(diff, jd, next_jd, Self::RASD_TOLERANCE);
+                    if sun_pos(rasd_jd).altitude > 0. {
+                        rasd_times.push(new_rasd(rasd_jd, alignment));
+                    }
+                }
+                jd = next_jd;
+                prev_diff = next_diff;
+            }
+        }
+
+        // The Sun directly over the Kaaba.
+        for rasd_jd in Self::kaaba_zenith_passages(date_range) {
+            if (start_jd..end_jd).contains(&rasd_jd) {
+                rasd_times.push(new_rasd(rasd_jd, QiblaAlignment::OverKaaba));
+            }
+        }
+
+        rasd_times.sort_by_key(|x| x.date_time);
+        rasd_times
+    }
+
+    // Returns the geographical coordinates of the Kaaba.
+    pub(crate) fn kaaba_coords() -> Coordinates {
+        Coordinates::new(
+            Latitude::try_from(Self::KAABA_LATITUDE).unwrap(),
+            Longitude::try_from(Self::KAABA_LONGITUDE).unwrap(),
+            Elevation::default(),
+        )
+    }
+
+    // Returns the Julian days of the solar noons of the Kaaba closest to the Sun passing its zenith.
+    fn kaaba_zenith_passages(date_range: &DateRange) -> Vec<f64> {
+        let coords = Self::kaaba_coords();
+        let gmt = Gmt::try_from(Self::KAABA_GMT).unwrap();
+
+        let mut passages = Vec::new();
+        let mut prev: Option<(f64, f64)> = None;
+        // Include a day either side of the date range for a change of the date across time zones.
+        for date in date_range
+            .start_date()
+            .pred_opt()
+            .unwrap()
+            .iter_days()
+            .take(date_range.num_days() + 2)
+        {
+            // Local mean noon of the Kaaba
+            let mean_noon_jd = f64::from(JulianDay::new(date, gmt)) + f64::from(gmt) / 24. + 0.5
+                - Self::KAABA_LONGITUDE / TWO_PI_DEG;
+            let (noon_jd, dec) = SunPosition::transit(coords, mean_noon_jd);
+            let dec_diff = dec - Self::KAABA_LATITUDE;
+            if let Some((prev_dec_diff, prev_noon_jd)) = prev {
+                if prev_dec_diff.signum() != dec_diff.signum() {
+                    passages.push(if prev_dec_diff.abs() < dec_diff.abs() {
+                        prev_noon_jd
+                    } else {
+                        noon_jd
+                    });
+                }
+            }
+            prev = Some((dec_diff, noon_jd));
+        }
+        passages
+    }
+
     /// Returns the `Qibla` [`Rotation`].
     pub fn rotation(&self) -> Rotation {
         if self.degrees < 0. {
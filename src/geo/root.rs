@@ -0,0 +1,17 @@
+// Root finding of functions of a Julian day, such as the instant the Sun crosses an altitude or an
+// azimuth, or the Moon touches the Sun.
+
+// Returns the root of a function that changes sign between `a` and `b` by bisection to within a
+// tolerance.
+pub fn bisect<F: Fn(f64) -> f64>(f: F, mut a: f64, mut b: f64, tolerance: f64) -> f64 {
+    let a_positive = f(a) > 0.;
+    while (b - a).abs() > tolerance {
+        let mid = (a + b) / 2.;
+        if (f(mid) > 0.) == a_positive {
+            a = mid;
+        } else {
+            b = mid;
+        }
+    }
+    (a + b) / 2.
+}
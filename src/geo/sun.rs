@@ -0,0 +1,143 @@
+//! Position of the Sun in the sky of a geographical location.
+//!
+
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::angle::{LimitAngle, PI_DEG};
+
+use super::{
+    astro::Astro,
+    coordinates::{Coordinates, Gmt},
+    delta_t::DeltaT,
+    ephemeris::Ephemeris,
+    julian_day::JulianDay,
+    root::bisect,
+    weather::{RefractionModel, Weather},
+};
+
+/// The topocentric position of the Sun seen from geographical [`Coordinates`] at an instant.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SunPosition {
+    /// Altitude of the center of the Sun in degrees above the horizon, corrected for
+    /// atmospheric refraction.
+    pub altitude: f64,
+    /// Azimuth of the Sun in degrees measured clockwise from true North [0. ..360.).
+    pub azimuth: f64,
+    /// Length of the shadow of a vertical object relative to its height, or [`None`] when
+    /// the Sun is not above the horizon.
+    pub shadow_ratio: Option<f64>,
+}
+
+impl SunPosition {
+    // Tolerance in days of a transit of the Sun
+    const TRANSIT_TOLERANCE: f64 = 1. / 86400.;
+
+    /// Returns the `SunPosition` seen from geographical [`Coordinates`] at an instant with
+    /// its altitude refracted by the (optional) current [`Weather`] using the
+    /// [`Saemundsson`](RefractionModel::Saemundsson) refraction model.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use chrono::{TimeZone, Utc};
+    /// use islamic_prayer_times::*;
+    ///
+    /// let latitude = Latitude::try_from(39.)?;
+    /// let longitude = Longitude::try_from(-77.)?;
+    /// let elevation = Elevation::try_from(0.)?;
+    /// let coords = Coordinates::new(latitude, longitude, elevation);
+    /// let instant = Utc.with_ymd_and_hms(2023, 6, 21, 17, 0, 0).unwrap();
+    ///
+    /// let sun_position = SunPosition::new(coords, instant, None);
+    ///
+    /// assert!(sun_position.altitude > 74.);
+    /// assert!(sun_position.shadow_ratio.unwrap() < 0.3);
+    /// # Ok::<(), Box<dyn Error>>(())
+    /// ```
+    pub fn new(coords: Coordinates, instant: DateTime<Utc>, weather: Option<Weather>) -> Self {
+        let julian_day = JulianDay::from_date_time(instant.naive_utc(), Gmt::default());
+        Self::from_jd(
+            coords,
+            julian_day,
+            weather.unwrap_or_default(),
+            RefractionModel::Saemundsson,
+            DeltaT::default(),
+            Ephemeris::default(),
+        )
+    }
+
+    pub(crate) fn from_jd(
+        coords: Coordinates,
+        julian_day: f64,
+        weather: Weather,
+        refraction_model: RefractionModel,
+        delta_t: DeltaT,
+        ephemeris: Ephemeris,
+    ) -> Self {
+        let delta_t = delta_t.seconds_at(julian_day);
+        let astro = Astro::from_ephemeris(julian_day, delta_t, ephemeris).topocentric(coords);
+        let lat_rads = f64::from(coords.latitude).to_radians();
+        let dec_rads = astro.dec().to_radians();
+        let hour_angle_rads = (astro.sid_time() + f64::from(coords.longitude) - astro.ra())
+            .cap_angle_360()
+            .to_radians();
+
+        // Astronomical Algorithms pg. 93 (13.6)
+        let airless_alt = (lat_rads.sin() * dec_rads.sin()
+            + lat_rads.cos() * dec_rads.cos() * hour_angle_rads.cos())
+        .asin()
+        .to_degrees();
+        let altitude = airless_alt + refraction_model.degrees(airless_alt, weather);
+
+        // Astronomical Algorithms pg. 93 (13.5) measured from the South
+        let azimuth = hour_angle_rads
+            .sin()
+            .atan2(hour_angle_rads.cos() * lat_rads.sin() - dec_rads.tan() * lat_rads.cos())
+            .to_degrees();
+        let azimuth = (azimuth + PI_DEG).cap_angle_360();
+
+        let shadow_ratio = if altitude > 0. {
+            Some(1. / altitude.to_radians().tan())
+        } else {
+            None
+        };
+
+        Self {
+            altitude,
+            azimuth,
+            shadow_ratio,
+        }
+    }
+
+    // Returns the Julian day the Sun transits the meridian of geographical coordinates within a
+    // quarter of a day of a Julian day, and the declination of the Sun in degrees at the transit.
+    pub(crate) fn transit(coords: Coordinates, julian_day: f64) -> (f64, f64) {
+        let astro_at =
+            |jd: f64| Astro::new(jd, DeltaT::default().seconds_at(jd)).topocentric(coords);
+        let hour_angle = |jd: f64| {
+            let astro = astro_at(jd);
+            (astro.sid_time() + f64::from(coords.longitude) - astro.ra()).cap_angle_between_180()
+        };
+        let transit_jd = bisect(
+            hour_angle,
+            julian_day - 0.25,
+            julian_day + 0.25,
+            Self::TRANSIT_TOLERANCE,
+        );
+        (transit_jd, astro_at(transit_jd).dec())
+    }
+}
+
+impl Display for SunPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "altitude {:.2}°, azimuth {:.2}°",
+            self.altitude, self.azimuth
+        )
+    }
+}
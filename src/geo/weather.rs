@@ -1,11 +1,43 @@
 //! Weather information types.
 //!
+//! Type [`Weather`] represents the atmospheric [`Pressure`] and outside [`Temperature`] which
+//! scale the atmospheric refraction of the Sun near the horizon. When the current weather is
+//! unknown it can be derived from the [`Elevation`] of a location using the International
+//! Standard Atmosphere with [`standard_atmosphere`](Weather::standard_atmosphere).
+//!
+//! Type [`RefractionModel`] represents the formula used to calculate the atmospheric refraction:
+//!
+//! * [`Itl`](RefractionModel::Itl) is the formula of the ported ITL code and the default for
+//!   calculating prayer times.
+//! * [`Saemundsson`](RefractionModel::Saemundsson) is the formula of Sæmundsson for true altitudes.
+//! * [`Bennett`](RefractionModel::Bennett) is the formula of Bennett for apparent altitudes,
+//!   solved for true altitudes.
+//! * [`Standard`](RefractionModel::Standard) is the fixed 34′ refraction at the horizon used by
+//!   most published almanacs.
+//! * [`None`](RefractionModel::None) is no refraction.
+//!
+//! With the [`Itl`](RefractionModel::Itl) formula, sunrise and sunset are when the center of the
+//! Sun is at the fixed altitude of -0.83337 degrees, which already includes the standard
+//! refraction of 34′. With the other models, they are when the upper limb of the Sun, raised by
+//! the refraction of the model, touches the horizon.
+//!
+//! Type [`WeatherSeries`] represents the [`Weather`] of each date of a range of dates, either as
+//! a monthly climatology or as daily observations. It is loaded from CSV lines of a month number
+//! (1-12) or a date followed by the pressure in millibars and the temperature in degrees Celcius.
+//!
+//! ```text
+//! # date,pressure,temperature
+//! 2024-01-01,1021.3,-2.5
+//! 2024-01-02,1016.8,1.2
+//! ```
 
-use std::ops::RangeInclusive;
+use std::{collections::BTreeMap, ops::RangeInclusive};
 
+use chrono::{Datelike, NaiveDate};
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 
-use crate::{Bounded, OutOfRangeError};
+use crate::{geo::coordinates::Elevation, Bounded, OutOfRangeError, ParseError};
 
 /// An atmospheric pressure in millibars.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -72,6 +104,49 @@ pub struct Weather {
     pub temperature: Temperature,
 }
 
+impl Weather {
+    // International Standard Atmosphere sea level temperature in degrees Celcius, temperature
+    // lapse rate in degrees Celcius per meter, sea level pressure in millibars, and exponent
+    // of the barometric formula
+    const ISA_SEA_LEVEL_TEMP: f64 = 15.;
+    const ISA_LAPSE_RATE: f64 = 0.0065;
+    const ISA_SEA_LEVEL_PRESSURE: f64 = 1013.25;
+    const ISA_PRESSURE_EXP: f64 = 5.25588;
+
+    /// Returns the [`Weather`] of the International Standard Atmosphere at an [`Elevation`],
+    /// with its [`Pressure`] limited to the range of the type below sea level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use islamic_prayer_times::*;
+    ///
+    /// let weather = Weather::standard_atmosphere(Elevation::try_from(1500.)?);
+    ///
+    /// assert!((f64::from(weather.pressure) - 845.6).abs() < 0.1);
+    /// assert!((f64::from(weather.temperature) - 5.25).abs() < 0.01);
+    /// # Ok::<(), Box<dyn Error>>(())
+    /// ```
+    pub fn standard_atmosphere(elevation: Elevation) -> Self {
+        let elevation = f64::from(elevation);
+        let temperature = Self::ISA_SEA_LEVEL_TEMP - Self::ISA_LAPSE_RATE * elevation;
+        let pressure = Self::ISA_SEA_LEVEL_PRESSURE
+            * (1. - Self::ISA_LAPSE_RATE * elevation / (Self::ISA_SEA_LEVEL_TEMP + 273.15))
+                .powf(Self::ISA_PRESSURE_EXP);
+        let pressure_range = <Pressure as Bounded<f64>>::range();
+        Self {
+            pressure: Pressure(pressure.clamp(*pressure_range.start(), *pressure_range.end())),
+            temperature: Temperature(temperature),
+        }
+    }
+
+    // Astronomical Algorithms pg. 107
+    fn refraction_factor(&self) -> f64 {
+        f64::from(self.pressure) / 1010. * (283. / (273. + f64::from(self.temperature)))
+    }
+}
+
 impl Default for Weather {
     fn default() -> Self {
         Self {
@@ -80,3 +155,226 @@ impl Default for Weather {
         }
     }
 }
+
+/// The `RefractionModel` type. See [the module level documentation](self) for more.
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum, Serialize, Deserialize)]
+pub enum RefractionModel {
+    /// The formula of the ported ITL code, scaled by the [`Weather`].
+    #[default]
+    Itl,
+    /// The formula of Sæmundsson for true altitudes, scaled by the [`Weather`].
+    Saemundsson,
+    /// The formula of Bennett for apparent altitudes, solved for true altitudes and scaled by
+    /// the [`Weather`].
+    Bennett,
+    /// A fixed refraction of 34′ regardless of the altitude and the [`Weather`].
+    Standard,
+    /// No refraction.
+    None,
+}
+
+impl RefractionModel {
+    // Refraction is not applied to altitudes this far below the horizon.
+    const MIN_REFRACTION_ALT: f64 = -2.;
+    // Standard refraction at the horizon in minutes of arc
+    const STANDARD_ARC_MIN: f64 = 34.;
+    // Iterations of the apparent altitude of the formula of Bennett
+    const BENNETT_ITERATIONS: usize = 5;
+
+    /// Returns the atmospheric refraction in degrees to add to a true (airless) altitude in
+    /// degrees in the [`Weather`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use islamic_prayer_times::*;
+    ///
+    /// let weather = Weather::default();
+    ///
+    /// assert!((RefractionModel::Saemundsson.degrees(0., weather) - 0.4763).abs() < 0.0001);
+    /// assert!((RefractionModel::Bennett.degrees(0., weather) - 0.4765).abs() < 0.0001);
+    /// assert_eq!(34. / 60., RefractionModel::Standard.degrees(0., weather));
+    /// assert_eq!(0., RefractionModel::None.degrees(0., weather));
+    /// ```
+    pub fn degrees(&self, altitude: f64, weather: Weather) -> f64 {
+        use RefractionModel::*;
+
+        let r = match self {
+            Itl => {
+                // Astronomical Algorithms pg. 105-107
+                // Astronomical Algorithms pg. 106 (16.4)
+                // The conversion to degrees of the tangent is not in the book but it's in the
+                // ported code.
+                1.02 / ((altitude + (10.3 / (altitude + 5.11)))
+                    .to_radians()
+                    .tan()
+                    .to_degrees()
+                    + 0.0019279)
+            }
+            Saemundsson | Bennett if altitude < Self::MIN_REFRACTION_ALT => return 0.,
+            Saemundsson => {
+                // Astronomical Algorithms pg. 106 (16.4)
+                1.02 / (altitude + 10.3 / (altitude + 5.11)).to_radians().tan() + 0.0019279
+            }
+            Bennett => {
+                // Astronomical Algorithms pg. 106 (16.3)
+                // The formula takes the apparent altitude, which is found from the true altitude
+                // by repeatedly adding the refraction.
+                let bennett = |apparent_alt: f64| {
+                    weather.refraction_factor()
+                        * (1.
+                            / (apparent_alt + 7.31 / (apparent_alt + 4.4))
+                                .to_radians()
+                                .tan()
+                            + 0.0013515)
+                        / 60.
+                };
+                let mut r = 0.;
+                for _ in 0..Self::BENNETT_ITERATIONS {
+                    r = bennett(altitude + r);
+                }
+                return r;
+            }
+            Standard => return Self::STANDARD_ARC_MIN / 60.,
+            None => return 0.,
+        };
+        weather.refraction_factor() * r / 60.
+    }
+
+    // Returns the model refracting the apparent altitude of the sun at any instant. The ITL
+    // refraction is folded into the fixed altitude of its sunrise and sunset, so Sæmundsson
+    // stands in for it.
+    pub(crate) fn for_sun_position(self) -> Self {
+        match self {
+            RefractionModel::Itl => RefractionModel::Saemundsson,
+            _ => self,
+        }
+    }
+}
+
+/// The `WeatherSeries` type. See [the module level documentation](self) for more.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WeatherSeries {
+    /// The [`Weather`] of each month of the year from January to December.
+    Monthly([Weather; 12]),
+    /// The [`Weather`] of each date.
+    Daily(BTreeMap<NaiveDate, Weather>),
+}
+
+impl WeatherSeries {
+    /// Returns the [`Weather`] on a date, or [`None`] when a daily series has no
+    /// weather for it.
+    pub fn weather_on(&self, date: NaiveDate) -> Option<Weather> {
+        match self {
+            WeatherSeries::Monthly(weathers) => Some(weathers[date.month0() as usize]),
+            WeatherSeries::Daily(weathers) => weathers.get(&date).copied(),
+        }
+    }
+
+    /// Creates a new `WeatherSeries` from CSV lines of a month number or a date followed by a
+    /// pressure and a temperature, ignoring blank lines, lines starting with `#`, and a leading
+    /// header line naming the three fields. A monthly series must have a line for each of the
+    /// 12 months.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use chrono::NaiveDate;
+    /// use islamic_prayer_times::*;
+    ///
+    /// let csv = "date,pressure,temperature\n2024-01-01,1021.3,-2.5\n2024-01-02,1016.8,1.2\n";
+    ///
+    /// let weather_series = WeatherSeries::from_csv(csv)?;
+    /// let date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+    /// let weather = weather_series.weather_on(date).unwrap();
+    ///
+    /// assert_eq!(1016.8, f64::from(weather.pressure));
+    /// assert_eq!(1.2, f64::from(weather.temperature));
+    /// # Ok::<(), Box<dyn Error>>(())
+    /// ```
+    pub fn from_csv(csv: &str) -> Result<Self, ParseError> {
+        let mut monthly = BTreeMap::new();
+        let mut daily = BTreeMap::new();
+        let mut header = false;
+        for (index, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid_line = || {
+                ParseError(format!(
+                    "Invalid weather series line {}: {}",
+                    index + 1,
+                    line
+                ))
+            };
+            let values = line.split(',').map(|x| x.trim()).collect::<Vec<_>>();
+            let weather = match values.as_slice() {
+                [_, pressure, temperature] => Self::parse_weather(pressure, temperature),
+                _ => None,
+            };
+            let key = values[0];
+            match weather {
+                Some(weather) => {
+                    if let Ok(date) = key.parse::<NaiveDate>() {
+                        daily.insert(date, weather);
+                    } else if let Some(month) =
+                        key.parse::<usize>().ok().filter(|x| (1..=12).contains(x))
+                    {
+                        monthly.insert(month, weather);
+                    } else {
+                        return Err(invalid_line());
+                    }
+                }
+                // A header line before any weather names its three fields instead of holding
+                // a date or a month with numbers.
+                None if monthly.is_empty()
+                    && daily.is_empty()
+                    && !header
+                    && Self::is_header(&values) =>
+                {
+                    header = true
+                }
+                None => return Err(invalid_line()),
+            }
+        }
+        match (monthly.len(), daily.len()) {
+            (0, 0) => Err(ParseError("The weather series has no weather".to_string())),
+            (0, _) => Ok(WeatherSeries::Daily(daily)),
+            (12, 0) => Ok(WeatherSeries::Monthly(
+                monthly
+                    .into_values()
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .unwrap(),
+            )),
+            (_, 0) => Err(ParseError(
+                "The monthly weather series does not have all 12 months".to_string(),
+            )),
+            _ => Err(ParseError(
+                "The weather series mixes months and dates".to_string(),
+            )),
+        }
+    }
+
+    fn is_header(values: &[&str]) -> bool {
+        match values {
+            [key, pressure, temperature] => {
+                let is_key = key.parse::<NaiveDate>().is_ok()
+                    || key.parse::<usize>().is_ok_and(|x| (1..=12).contains(&x));
+                !is_key || pressure.parse::<f64>().is_err() || temperature.parse::<f64>().is_err()
+            }
+            _ => false,
+        }
+    }
+
+    fn parse_weather(pressure: &str, temperature: &str) -> Option<Weather> {
+        let pressure = pressure.parse::<f64>().ok()?;
+        let temperature = temperature.parse::<f64>().ok()?;
+        Some(Weather {
+            pressure: <Pressure as TryFrom<f64>>::try_from(pressure).ok()?,
+            temperature: <Temperature as TryFrom<f64>>::try_from(temperature).ok()?,
+        })
+    }
+}
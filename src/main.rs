@@ -3,16 +3,33 @@ use std::{
     fs::{self, File},
 };
 
-use chrono::{Local, NaiveDate};
+use chrono::{Local, NaiveDate, NaiveTime};
 use clap::Parser;
 use cli::{CliArgs, ParamsConfig};
 use islamic_prayer_times::{
-    prayer_times_dt_rng_block, Coordinates, DateRange, HijriDate, Location, Params, Prayer,
-    PrayerTime,
+    extreme_latitude_spans, prayer_times_dt_rng_block_with_weather, prayer_times_solar_days_dt_rng,
+    Coordinates, CrossingError, DateRange, DeltaT, ExtremeLatitudeSpan, HijriDate, HorizonDip,
+    Location, Params, Prayer, PrayerTime, Qibla, SolarDay, WeatherSeries,
 };
+use serde::Serialize;
 
 mod cli;
 
+// Segments of the great circle and length in meters of the bearing line of the written Qibla path.
+const QIBLA_PATH_SEGMENTS: usize = 100;
+const QIBLA_BEARING_LINE_LENGTH: f64 = 10_000.;
+
+// The calculated prayer times, solar summaries, and extreme latitude spans written as JSON when the solar summary
+// or extreme latitude spans are requested.
+#[derive(Serialize)]
+struct PrayerTimesReport<'a> {
+    prayer_times: &'a BTreeMap<NaiveDate, BTreeMap<Prayer, Result<PrayerTime, ()>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    solar_days: Option<&'a BTreeMap<NaiveDate, SolarDay>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extreme_latitude_spans: Option<&'a Vec<ExtremeLatitudeSpan>>,
+}
+
 fn main() {
     let cli_args = CliArgs::parse();
 
@@ -28,24 +45,61 @@ fn main() {
         params_config
     };
 
-    let pts_by_date = prayer_times_dt_rng_block(
-        &params_config.params,
-        params_config.location,
-        &params_config.date_range.unwrap(),
-        365,
-    );
+    let date_range = params_config.date_range.unwrap();
+    // The solar summaries are calculated from the same ephemerides as the prayer times.
+    let (pts_by_date, solar_days) = if cli_args.solar_day {
+        let (pts_by_date, solar_days) = prayer_times_solar_days_dt_rng(
+            &params_config.params,
+            params_config.location,
+            &date_range,
+            params_config.weather_series.as_ref(),
+        );
+        (pts_by_date, Some(solar_days))
+    } else {
+        let pts_by_date = prayer_times_dt_rng_block_with_weather(
+            &params_config.params,
+            params_config.location,
+            &date_range,
+            params_config.weather_series.as_ref(),
+            365,
+        );
+        (pts_by_date, None)
+    };
+
+    let ext_lat_spans = if cli_args.extreme_latitude_spans {
+        Some(extreme_latitude_spans(
+            &params_config.params,
+            params_config.location,
+            &date_range,
+            params_config.weather_series.as_ref(),
+        ))
+    } else {
+        None
+    };
+
+    if let Some(qibla_file_path) = cli_args.qibla_file_path {
+        write_qibla_file(params_config.location.coords, &qibla_file_path);
+    }
 
     if let Some(output_file_path) = cli_args.output_file_path {
-        write_prayer_times_file(&pts_by_date, &output_file_path);
+        write_prayer_times_file(
+            &pts_by_date,
+            solar_days.as_ref(),
+            ext_lat_spans.as_ref(),
+            &output_file_path,
+        );
     } else {
-        write_prayer_times_terminal(&pts_by_date);
+        write_prayer_times_terminal(&pts_by_date, solar_days.as_ref());
+        if let Some(ext_lat_spans) = &ext_lat_spans {
+            write_ext_lat_spans_terminal(ext_lat_spans);
+        }
     }
 }
 
 fn read_params_file(input_file_path: &str) -> ParamsConfig {
     // Read the geographical and calculation parameters as JSON from the input file.
 
-    let file_data = fs::read_to_string(&input_file_path).unwrap_or_else(|_| {
+    let file_data = fs::read_to_string(input_file_path).unwrap_or_else(|_| {
         panic!(
             "Failed to read the geographical and calculation parameters from the file {}",
             &input_file_path
@@ -70,7 +124,16 @@ fn read_params_file(input_file_path: &str) -> ParamsConfig {
 fn read_params_cli(cli_args: &CliArgs) -> ParamsConfig {
     // Read the geographical and calculation parameters from the command line arguments.
 
-    let params = Params::new(cli_args.method);
+    let mut params = Params::new(cli_args.method);
+    params.refraction_model = cli_args.refraction_model;
+    params.standard_atmosphere = cli_args.standard_atmosphere;
+    params.ephemeris = cli_args.ephemeris;
+    if let Some(delta_t) = cli_args.delta_t {
+        params.delta_t = DeltaT::Seconds(delta_t);
+    }
+    if let Some(terrain_height) = cli_args.terrain_height {
+        params.horizon_dip = HorizonDip::TerrainHeight(terrain_height);
+    }
     let coords = Coordinates::new(
         cli_args.latitude.unwrap(),
         cli_args.longitude.unwrap(),
@@ -97,17 +160,41 @@ fn read_params_cli(cli_args: &CliArgs) -> ParamsConfig {
         start_date
     };
 
+    let weather_series = cli_args
+        .weather_file_path
+        .as_ref()
+        .map(|x| read_weather_file(x));
+
     ParamsConfig {
         params,
         location,
         date_range: Some(DateRange::from(start_date..=end_date)),
+        weather_series,
     }
 }
 
+fn read_weather_file(weather_file_path: &str) -> WeatherSeries {
+    // Read the monthly or daily weather series as CSV from the weather file.
+
+    let csv = fs::read_to_string(weather_file_path).unwrap_or_else(|_| {
+        panic!(
+            "Failed to read the weather series from the file {}",
+            &weather_file_path
+        )
+    });
+
+    WeatherSeries::from_csv(&csv).unwrap_or_else(|e| {
+        panic!(
+            "Failed to parse the weather series as CSV from the file {}: {}",
+            &weather_file_path, e
+        )
+    })
+}
+
 fn write_params_file(params_config: &ParamsConfig, params_file_path: &str) {
     // Write the calculation parameters as JSON to the parameters file.
 
-    let file = File::create(&params_file_path).unwrap_or_else(|_| {
+    let file = File::create(params_file_path).unwrap_or_else(|_| {
         panic!(
             "Failed to create the geographical and calculation parameters file {}",
             &params_file_path
@@ -123,20 +210,53 @@ fn write_params_file(params_config: &ParamsConfig, params_file_path: &str) {
     });
 }
 
+fn write_qibla_file(coords: Coordinates, qibla_file_path: &str) {
+    // Write the great-circle path and bearing line of the Qibla as KML or GeoJSON to the Qibla file.
+
+    let qibla_path = Qibla::new(coords).path(QIBLA_PATH_SEGMENTS, QIBLA_BEARING_LINE_LENGTH);
+    let contents = if qibla_file_path.to_lowercase().ends_with(".kml") {
+        qibla_path.to_kml()
+    } else {
+        qibla_path.to_geojson()
+    };
+
+    fs::write(qibla_file_path, contents).unwrap_or_else(|_| {
+        panic!(
+            "Failed to write the Qibla path to the file {}",
+            &qibla_file_path
+        )
+    });
+}
+
 fn write_prayer_times_file(
     pts_by_date: &BTreeMap<NaiveDate, BTreeMap<Prayer, Result<PrayerTime, ()>>>,
+    solar_days: Option<&BTreeMap<NaiveDate, SolarDay>>,
+    ext_lat_spans: Option<&Vec<ExtremeLatitudeSpan>>,
     output_file: &str,
 ) {
     // Write the calculated prayer times as JSON to the output file.
 
-    let file = File::create(&output_file).unwrap_or_else(|_| {
+    let file = File::create(output_file).unwrap_or_else(|_| {
         panic!(
             "Failed to create the calculated prayer times output file {}",
             &output_file
         )
     });
 
-    serde_json::to_writer(file, &pts_by_date).unwrap_or_else(|_| {
+    let result = if solar_days.is_some() || ext_lat_spans.is_some() {
+        serde_json::to_writer(
+            file,
+            &PrayerTimesReport {
+                prayer_times: pts_by_date,
+                solar_days,
+                extreme_latitude_spans: ext_lat_spans,
+            },
+        )
+    } else {
+        serde_json::to_writer(file, &pts_by_date)
+    };
+
+    result.unwrap_or_else(|_| {
         panic!(
             "Failed to serialize the calculated prayer times as JSON to the file {}",
             &output_file
@@ -146,6 +266,7 @@ fn write_prayer_times_file(
 
 fn write_prayer_times_terminal(
     pts_by_date: &BTreeMap<NaiveDate, BTreeMap<Prayer, Result<PrayerTime, ()>>>,
+    solar_days: Option<&BTreeMap<NaiveDate, SolarDay>>,
 ) {
     // Display the calculated prayer times in the terminal.
 
@@ -163,5 +284,75 @@ fn write_prayer_times_terminal(
                 println!("  {}: Invalid", pts.0);
             }
         }
+        if let Some(solar_day) = solar_days.and_then(|x| x.get(pts_for_date.0)) {
+            write_solar_day_terminal(solar_day);
+        }
+    }
+}
+
+fn write_solar_day_terminal(solar_day: &SolarDay) {
+    // Display the solar summary of a date in the terminal.
+
+    let fmt_time = |time: Result<NaiveTime, CrossingError>| match time {
+        Ok(time) => time.format("%l:%M %p").to_string(),
+        Err(err) => err.to_string(),
+    };
+
+    println!("  Solar noon: {}", solar_day.solar_noon.format("%l:%M %p"));
+    println!("  Sunrise: {}", fmt_time(solar_day.sunrise));
+    println!("  Sunset: {}", fmt_time(solar_day.sunset));
+    println!("  Day length: {:.2} hours", solar_day.day_length);
+    println!(
+        "  Equation of time: {:.2} minutes",
+        solar_day.equation_of_time
+    );
+    println!("  Declination: {:.2}°", solar_day.declination);
+    println!("  Maximum altitude: {:.2}°", solar_day.max_altitude);
+    for (name, twilight) in [
+        ("Civil", solar_day.civil_twilight),
+        ("Nautical", solar_day.nautical_twilight),
+        ("Astronomical", solar_day.astronomical_twilight),
+    ] {
+        println!(
+            "  {} twilight: {} - {}",
+            name,
+            fmt_time(twilight.rising),
+            fmt_time(twilight.setting)
+        );
+    }
+}
+
+fn write_ext_lat_spans_terminal(ext_lat_spans: &[ExtremeLatitudeSpan]) {
+    // Display the spans of dates in which a prayer time cannot be calculated conventionally in the terminal.
+
+    let fmt_mins = |mins: Option<f64>| match mins {
+        Some(mins) => format!("{:+.0} minutes", mins),
+        None => "n/a".to_string(),
+    };
+
+    println!("\nExtreme latitude spans");
+    if ext_lat_spans.is_empty() {
+        println!("  None");
+    }
+    for span in ext_lat_spans {
+        println!(
+            "  {}: {} - {}",
+            span.prayer,
+            span.start_date.format("%B %d, %Y"),
+            span.end_date.format("%B %d, %Y")
+        );
+        match span.fallback {
+            Some(fallback) => println!(
+                "    Falls back to: {:?} ({:?})",
+                fallback.method, fallback.reference
+            ),
+            None => println!("    Falls back to: Invalid"),
+        }
+        println!(
+            "    Discontinuity: {} at start, {} largest within, {} at end",
+            fmt_mins(span.start_discontinuity),
+            fmt_mins(span.largest_discontinuity),
+            fmt_mins(span.end_discontinuity)
+        );
     }
 }
@@ -1,38 +1,43 @@
-use std::{cell::RefCell, collections::HashMap};
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap},
+    ops::Bound::{Excluded, Included},
+};
 
-use chrono::Datelike;
+use chrono::{Datelike, Days, NaiveDate};
 
 use crate::{
-    geo::{astro::TopAstroDay, coordinates::Coordinates, julian_day::JulianDay},
+    geo::{
+        astro::{AstroCache, TopAstroDay},
+        coordinates::Coordinates,
+        julian_day::JulianDay,
+    },
     prayer_times::{
         hours::{get_hours, HRS_PER_DAY, MIN_SEC_PER_HR_MIN},
-        params::ExtremeLatitudeMethod,
+        params::{
+            ExtremeLatitudeMethod, LatitudeBandCondition, NearestLatitudeReference,
+            SearchDirection, TransitIntervals,
+        },
     },
-    Latitude, Weather,
+    Latitude, Qibla, Weather,
 };
 
-use super::{params::Params, Prayer};
+use super::{
+    params::Params,
+    strategy::{ExtremeLatitudeDay, ExtremeLatitudeStrategy, PrayerHour},
+    Prayer, Provenance, RangeCache, Reference,
+};
 
-#[derive(Debug, Clone, Copy)]
-pub struct PrayerHour {
-    pub value: f64,
-    pub extreme: bool,
-}
+// Prayer hours before they are adjusted for an extreme latitude.
+type RawHours = HashMap<Prayer, Result<f64, ()>>;
 
-impl PrayerHour {
-    fn new(hour: f64) -> Self {
-        Self {
-            value: hour,
-            extreme: false,
-        }
-    }
+// Degrees of latitude between the candidates of the nearest valid latitude.
+const NEAR_VALID_LAT_STEP: f64 = 0.5;
+// Degrees of longitude the Sun appears to move in an hour.
+const DEG_PER_HR: f64 = 15.;
 
-    fn new_extreme(hour: f64) -> Self {
-        Self {
-            value: hour,
-            extreme: true,
-        }
-    }
+fn provenance(method: ExtremeLatitudeMethod, reference: Reference) -> Provenance {
+    Provenance { method, reference }
 }
 
 pub fn adj_for_ext_lat(
@@ -40,41 +45,135 @@ pub fn adj_for_ext_lat(
     hours: HashMap<Prayer, Result<f64, ()>>,
     top_astro_day: &TopAstroDay,
     weather: Weather,
+    cache: &RangeCache,
 ) -> HashMap<Prayer, Result<PrayerHour, ()>> {
-    use ExtremeLatitudeMethod::*;
+    let day = ExtremeLatitudeDay {
+        params,
+        top_astro_day,
+        weather,
+        cache,
+    };
+    let method = match params.extreme_latitude_method {
+        ExtremeLatitudeMethod::LatitudeBands => {
+            get_band_method(params, &hours, top_astro_day.coords().latitude)
+        }
+        method => method,
+    };
+    let adj_hours = method.adjust(&day, &hours);
+    let hours: HashMap<_, _> =
+        HashMap::from_iter(adj_hours.into_iter().map(|x| (x.0, RefCell::new(x.1))));
 
-    let hours: HashMap<_, _> = HashMap::from_iter(
-        hours
-            .iter()
-            .map(|x| (*x.0, RefCell::new(x.1.map(PrayerHour::new)))),
-    );
+    adj_for_int(params, method, &hours);
 
-    if can_adj(&hours, params.extreme_latitude_method) {
-        match params.extreme_latitude_method {
-            AngleBased => angle_based(params, &hours),
-            NearestLatitudeAllPrayersAlways(nearest_latitude)
-            | NearestLatitudeFajrIshaAlways(nearest_latitude)
-            | NearestLatitudeFajrIshaInvalid(nearest_latitude) => {
-                adj_near_lat(params, &hours, top_astro_day, weather, nearest_latitude)
+    HashMap::from_iter(hours.iter().map(|x| (*x.0, x.1.borrow().map(|y| y))))
+}
+
+impl ExtremeLatitudeStrategy for ExtremeLatitudeMethod {
+    fn adjust(
+        &self,
+        day: &ExtremeLatitudeDay,
+        hours: &HashMap<Prayer, Result<f64, ()>>,
+    ) -> HashMap<Prayer, Result<PrayerHour, ()>> {
+        use ExtremeLatitudeMethod::*;
+
+        let (params, method) = (day.params, *self);
+        match self {
+            LatitudeBands => {
+                let method = get_band_method(params, hours, day.top_astro_day.coords().latitude);
+                return method.adjust(day, hours);
             }
-            NearestGoodDayAllPrayersAlways | NearestGoodDayFajrIshaInvalid => {
-                adj_near_good(params, &hours, top_astro_day, weather)
+            Custom => {
+                let mut adj_hours = match &params.custom_strategy {
+                    Some(strategy) => strategy.0.adjust(day, hours),
+                    _ => HashMap::new(),
+                };
+                // A prayer the strategy leaves out keeps its conventionally calculated hour.
+                for (prayer, hour) in hours {
+                    adj_hours
+                        .entry(*prayer)
+                        .or_insert_with(|| hour.map(PrayerHour::new));
+                }
+                return adj_hours;
             }
-            SeventhOfNightFajrIshaAlways
-            | SeventhOfNightFajrIshaInvalid
-            | SeventhOfDayFajrIshaAlways
-            | SeventhOfDayFajrIshaInvalid
-            | HalfOfNightFajrIshaAlways
-            | HalfOfNightFajrIshaInvalid => adj_sev_half(params, &hours),
-            MinutesFromMaghribFajrIshaAlways => adj_min_always(&hours),
-            MinutesFromMaghribFajrIshaInvalid => adj_min_inv(params, &hours),
             _ => {}
         }
+
+        let (top_astro_day, weather, cache) = (day.top_astro_day, day.weather, day.cache);
+        let hours: HashMap<_, _> = HashMap::from_iter(
+            hours
+                .iter()
+                .map(|x| (*x.0, RefCell::new(x.1.map(PrayerHour::new)))),
+        );
+
+        if can_adj(&hours, *self) {
+            match self {
+                AngleBased => angle_based(params, method, &hours),
+                NearestLatitudeAllPrayersAlways(nearest_latitude)
+                | NearestLatitudeFajrIshaAlways(nearest_latitude)
+                | NearestLatitudeFajrIshaInvalid(nearest_latitude) => adj_near_lat(
+                    params,
+                    method,
+                    &hours,
+                    top_astro_day,
+                    weather,
+                    *nearest_latitude,
+                ),
+                NearestGoodDayAllPrayersAlways | NearestGoodDayFajrIshaInvalid => {
+                    adj_near_good(params, method, &hours, top_astro_day, weather, cache)
+                }
+                SeventhOfNightFajrIshaAlways
+                | SeventhOfNightFajrIshaInvalid
+                | SeventhOfDayFajrIshaAlways
+                | SeventhOfDayFajrIshaInvalid
+                | HalfOfNightFajrIshaAlways
+                | HalfOfNightFajrIshaInvalid => adj_sev_half(params, method, &hours),
+                MinutesFromMaghribFajrIshaAlways => adj_min_always(params, method, &hours),
+                MinutesFromMaghribFajrIshaInvalid => adj_min_inv(params, method, &hours),
+                NearestValidLatitudeAllPrayersInvalid => {
+                    adj_near_valid_lat(params, method, &hours, top_astro_day, weather)
+                }
+                MeccaAllPrayersInvalid => adj_mecca(params, method, &hours, top_astro_day, weather),
+                TransitIntervalsAllPrayersInvalid(transit_intervals) => {
+                    adj_transit(method, &hours, *transit_intervals)
+                }
+                _ => {}
+            }
+        }
+
+        HashMap::from_iter(hours.into_iter().map(|x| (x.0, x.1.into_inner())))
     }
+}
 
-    adj_for_int(params, &hours);
+fn get_band_method(
+    params: &Params,
+    hours: &HashMap<Prayer, Result<f64, ()>>,
+    latitude: Latitude,
+) -> ExtremeLatitudeMethod {
+    use Prayer::*;
 
-    HashMap::from_iter(hours.iter().map(|x| (*x.0, x.1.borrow().map(|y| y))))
+    let latitude = f64::from(latitude).abs();
+    let mut bands: Vec<_> = params
+        .latitude_bands
+        .iter()
+        .filter(|x| x.min_latitude <= latitude)
+        .collect();
+    bands.sort_by(|x, y| y.min_latitude.total_cmp(&x.min_latitude));
+
+    bands
+        .iter()
+        .find(|x| match x.condition {
+            LatitudeBandCondition::Always => true,
+            LatitudeBandCondition::FajrIshaInvalid => {
+                hours[&Fajr].is_err() || hours[&Isha].is_err()
+            }
+            LatitudeBandCondition::AnyInvalid => hours.values().any(|x| x.is_err()),
+            LatitudeBandCondition::ShurooqMaghribInvalid => {
+                hours[&Shurooq].is_err() || hours[&Maghrib].is_err()
+            }
+        })
+        .map(|x| x.method)
+        .filter(|x| *x != ExtremeLatitudeMethod::LatitudeBands)
+        .unwrap_or(ExtremeLatitudeMethod::None)
 }
 
 fn can_adj(
@@ -89,6 +188,14 @@ fn has_inv_hours(hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>) -> bo
     hours.iter().any(|x| x.1.borrow().is_err())
 }
 
+pub fn inv_prayers(hours: &HashMap<Prayer, Result<f64, ()>>) -> BTreeSet<Prayer> {
+    hours
+        .iter()
+        .filter(|x| x.1.is_err())
+        .map(|x| *x.0)
+        .collect()
+}
+
 fn is_ext_lat_always(ext_lat_meth: ExtremeLatitudeMethod) -> bool {
     use ExtremeLatitudeMethod::*;
 
@@ -104,23 +211,47 @@ fn is_ext_lat_always(ext_lat_meth: ExtremeLatitudeMethod) -> bool {
     )
 }
 
-fn angle_based(params: &Params, hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>) {
+fn angle_based(
+    params: &Params,
+    method: ExtremeLatitudeMethod,
+    hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>,
+) {
+    use Prayer::*;
+
+    adj_night_fraction(params, method, hours, &[Fajr, Isha]);
+}
+
+// Moves Fajr or Isha prayer hours to the fraction of the night of their angles before Shurooq or
+// after Maghrib, respectively.
+fn adj_night_fraction(
+    params: &Params,
+    method: ExtremeLatitudeMethod,
+    hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>,
+    prayers: &[Prayer],
+) {
     use Prayer::*;
 
     if hours[&Shurooq].borrow().is_ok() && hours[&Maghrib].borrow().is_ok() {
         let shur_hour = hours[&Shurooq].borrow().unwrap().value;
         let magh_hour = hours[&Maghrib].borrow().unwrap().value;
-        let portion = HRS_PER_DAY - magh_hour + shur_hour;
+        // The hours of a reference may fall outside of the day.
+        let portion = (shur_hour - magh_hour).rem_euclid(HRS_PER_DAY);
         let ratio = 1. / MIN_SEC_PER_HR_MIN;
-        let fajr_diff = ratio * params.angles[&Fajr] * portion;
-        let isha_diff = ratio * params.angles[&Isha] * portion;
-        *hours[&Fajr].borrow_mut() = Ok(PrayerHour::new_extreme(shur_hour - fajr_diff));
-        *hours[&Isha].borrow_mut() = Ok(PrayerHour::new_extreme(magh_hour + isha_diff));
+        for prayer in prayers {
+            let fraction = ratio * params.angles[prayer];
+            let prov = provenance(method, Reference::NightFraction(fraction));
+            let hour = match prayer {
+                Fajr => shur_hour - fraction * portion,
+                _ => magh_hour + fraction * portion,
+            };
+            *hours[prayer].borrow_mut() = Ok(PrayerHour::new_extreme(hour, prov));
+        }
     }
 }
 
 fn adj_near_lat(
     params: &Params,
+    method: ExtremeLatitudeMethod,
     hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>,
     top_astro_day: &TopAstroDay,
     weather: Weather,
@@ -129,103 +260,347 @@ fn adj_near_lat(
     use ExtremeLatitudeMethod::*;
     use Prayer::*;
 
-    let mut coords = top_astro_day.coords();
-    coords.latitude = nearest_latitude;
-    let adj_top_astro_day = top_astro_day.new_coords(coords);
-    let adj_hours = get_hours(params, &adj_top_astro_day, weather);
-    let not_near_lat_fi_inv = !matches!(
-        params.extreme_latitude_method,
-        NearestLatitudeFajrIshaInvalid(_)
-    );
+    let Some((reference, adj_hours)) =
+        get_near_lat_hours(params, top_astro_day, weather, nearest_latitude)
+    else {
+        return;
+    };
+    let prov = provenance(method, reference);
+    let new_extreme = |hour| PrayerHour::new_extreme(hour, prov);
+    let not_near_lat_fi_inv = !matches!(method, NearestLatitudeFajrIshaInvalid(_));
 
     if let Ok(adj_hour) = adj_hours[&Fajr] {
         let mut hours_res = hours[&Fajr].borrow_mut();
         if not_near_lat_fi_inv || hours_res.is_err() {
-            *hours_res = Ok(PrayerHour::new_extreme(adj_hour));
+            *hours_res = Ok(new_extreme(adj_hour));
         }
     }
 
     if let Ok(adj_hour) = adj_hours[&Isha] {
         let mut hours_res = hours[&Isha].borrow_mut();
         if not_near_lat_fi_inv || hours_res.is_err() {
-            *hours_res = Ok(PrayerHour::new_extreme(adj_hour));
+            *hours_res = Ok(new_extreme(adj_hour));
         }
     }
 
-    if matches!(
-        params.extreme_latitude_method,
-        NearestLatitudeAllPrayersAlways(_)
-    ) {
-        *hours[&Shurooq].borrow_mut() = adj_hours[&Shurooq].map(PrayerHour::new_extreme);
-        hours[&Dhuhr].borrow_mut().as_mut().unwrap().extreme = true;
-        *hours[&Asr].borrow_mut() = adj_hours[&Asr].map(PrayerHour::new_extreme);
-        *hours[&Maghrib].borrow_mut() = adj_hours[&Maghrib].map(PrayerHour::new_extreme);
+    if matches!(method, NearestLatitudeAllPrayersAlways(_)) {
+        *hours[&Shurooq].borrow_mut() = adj_hours[&Shurooq].map(new_extreme);
+        hours[&Dhuhr].borrow_mut().as_mut().unwrap().provenance = Some(prov);
+        *hours[&Asr].borrow_mut() = adj_hours[&Asr].map(new_extreme);
+        *hours[&Maghrib].borrow_mut() = adj_hours[&Maghrib].map(new_extreme);
     }
 }
 
-fn adj_near_good(
+// Returns the prayer hours of the reference of the nearest latitude, and the reference.
+fn get_near_lat_hours(
+    params: &Params,
+    top_astro_day: &TopAstroDay,
+    weather: Weather,
+    nearest_latitude: Latitude,
+) -> Option<(Reference, RawHours)> {
+    use Prayer::*;
+
+    match params.nearest_latitude {
+        NearestLatitudeReference::Fixed => {
+            let mut coords = top_astro_day.coords();
+            coords.latitude = nearest_latitude;
+            let adj_hours = get_hours(params, &top_astro_day.new_coords(coords), weather);
+            Some((Reference::Latitude(nearest_latitude), adj_hours))
+        }
+        NearestLatitudeReference::Automatic => {
+            get_valid_lat_hours(params, top_astro_day, weather, &[Fajr, Isha])
+                .map(|(latitude, adj_hours)| (Reference::Latitude(latitude), adj_hours))
+        }
+        NearestLatitudeReference::City(city) => {
+            // The geocentric positions of the day are shared with the city, whose prayer hours
+            // at the same local times are moved to the same solar times at the longitude.
+            let shift = (f64::from(city.coords.longitude)
+                - f64::from(top_astro_day.coords().longitude))
+                / DEG_PER_HR;
+            let adj_hours = get_hours(params, &top_astro_day.new_coords(city.coords), weather)
+                .into_iter()
+                .map(|(prayer, hour)| (prayer, hour.map(|x| x + shift)))
+                .collect();
+            Some((Reference::City(city), adj_hours))
+        }
+    }
+}
+
+// Returns the highest latitude, from that of the location toward the equator in steps, at which
+// the prayer hours are valid, and the prayer hours there.
+fn get_valid_lat_hours(
+    params: &Params,
+    top_astro_day: &TopAstroDay,
+    weather: Weather,
+    prayers: &[Prayer],
+) -> Option<(Latitude, RawHours)> {
+    let latitude = top_astro_day.coords().latitude;
+    let lat_val = f64::from(latitude);
+    let steps = (lat_val.abs() / NEAR_VALID_LAT_STEP).ceil() as u32;
+    let step_lats = (0..steps)
+        .rev()
+        .map(|x| Latitude::try_from(lat_val.signum() * x as f64 * NEAR_VALID_LAT_STEP).unwrap());
+
+    std::iter::once(latitude).chain(step_lats).find_map(|x| {
+        let mut coords = top_astro_day.coords();
+        coords.latitude = x;
+        let adj_hours = get_hours(params, &top_astro_day.new_coords(coords), weather);
+        prayers
+            .iter()
+            .all(|y| adj_hours[y].is_ok())
+            .then_some((x, adj_hours))
+    })
+}
+
+fn adj_near_valid_lat(
     params: &Params,
+    method: ExtremeLatitudeMethod,
     hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>,
     top_astro_day: &TopAstroDay,
     weather: Weather,
 ) {
-    use ExtremeLatitudeMethod::*;
     use Prayer::*;
 
-    let mut adj_hours = HashMap::new();
-    let julian_day = top_astro_day.julian_day();
-    for i in 0..=julian_day.date.ordinal() {
-        if let Some(hour) = test_fajr_isha(
-            params,
-            top_astro_day.coords(),
-            weather,
-            julian_day.sub(i as u64),
-        ) {
-            adj_hours = hour;
-            break;
-        }
+    let day_prayers = [Shurooq, Asr, Maghrib];
+    if day_prayers.iter().all(|x| hours[x].borrow().is_ok()) {
+        return;
+    }
 
-        if let Some(hour) = test_fajr_isha(
-            params,
-            top_astro_day.coords(),
-            weather,
-            julian_day.add(i as u64),
-        ) {
-            adj_hours = hour;
-            break;
+    if let Some((latitude, adj_hours)) =
+        get_valid_lat_hours(params, top_astro_day, weather, &day_prayers)
+    {
+        let prov = provenance(method, Reference::Latitude(latitude));
+        for (prayer, adj_hour) in adj_hours {
+            *hours[&prayer].borrow_mut() = adj_hour.map(|x| PrayerHour::new_extreme(x, prov));
         }
+
+        // Fajr or Isha also invalid at the latitude falls back to the fraction of its night.
+        let inv_prayers: Vec<_> = [Fajr, Isha]
+            .into_iter()
+            .filter(|x| hours[x].borrow().is_err())
+            .collect();
+        adj_night_fraction(params, method, hours, &inv_prayers);
     }
+}
+
+fn adj_mecca(
+    params: &Params,
+    method: ExtremeLatitudeMethod,
+    hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>,
+    top_astro_day: &TopAstroDay,
+    weather: Weather,
+) {
+    use Prayer::*;
+
+    let mecca_hours = get_hours(
+        params,
+        &top_astro_day.new_coords(Qibla::kaaba_coords()),
+        weather,
+    );
+    let dhuhr_hour = hours[&Dhuhr].borrow().unwrap().value;
+    let shift = dhuhr_hour - mecca_hours[&Dhuhr].unwrap();
+    let prov = provenance(method, Reference::Mecca(shift * MIN_SEC_PER_HR_MIN));
+    for prayer in [Fajr, Shurooq, Asr, Maghrib, Isha] {
+        *hours[&prayer].borrow_mut() =
+            mecca_hours[&prayer].map(|x| PrayerHour::new_extreme(x + shift, prov));
+    }
+}
+
+fn adj_transit(
+    method: ExtremeLatitudeMethod,
+    hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>,
+    transit_intervals: TransitIntervals,
+) {
+    use Prayer::*;
+
+    let dhuhr_hour = hours[&Dhuhr].borrow().unwrap().value;
+    for (prayer, interval) in [
+        (Fajr, transit_intervals.fajr()),
+        (Shurooq, transit_intervals.shurooq()),
+        (Asr, transit_intervals.asr()),
+        (Maghrib, transit_intervals.maghrib()),
+        (Isha, transit_intervals.isha()),
+    ] {
+        let prov = provenance(method, Reference::Transit(interval));
+        *hours[&prayer].borrow_mut() = Ok(PrayerHour::new_extreme(
+            dhuhr_hour + interval / MIN_SEC_PER_HR_MIN,
+            prov,
+        ));
+    }
+}
+
+fn adj_near_good(
+    params: &Params,
+    method: ExtremeLatitudeMethod,
+    hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>,
+    top_astro_day: &TopAstroDay,
+    weather: Weather,
+    cache: &RangeCache,
+) {
+    use ExtremeLatitudeMethod::*;
+    use Prayer::*;
 
-    if !adj_hours.is_empty() {
-        if params.extreme_latitude_method == NearestGoodDayAllPrayersAlways {
-            *hours[&Fajr].borrow_mut() = adj_hours[&Fajr].map(PrayerHour::new_extreme);
-            *hours[&Shurooq].borrow_mut() = adj_hours[&Shurooq].map(PrayerHour::new_extreme);
-            *hours[&Dhuhr].borrow_mut() = adj_hours[&Dhuhr].map(PrayerHour::new_extreme);
-            *hours[&Asr].borrow_mut() = adj_hours[&Asr].map(PrayerHour::new_extreme);
-            *hours[&Maghrib].borrow_mut() = adj_hours[&Maghrib].map(PrayerHour::new_extreme);
-            *hours[&Isha].borrow_mut() = adj_hours[&Isha].map(PrayerHour::new_extreme);
+    let julian_day = top_astro_day.julian_day();
+    let coords = top_astro_day.coords();
+    let good_day = test_fajr_isha(params, coords, weather, julian_day, &cache.astros)
+        .map(|hours| (julian_day.date, hours))
+        .or_else(|| {
+            let adj_date = get_good_day(params, coords, weather, julian_day, cache)?;
+            let adj_julian_day = JulianDay::new(adj_date, julian_day.gmt);
+            test_fajr_isha(params, coords, weather, adj_julian_day, &cache.astros)
+                .map(|hours| (adj_date, hours))
+        });
+
+    if let Some((adj_date, adj_hours)) = good_day {
+        let prov = provenance(method, Reference::Date(adj_date));
+        let new_extreme = |hour| PrayerHour::new_extreme(hour, prov);
+        if method == NearestGoodDayAllPrayersAlways {
+            *hours[&Fajr].borrow_mut() = adj_hours[&Fajr].map(new_extreme);
+            *hours[&Shurooq].borrow_mut() = adj_hours[&Shurooq].map(new_extreme);
+            *hours[&Dhuhr].borrow_mut() = adj_hours[&Dhuhr].map(new_extreme);
+            *hours[&Asr].borrow_mut() = adj_hours[&Asr].map(new_extreme);
+            *hours[&Maghrib].borrow_mut() = adj_hours[&Maghrib].map(new_extreme);
+            *hours[&Isha].borrow_mut() = adj_hours[&Isha].map(new_extreme);
         } else {
             // NearestGoodDayFajrIshaInvalid
             if hours[&Fajr].borrow().is_err() {
-                *hours[&Fajr].borrow_mut() = adj_hours[&Fajr].map(PrayerHour::new_extreme);
+                *hours[&Fajr].borrow_mut() = adj_hours[&Fajr].map(new_extreme);
             }
 
             if hours[&Isha].borrow().is_err() {
-                *hours[&Isha].borrow_mut() = adj_hours[&Isha].map(PrayerHour::new_extreme);
+                *hours[&Isha].borrow_mut() = adj_hours[&Isha].map(new_extreme);
             }
         }
     }
 }
 
+// The latitude, longitude, elevation, and Fajr and Isha angles of the good days of a year, as
+// bits so that they can be hashed. The built-in rules of Fajr and Isha depend on nothing else.
+type GoodDayKey = [u64; 5];
+
+// The good days of the nearest good day search by year, computed once for each location and
+// angles and shared by the dates of a range, including those without a good day on either side.
+#[derive(Debug, Default)]
+pub struct GoodDayCache {
+    good_days: RefCell<HashMap<(GoodDayKey, i32), BTreeSet<NaiveDate>>>,
+}
+
+impl GoodDayCache {
+    // Years kept before that of the current date when the window slides.
+    const WINDOW_YEARS: i32 = 1;
+
+    // Drops the good days of the years before the window ending at a date.
+    pub fn slide(&self, date: NaiveDate) {
+        let start_year = date.year() - Self::WINDOW_YEARS;
+        self.good_days
+            .borrow_mut()
+            .retain(|(_, year), _| *year >= start_year);
+    }
+
+    // Returns the result of a function of the good days of a year, computing them when missing.
+    fn with_year<T>(
+        &self,
+        key: GoodDayKey,
+        year: i32,
+        get_good_days: impl FnOnce() -> BTreeSet<NaiveDate>,
+        f: impl FnOnce(&BTreeSet<NaiveDate>) -> T,
+    ) -> T {
+        if !self.good_days.borrow().contains_key(&(key, year)) {
+            let good_days = get_good_days();
+            self.good_days.borrow_mut().insert((key, year), good_days);
+        }
+        f(&self.good_days.borrow()[&(key, year)])
+    }
+}
+
+// Returns the nearest good day within the search limits in the preferred direction.
+fn get_good_day(
+    params: &Params,
+    coords: Coordinates,
+    weather: Weather,
+    julian_day: JulianDay,
+    cache: &RangeCache,
+) -> Option<NaiveDate> {
+    use Prayer::*;
+
+    let search = params.nearest_good_day;
+    let date = julian_day.date;
+    let is_good = |x| test_fajr_isha(params, coords, weather, x, &cache.astros).is_some();
+    let (before, after) = if params.rules.contains_key(&Fajr) || params.rules.contains_key(&Isha) {
+        // A custom rule may depend on more than the location and angles, so the good days are
+        // searched for the date instead.
+        let before = (1..=search.max_days_before as u64)
+            .map(|i| julian_day.sub(i))
+            .find(|x| is_good(*x))
+            .map(|x| x.date);
+        let after = (1..=search.max_days_after as u64)
+            .map(|i| julian_day.add(i))
+            .find(|x| is_good(*x))
+            .map(|x| x.date);
+        (before, after)
+    } else {
+        let key = [
+            f64::from(coords.latitude).to_bits(),
+            f64::from(coords.longitude).to_bits(),
+            f64::from(coords.elevation).to_bits(),
+            params.angles[&Fajr].to_bits(),
+            params.angles[&Isha].to_bits(),
+        ];
+        let get_good_days = |year| {
+            move || {
+                NaiveDate::from_ymd_opt(year, 1, 1)
+                    .unwrap()
+                    .iter_days()
+                    .take_while(|x| x.year() == year)
+                    .filter(|x| is_good(JulianDay::new(*x, julian_day.gmt)))
+                    .collect()
+            }
+        };
+        let first_date = date
+            .checked_sub_days(Days::new(search.max_days_before as u64))
+            .unwrap_or(NaiveDate::MIN);
+        let last_date = date
+            .checked_add_days(Days::new(search.max_days_after as u64))
+            .unwrap_or(NaiveDate::MAX);
+        let before = (first_date.year()..=date.year()).rev().find_map(|year| {
+            cache
+                .good_days
+                .with_year(key, year, get_good_days(year), |x| {
+                    x.range(first_date..date).next_back().copied()
+                })
+        });
+        let after = (date.year()..=last_date.year()).find_map(|year| {
+            cache
+                .good_days
+                .with_year(key, year, get_good_days(year), |x| {
+                    x.range((Excluded(date), Included(last_date)))
+                        .next()
+                        .copied()
+                })
+        });
+        (before, after)
+    };
+
+    match search.direction {
+        SearchDirection::Nearest => match (before, after) {
+            (Some(before), Some(after)) if after - date < date - before => Some(after),
+            (before, after) => before.or(after),
+        },
+        SearchDirection::Earlier => before.or(after),
+        SearchDirection::Later => after.or(before),
+    }
+}
+
 fn test_fajr_isha(
     params: &Params,
     coords: Coordinates,
     weather: Weather,
     julian_day: JulianDay,
+    cache: &AstroCache,
 ) -> Option<HashMap<Prayer, Result<f64, ()>>> {
     use Prayer::*;
 
-    let top_astro_day = TopAstroDay::from_jd(julian_day, coords);
+    let top_astro_day = TopAstroDay::from_cache(cache, julian_day, coords);
     let hours = get_hours(params, &top_astro_day, weather);
     if hours[&Fajr].is_ok() && hours[&Isha].is_ok() {
         Some(hours)
@@ -234,14 +609,18 @@ fn test_fajr_isha(
     }
 }
 
-fn adj_sev_half(params: &Params, hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>) {
+fn adj_sev_half(
+    params: &Params,
+    method: ExtremeLatitudeMethod,
+    hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>,
+) {
     use ExtremeLatitudeMethod::*;
     use Prayer::*;
 
     if hours[&Shurooq].borrow().is_ok() && hours[&Maghrib].borrow().is_ok() {
         let shur_hour = hours[&Shurooq].borrow().as_ref().unwrap().value;
         let magh_hour = hours[&Maghrib].borrow().as_ref().unwrap().value;
-        let portion = match params.extreme_latitude_method {
+        let portion = match method {
             SeventhOfNightFajrIshaAlways | SeventhOfNightFajrIshaInvalid => {
                 (HRS_PER_DAY - (magh_hour - shur_hour)) / 7.
             }
@@ -250,47 +629,63 @@ fn adj_sev_half(params: &Params, hours: &HashMap<Prayer, RefCell<Result<PrayerHo
             } // HalfOfNightFajrIshaAlways | HalfOfNightFajrIshaInvalid
             _ => (HRS_PER_DAY - magh_hour - shur_hour) * 0.5,
         };
+        let reference = match method {
+            SeventhOfNightFajrIshaAlways | SeventhOfNightFajrIshaInvalid => {
+                Reference::NightFraction(1. / 7.)
+            }
+            SeventhOfDayFajrIshaAlways | SeventhOfDayFajrIshaInvalid => {
+                Reference::DayFraction(1. / 7.)
+            } // HalfOfNightFajrIshaAlways | HalfOfNightFajrIshaInvalid
+            _ => Reference::NightFraction(0.5),
+        };
+        let prov = provenance(method, reference);
 
-        match params.extreme_latitude_method {
+        match method {
             SeventhOfNightFajrIshaAlways
             | SeventhOfDayFajrIshaAlways
             | HalfOfNightFajrIshaAlways => {
-                if params.extreme_latitude_method == HalfOfNightFajrIshaAlways {
+                if method == HalfOfNightFajrIshaAlways {
                     *hours[&Fajr].borrow_mut() = Ok(PrayerHour::new_extreme(
                         portion - params.intervals[&Fajr] / MIN_SEC_PER_HR_MIN,
+                        prov,
                     ));
                     *hours[&Isha].borrow_mut() = Ok(PrayerHour::new_extreme(
                         portion + params.intervals[&Isha] / MIN_SEC_PER_HR_MIN,
+                        prov,
                     ));
                 } else {
                     // SeventhOfNightFajrIshaAlways | SeventhOfDayFajrIshaAlways
-                    *hours[&Fajr].borrow_mut() = Ok(PrayerHour::new_extreme(shur_hour - portion));
-                    *hours[&Isha].borrow_mut() = Ok(PrayerHour::new_extreme(magh_hour + portion));
+                    *hours[&Fajr].borrow_mut() =
+                        Ok(PrayerHour::new_extreme(shur_hour - portion, prov));
+                    *hours[&Isha].borrow_mut() =
+                        Ok(PrayerHour::new_extreme(magh_hour + portion, prov));
                 }
             }
             _ => {
                 // SeventhOfNightFajrIshaInvalid | SeventhOfDayFajrIshaInvalid | HalfOfNightFajrIshaInvalid
                 if hours[&Fajr].borrow().is_err() {
-                    if params.extreme_latitude_method == HalfOfNightFajrIshaInvalid {
+                    if method == HalfOfNightFajrIshaInvalid {
                         *hours[&Fajr].borrow_mut() = Ok(PrayerHour::new_extreme(
                             portion - params.intervals[&Fajr] / MIN_SEC_PER_HR_MIN,
+                            prov,
                         ));
                     } else {
                         // SeventhOfNightFajrIshaInvalid | SeventhOfDayFajrIshaInvalid
                         *hours[&Fajr].borrow_mut() =
-                            Ok(PrayerHour::new_extreme(shur_hour - portion));
+                            Ok(PrayerHour::new_extreme(shur_hour - portion, prov));
                     }
                 }
 
                 if hours[&Isha].borrow().is_err() {
-                    if params.extreme_latitude_method == HalfOfNightFajrIshaInvalid {
+                    if method == HalfOfNightFajrIshaInvalid {
                         *hours[&Isha].borrow_mut() = Ok(PrayerHour::new_extreme(
                             portion + params.intervals[&Isha] / MIN_SEC_PER_HR_MIN,
+                            prov,
                         ));
                     } else {
                         // SeventhOfNightFajrIshaInvalid | SeventhOfDayFajrIshaInvalid
                         *hours[&Isha].borrow_mut() =
-                            Ok(PrayerHour::new_extreme(magh_hour + portion));
+                            Ok(PrayerHour::new_extreme(magh_hour + portion, prov));
                     }
                 }
             }
@@ -298,28 +693,41 @@ fn adj_sev_half(params: &Params, hours: &HashMap<Prayer, RefCell<Result<PrayerHo
     }
 }
 
-fn adj_min_always(hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>) {
+fn adj_min_always(
+    params: &Params,
+    method: ExtremeLatitudeMethod,
+    hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>,
+) {
     use Prayer::*;
 
     // Do nothing because this is implemented through fajr and isha intervals.
+    let fajr_prov = provenance(method, Reference::Minutes(params.intervals[&Fajr]));
     *hours[&Fajr].borrow_mut() = hours[&Shurooq].borrow().map(|mut x| {
-        x.extreme = true;
+        x.provenance = Some(fajr_prov);
         x
     });
 
+    let isha_prov = provenance(method, Reference::Minutes(params.intervals[&Isha]));
     *hours[&Isha].borrow_mut() = hours[&Maghrib].borrow().map(|mut x| {
-        x.extreme = true;
+        x.provenance = Some(isha_prov);
         x
     });
 }
 
-fn adj_min_inv(params: &Params, hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>) {
+fn adj_min_inv(
+    params: &Params,
+    method: ExtremeLatitudeMethod,
+    hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>,
+) {
     use Prayer::*;
 
     if hours[&Fajr].borrow().is_err() {
         *hours[&Fajr].borrow_mut() = hours[&Shurooq].borrow().map(|mut x| {
             x.value -= params.intervals[&Fajr] / MIN_SEC_PER_HR_MIN;
-            x.extreme = true;
+            x.provenance = Some(provenance(
+                method,
+                Reference::Minutes(params.intervals[&Fajr]),
+            ));
             x
         });
     }
@@ -327,34 +735,43 @@ fn adj_min_inv(params: &Params, hours: &HashMap<Prayer, RefCell<Result<PrayerHou
     if hours[&Isha].borrow().is_err() {
         *hours[&Isha].borrow_mut() = hours[&Maghrib].borrow().map(|mut x| {
             x.value += params.intervals[&Isha] / MIN_SEC_PER_HR_MIN;
-            x.extreme = true;
+            x.provenance = Some(provenance(
+                method,
+                Reference::Minutes(params.intervals[&Isha]),
+            ));
             x
         });
     }
 }
 
-fn adj_for_int(params: &Params, hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>) {
+// Applies the intervals of the `Params` to Fajr and Isha, unless the extreme latitude method
+// applied to the day already does or the prayer has a custom rule of its own.
+fn adj_for_int(
+    params: &Params,
+    method: ExtremeLatitudeMethod,
+    hours: &HashMap<Prayer, RefCell<Result<PrayerHour, ()>>>,
+) {
     use ExtremeLatitudeMethod::*;
     use Prayer::*;
 
-    if params.extreme_latitude_method != MinutesFromMaghribFajrIshaInvalid
-        && params.extreme_latitude_method != HalfOfNightFajrIshaInvalid
-        && params.extreme_latitude_method != HalfOfNightFajrIshaAlways
+    if method != MinutesFromMaghribFajrIshaInvalid
+        && method != HalfOfNightFajrIshaInvalid
+        && method != HalfOfNightFajrIshaAlways
     {
-        if params.intervals[&Fajr] != 0. {
-            let extreme = hours[&Fajr].borrow().unwrap().extreme;
+        if params.intervals[&Fajr] != 0. && !params.rules.contains_key(&Fajr) {
+            let prov = hours[&Fajr].borrow().ok().and_then(|x| x.provenance);
             *hours[&Fajr].borrow_mut() = hours[&Shurooq].borrow().map(|mut x| {
                 x.value -= params.intervals[&Fajr] / MIN_SEC_PER_HR_MIN;
-                x.extreme = extreme;
+                x.provenance = prov;
                 x
             });
         }
 
-        if params.intervals[&Isha] != 0. {
-            let extreme = hours[&Isha].borrow().unwrap().extreme;
+        if params.intervals[&Isha] != 0. && !params.rules.contains_key(&Isha) {
+            let prov = hours[&Isha].borrow().ok().and_then(|x| x.provenance);
             *hours[&Isha].borrow_mut() = hours[&Maghrib].borrow().map(|mut x| {
                 x.value += params.intervals[&Isha] / MIN_SEC_PER_HR_MIN;
-                x.extreme = extreme;
+                x.provenance = prov;
                 x
             });
         }
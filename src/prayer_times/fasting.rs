@@ -0,0 +1,197 @@
+//! Fasting times of a day at a geographical location.
+//!
+//! [`fasting_times_dt`] returns the [`FastingTimes`] from [`Imsaak`](Prayer::Imsaak) to
+//! [`Maghrib`](Prayer::Maghrib) of a day. When the fast is longer than the threshold of the
+//! [`FastingCap`] of the [`Params`], such as during the summer at a high latitude, it is
+//! shortened by rescaling it about Dhuhr to the target length of the cap. The prayer times
+//! themselves are not changed.
+
+use std::collections::BTreeMap;
+
+use chrono::{NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    geo::{
+        astro::TopAstroDay,
+        coordinates::{Coordinates, Location},
+        julian_day::JulianDay,
+    },
+    DateRange, Qibla, Weather, WeatherSeries,
+};
+
+use super::{
+    current_weather, get_hours_adj_ext, get_imsaak_hour,
+    hours::{hour_to_time, HRS_PER_DAY, MIN_SEC_PER_HR_MIN},
+    FastingCap, Params, Prayer, RangeCache,
+};
+
+/// The times a fast begins and ends on a day.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FastingTimes {
+    /// The time the fast begins.
+    pub imsaak: NaiveTime,
+    /// The time the fast ends.
+    pub iftar: NaiveTime,
+    /// The length of the fast in hours from the (rounded) time it begins to the time it ends.
+    pub hours: f64,
+    /// Whether the fast is shortened to the [`FastingCap`].
+    pub capped: bool,
+}
+
+/// Returns a [`B-tree`](std::collections::BTreeMap) of [`NaiveDate`] keys to [`FastingTimes`]
+/// values using the specified [`Params`] for a [`Location`], [`DateRange`], and its (optional)
+/// [`WeatherSeries`].
+pub fn fasting_times_dt_rng(
+    params: &Params,
+    location: Location,
+    date_range: &DateRange,
+    weather_series: Option<&WeatherSeries>,
+) -> BTreeMap<NaiveDate, Result<FastingTimes, ()>> {
+    let cache = RangeCache::new(params, location);
+    let mut times = BTreeMap::new();
+    for date in date_range
+        .start_date()
+        .iter_days()
+        .take(date_range.num_days())
+    {
+        let weather = weather_series.and_then(|x| x.weather_on(date));
+        times.insert(
+            date,
+            get_fasting_times(params, location, date, weather, &cache),
+        );
+        cache.slide(date);
+    }
+    times
+}
+
+/// Returns the [`FastingTimes`] using the specified [`Params`] for a [`Location`],
+/// [`NaiveDate`], and its (optional) current [`Weather`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// use chrono::NaiveDate;
+/// use islamic_prayer_times::*;
+///
+/// let mut params = Params::new(Method::Isna);
+/// params.fasting_cap = FastingCap::Hours(18.);
+/// let latitude = Latitude::try_from(59.91)?;
+/// let longitude = Longitude::try_from(10.75)?;
+/// let elevation = Elevation::try_from(0.)?;
+/// let coords = Coordinates::new(latitude, longitude, elevation);
+/// let gmt = Gmt::try_from(2.)?;
+/// let location = Location { coords, gmt };
+/// let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+///
+/// let fasting_times = fasting_times_dt(&params, location, date, None).unwrap();
+///
+/// assert!(fasting_times.capped);
+/// # Ok::<(), Box<dyn Error>>(())
+/// ```
+#[allow(clippy::result_unit_err)]
+pub fn fasting_times_dt(
+    params: &Params,
+    location: Location,
+    date: NaiveDate,
+    weather: Option<Weather>,
+) -> Result<FastingTimes, ()> {
+    let cache = RangeCache::new(params, location);
+    get_fasting_times(params, location, date, weather, &cache)
+}
+
+fn get_fasting_times(
+    params: &Params,
+    location: Location,
+    date: NaiveDate,
+    weather: Option<Weather>,
+    cache: &RangeCache,
+) -> Result<FastingTimes, ()> {
+    let weather = current_weather(params, location, weather);
+    let julian_day = JulianDay::new(date, location.gmt);
+    let top_astro_day = TopAstroDay::from_cache(&cache.astros, julian_day, location.coords);
+    let (params_imsaak, imsaak, dhuhr, maghrib) =
+        get_fast_hours(params, &top_astro_day, weather, cache)?;
+
+    // The threshold a fast must exceed to be capped, and the target length it is capped at.
+    let cap = match params.fasting_cap {
+        FastingCap::None => None,
+        FastingCap::Hours(hours) => Some((hours, Some(hours))),
+        FastingCap::Mecca(threshold) => Some((
+            threshold,
+            get_ref_fast_len(
+                params,
+                &top_astro_day,
+                Qibla::kaaba_coords(),
+                weather,
+                cache,
+            ),
+        )),
+        FastingCap::Reference(coords, threshold) => Some((
+            threshold,
+            get_ref_fast_len(params, &top_astro_day, coords, weather, cache),
+        )),
+    };
+
+    // Rescale the fast about Dhuhr so that both ends move toward it in proportion.
+    let len = maghrib - imsaak;
+    let (imsaak, maghrib, capped) = match cap {
+        Some((threshold, Some(target))) if len > threshold && len > target => {
+            let scale = target / len;
+            (
+                dhuhr - (dhuhr - imsaak) * scale,
+                dhuhr + (maghrib - dhuhr) * scale,
+                true,
+            )
+        }
+        _ => (imsaak, maghrib, false),
+    };
+
+    let imsaak = hour_to_time(&params_imsaak, Prayer::Fajr, imsaak);
+    let iftar = hour_to_time(params, Prayer::Maghrib, maghrib);
+    // The length between the rounded times, a fast never being a whole day or longer.
+    let secs_per_day = HRS_PER_DAY * MIN_SEC_PER_HR_MIN * MIN_SEC_PER_HR_MIN;
+    let secs = ((iftar - imsaak).num_seconds() as f64).rem_euclid(secs_per_day);
+
+    Ok(FastingTimes {
+        imsaak,
+        iftar,
+        hours: secs / (MIN_SEC_PER_HR_MIN * MIN_SEC_PER_HR_MIN),
+        capped,
+    })
+}
+
+// Returns the length in hours of the fast at the reference coordinates on the same day.
+fn get_ref_fast_len(
+    params: &Params,
+    top_astro_day: &TopAstroDay,
+    coords: Coordinates,
+    weather: Weather,
+    cache: &RangeCache,
+) -> Option<f64> {
+    get_fast_hours(params, &top_astro_day.new_coords(coords), weather, cache)
+        .ok()
+        .map(|(_, imsaak, _, maghrib)| maghrib - imsaak)
+}
+
+// Returns the params adjusted for Imsaak, and the hours of Imsaak, Dhuhr, and Maghrib, with
+// Imsaak before and Maghrib after Dhuhr, such as Imsaak before midnight.
+fn get_fast_hours(
+    params: &Params,
+    top_astro_day: &TopAstroDay,
+    weather: Weather,
+    cache: &RangeCache,
+) -> Result<(Params, f64, f64, f64), ()> {
+    let (params_imsaak, imsaak) = get_imsaak_hour(params, top_astro_day, weather, cache)?;
+    let hours = get_hours_adj_ext(params, top_astro_day, weather, cache);
+    let dhuhr = hours[&Prayer::Dhuhr]?.value;
+    let maghrib = hours[&Prayer::Maghrib]?.value;
+
+    Ok((
+        params_imsaak,
+        dhuhr - (dhuhr - imsaak.value).rem_euclid(HRS_PER_DAY),
+        dhuhr,
+        dhuhr + (maghrib - dhuhr).rem_euclid(HRS_PER_DAY),
+    ))
+}
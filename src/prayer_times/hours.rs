@@ -3,21 +3,26 @@ use std::{collections::HashMap, ops::Rem};
 use chrono::NaiveTime;
 
 use crate::{
-    angle::{LimitAngle, TWO_PI_DEG},
+    angle::{LimitAngle, RIGHT_ANG_DEG, TWO_PI_DEG},
     geo::astro::TopAstroDay,
     prayer_times::params::{Params, RoundSeconds},
-    Weather,
+    CrossingError, RefractionModel, Weather,
 };
 
-use super::Prayer;
+use super::{AngleRule, Prayer, PrayerDay, PrayerRule, ShadowRatioRule, SunriseSunsetRule};
 
 pub const MIN_SEC_PER_HR_MIN: f64 = 60.;
 pub const HRS_PER_DAY: f64 = 24.;
 
 const DEGREES_TO_10_BASE: f64 = 0.06666666666666667;
 const CENTER_OF_SUN_ANGLE: f64 = -0.83337;
+// Semi-diameter of the sun in degrees
+pub const SUN_SEMI_DIAMETER: f64 = 0.26667;
 const DEF_ROUND_SEC: f64 = 30.;
 const AGGRESSIVE_ROUND_SEC: f64 = 1.;
+// Tolerance in days of a rise or set, and the most corrections to reach it
+const RISE_SET_TOLERANCE: f64 = 0.1 / 86400.;
+const MAX_RISE_SET_ITERATIONS: usize = 10;
 
 pub fn get_hours(
     params: &Params,
@@ -26,65 +31,136 @@ pub fn get_hours(
 ) -> HashMap<Prayer, Result<f64, ()>> {
     use Prayer::*;
 
-    let (shur_hour_res, dhuhr_hour, magh_hour_res) = get_shur_dhuhr_magh(top_astro_day, weather);
-    let (fajr_hour_res, isha_hour_res) = get_fajr_isha(params, top_astro_day, dhuhr_hour);
-    let asr_hour_res = get_asr(params, top_astro_day, dhuhr_hour);
+    let horizon_dip = params.horizon_dip.degrees(top_astro_day.coords().elevation);
+    let (shur_magh_res, dhuhr_hour) =
+        get_shur_dhuhr_magh(top_astro_day, weather, params.refraction_model, horizon_dip);
+    let day = PrayerDay {
+        params,
+        top_astro_day,
+        weather,
+        dhuhr_hour,
+        shur_magh_res,
+    };
+    let hour = |prayer: Prayer, built_in: &dyn PrayerRule| match params.rules.get(&prayer) {
+        Some(rule) => rule.0.hour(prayer, &day),
+        None => built_in.hour(prayer, &day),
+    };
 
     let mut hours = HashMap::new();
-    hours.insert(Fajr, fajr_hour_res);
-    hours.insert(Shurooq, shur_hour_res);
+    hours.insert(Fajr, hour(Fajr, &AngleRule));
+    hours.insert(Shurooq, hour(Shurooq, &SunriseSunsetRule));
     hours.insert(Dhuhr, Ok(dhuhr_hour));
-    hours.insert(Asr, asr_hour_res);
-    hours.insert(Maghrib, magh_hour_res);
-    hours.insert(Isha, isha_hour_res);
+    hours.insert(Asr, hour(Asr, &ShadowRatioRule));
+    hours.insert(Maghrib, hour(Maghrib, &SunriseSunsetRule));
+    hours.insert(Isha, hour(Isha, &AngleRule));
     hours
 }
 
-fn get_shur_dhuhr_magh(
+// Returns the hours of Shurooq and Maghrib (or why the sun does not rise and set) below a
+// horizon dipped by degrees, and of Dhuhr.
+pub fn get_shur_dhuhr_magh(
     top_astro_day: &TopAstroDay,
     weather: Weather,
-) -> (Result<f64, ()>, f64, Result<f64, ()>) {
+    refraction_model: RefractionModel,
+    horizon_dip: f64,
+) -> (Result<(f64, f64), CrossingError>, f64) {
     // Astronomical Algorithms pg. 100-104
     let ra_interp_deltas = get_ra_interp_deltas(top_astro_day);
 
-    let m_0 = (top_astro_day.astro().ra()
-        - f64::from(top_astro_day.coords().longitude)
-        - top_astro_day.astro().sid_time())
-        / TWO_PI_DEG;
+    let m_0 = get_transit_m_0(top_astro_day);
     let dhuhr_m_time = m_0.cap_angle_1();
     let dhuhr_hour_angle = get_hour_angle(top_astro_day, ra_interp_deltas, dhuhr_m_time);
     let dhuhr_delta_m = dhuhr_hour_angle / TWO_PI_DEG;
     let dhuhr_hour = HRS_PER_DAY * (dhuhr_m_time - dhuhr_delta_m);
 
-    let shur_magh_res = if let Ok(sm_m_0_adj) = get_shur_magh_m_0_adj(top_astro_day) {
-        let dec_interp_deltas = get_dec_interp_deltas(top_astro_day);
+    let shur_magh_res = get_rise_set(
+        top_astro_day,
+        weather,
+        refraction_model,
+        get_horizon_alt(refraction_model) - horizon_dip,
+    )
+    .and_then(|(shur_hour, magh_hour)| {
+        // The sun grazing the horizon near a polar day or night can give a rise or set on the
+        // wrong side of its transit, in which case it does not distinctly rise and set, such as
+        // a rise just after midnight that belongs to the previous day. Only those days of a
+        // location in or near the polar circles fail this check.
+        let half_day = HRS_PER_DAY / 2.;
+        if (dhuhr_hour - shur_hour).rem_euclid(HRS_PER_DAY) < half_day
+            && (magh_hour - dhuhr_hour).rem_euclid(HRS_PER_DAY) < half_day
+        {
+            Ok((shur_hour, magh_hour))
+        } else if f64::from(top_astro_day.coords().latitude) * top_astro_day.astro().dec() > 0. {
+            Err(CrossingError::AlwaysAbove)
+        } else {
+            Err(CrossingError::AlwaysBelow)
+        }
+    });
 
-        let shuhr_m_time = (m_0 - sm_m_0_adj).cap_angle_1();
-        let shuhr_hour_angle = get_hour_angle(top_astro_day, ra_interp_deltas, shuhr_m_time);
-        let shur_hour = get_shur_magh(
-            top_astro_day,
-            weather,
-            dec_interp_deltas,
-            shuhr_m_time,
-            shuhr_hour_angle,
-        );
+    (shur_magh_res, dhuhr_hour)
+}
 
-        let magh_m_time = (m_0 + sm_m_0_adj).cap_angle_1();
-        let magh_hour_angle = get_hour_angle(top_astro_day, ra_interp_deltas, magh_m_time);
-        let magh_hour = get_shur_magh(
-            top_astro_day,
-            weather,
-            dec_interp_deltas,
-            magh_m_time,
-            magh_hour_angle,
-        );
+// Returns the altitude in degrees the upper limb of the sun touches the horizon at. The ported
+// constant includes the standard refraction, so the other models solve for the apparent
+// altitude of the limb with their own refraction instead.
+fn get_horizon_alt(refraction_model: RefractionModel) -> f64 {
+    match refraction_model {
+        RefractionModel::Itl => CENTER_OF_SUN_ANGLE,
+        _ => -SUN_SEMI_DIAMETER,
+    }
+}
 
-        (Ok(shur_hour), Ok(magh_hour))
-    } else {
-        (Err(()), Err(()))
-    };
+// Returns the declination of the sun in degrees interpolated at an hour of the day.
+pub fn get_dec_at(top_astro_day: &TopAstroDay, hour: f64) -> f64 {
+    // Astronomical Algorithms pg. 24 (3.3)
+    let dec_interp_deltas = get_dec_interp_deltas(top_astro_day);
+    let m_time = hour / HRS_PER_DAY;
+    top_astro_day.astro().dec() + m_time * (dec_interp_deltas.0 + dec_interp_deltas.1 * m_time) / 2.
+}
 
-    (shur_magh_res.0, dhuhr_hour, shur_magh_res.1)
+// Returns the hours the center of the sun crosses an altitude in degrees while rising and setting.
+pub fn get_rise_set(
+    top_astro_day: &TopAstroDay,
+    weather: Weather,
+    refraction_model: RefractionModel,
+    alt: f64,
+) -> Result<(f64, f64), CrossingError> {
+    // Astronomical Algorithms pg. 100-104
+    let ra_interp_deltas = get_ra_interp_deltas(top_astro_day);
+    let m_0 = get_transit_m_0(top_astro_day);
+    let sm_m_0_adj = get_rise_set_m_0_adj(top_astro_day, alt)?;
+    let dec_interp_deltas = get_dec_interp_deltas(top_astro_day);
+
+    let rise_m_time = (m_0 - sm_m_0_adj).cap_angle_1();
+    let rise_hour = get_rise_set_hour(
+        top_astro_day,
+        weather,
+        refraction_model,
+        alt,
+        ra_interp_deltas,
+        dec_interp_deltas,
+        rise_m_time,
+    );
+
+    let set_m_time = (m_0 + sm_m_0_adj).cap_angle_1();
+    let set_hour = get_rise_set_hour(
+        top_astro_day,
+        weather,
+        refraction_model,
+        alt,
+        ra_interp_deltas,
+        dec_interp_deltas,
+        set_m_time,
+    );
+
+    Ok((rise_hour, set_hour))
+}
+
+fn get_transit_m_0(top_astro_day: &TopAstroDay) -> f64 {
+    // Astronomical Algorithms pg. 102 (15.2)
+    (top_astro_day.astro().ra()
+        - f64::from(top_astro_day.coords().longitude)
+        - top_astro_day.astro().sid_time())
+        / TWO_PI_DEG
 }
 
 fn get_ra_interp_deltas(top_astro_day: &TopAstroDay) -> (f64, f64) {
@@ -119,18 +195,20 @@ fn get_dec_interp_deltas(top_astro_day: &TopAstroDay) -> (f64, f64) {
     (delta1, delta2)
 }
 
-fn get_shur_magh_m_0_adj(top_astro_day: &TopAstroDay) -> Result<f64, ()> {
+fn get_rise_set_m_0_adj(top_astro_day: &TopAstroDay, alt: f64) -> Result<f64, CrossingError> {
     // Astronomical Algorithms pg. 102 (15.1)
     let lat_rads = f64::from(top_astro_day.coords().latitude).to_radians();
     let dec_rads = top_astro_day.astro().dec().to_radians();
-    let n = CENTER_OF_SUN_ANGLE.to_radians().sin() - lat_rads.sin() * dec_rads.sin();
+    let n = alt.to_radians().sin() - lat_rads.sin() * dec_rads.sin();
     let d = lat_rads.cos() * dec_rads.cos();
     let r = n / d;
     if within_abs_1(r) {
         // Astronomical Algorithms pg. 102 (15.2)
         Ok(r.acos().to_degrees().cap_angle_180() / TWO_PI_DEG)
+    } else if r < -1. {
+        Err(CrossingError::AlwaysAbove)
     } else {
-        Err(())
+        Err(CrossingError::AlwaysBelow)
     }
 }
 
@@ -138,9 +216,42 @@ fn within_abs_1(val: f64) -> bool {
     (-1. ..=1.).contains(&val)
 }
 
-fn get_shur_magh(
+fn get_rise_set_hour(
     top_astro_day: &TopAstroDay,
     weather: Weather,
+    refraction_model: RefractionModel,
+    alt: f64,
+    ra_interp_deltas: (f64, f64),
+    dec_interp_deltas: (f64, f64),
+    mut m_time: f64,
+) -> f64 {
+    // Astronomical Algorithms pg. 103
+    // The correction is repeated, as the refraction of the estimated altitude can be far from
+    // the refraction at the crossing.
+    for _ in 0..MAX_RISE_SET_ITERATIONS {
+        let hour_angle = get_hour_angle(top_astro_day, ra_interp_deltas, m_time);
+        let delta_m = get_rise_set_delta_m(
+            top_astro_day,
+            weather,
+            refraction_model,
+            alt,
+            dec_interp_deltas,
+            m_time,
+            hour_angle,
+        );
+        m_time += delta_m;
+        if delta_m.abs() < RISE_SET_TOLERANCE {
+            break;
+        }
+    }
+    HRS_PER_DAY * m_time
+}
+
+fn get_rise_set_delta_m(
+    top_astro_day: &TopAstroDay,
+    weather: Weather,
+    refraction_model: RefractionModel,
+    alt: f64,
     dec_interp_deltas: (f64, f64),
     m_time: f64,
     hour_angle: f64,
@@ -159,58 +270,64 @@ fn get_shur_magh(
     .asin()
     .to_degrees();
     // Astronomical Algorithms pg. 105 (Apparent altitude h0)
-    sun_alt += get_refraction(weather, sun_alt);
+    sun_alt += refraction_model.degrees(sun_alt, weather);
     // Astronomical Algorithms pg. 103
-    let delta_m = (sun_alt - CENTER_OF_SUN_ANGLE)
-        / (TWO_PI_DEG * dec_interp_rads.cos() * lat_rads.cos() * hour_angle_rads.sin());
-    HRS_PER_DAY * (m_time + delta_m)
-}
-
-fn get_refraction(weather: Weather, sun_alt: f64) -> f64 {
-    // Astronomical Algorithms pg. 105-107
-    // Astronomical Algorithms pg. 106 (16.4)
-    let r = 1.02
-        / ((sun_alt + (10.3 / (sun_alt + 5.11)))
-            .to_radians()
-            .tan()
-            .to_degrees()
-            + 0.0019279);
-    // Astronomical Algorithms pg. 107
-    let m = f64::from(weather.pressure) / 1010. * (283. / (273. + f64::from(weather.temperature)));
-    m * r / 60.
+    (sun_alt - alt) / (TWO_PI_DEG * dec_interp_rads.cos() * lat_rads.cos() * hour_angle_rads.sin())
 }
 
-fn get_fajr_isha(
+pub fn get_fajr_isha(
     params: &Params,
     top_astro_day: &TopAstroDay,
     dhuhr_hour: f64,
 ) -> (Result<f64, ()>, Result<f64, ()>) {
     use Prayer::*;
 
+    let fajr_hour = get_alt_hours(top_astro_day, dhuhr_hour, -params.angles[&Fajr]).0;
+    let isha_hour = get_alt_hours(top_astro_day, dhuhr_hour, -params.angles[&Isha]).1;
+    (fajr_hour, isha_hour)
+}
+
+// Returns the hours before and after Dhuhr the center of the sun is at an altitude in degrees.
+pub fn get_alt_hours(
+    top_astro_day: &TopAstroDay,
+    dhuhr_hour: f64,
+    alt: f64,
+) -> (Result<f64, ()>, Result<f64, ()>) {
     let lat_rads = f64::from(top_astro_day.coords().latitude).to_radians();
     let dec_rads = top_astro_day.astro().dec().to_radians();
     let c = lat_rads.cos() * dec_rads.cos();
     let s = lat_rads.sin() * dec_rads.sin();
-    let fajr_hour = ((-params.angles[&Fajr]).to_radians().sin() - s) / c;
-    let isha_hour = ((-params.angles[&Isha]).to_radians().sin() - s) / c;
-    let fajr_hour = if within_abs_1(fajr_hour) {
-        Ok(dhuhr_hour - DEGREES_TO_10_BASE * fajr_hour.acos().to_degrees())
+    let alt_hour = (alt.to_radians().sin() - s) / c;
+    if within_abs_1(alt_hour) {
+        let half_arc = DEGREES_TO_10_BASE * alt_hour.acos().to_degrees();
+        (Ok(dhuhr_hour - half_arc), Ok(dhuhr_hour + half_arc))
     } else {
-        Err(())
-    };
-    let isha_hour = if within_abs_1(isha_hour) {
-        Ok(dhuhr_hour + DEGREES_TO_10_BASE * isha_hour.acos().to_degrees())
-    } else {
-        Err(())
-    };
+        (Err(()), Err(()))
+    }
+}
 
-    (fajr_hour, isha_hour)
+// Returns the altitude in degrees of the center of the sun at an hour, the inverse of
+// `get_alt_hours`.
+pub fn get_hour_alt(top_astro_day: &TopAstroDay, dhuhr_hour: f64, hour: f64) -> f64 {
+    let lat_rads = f64::from(top_astro_day.coords().latitude).to_radians();
+    let dec_rads = top_astro_day.astro().dec().to_radians();
+    let c = lat_rads.cos() * dec_rads.cos();
+    let s = lat_rads.sin() * dec_rads.sin();
+    let hour_angle = ((dhuhr_hour - hour) / DEGREES_TO_10_BASE).to_radians();
+    (s + c * hour_angle.cos())
+        .clamp(-1., 1.)
+        .asin()
+        .to_degrees()
 }
 
-fn get_asr(params: &Params, top_astro_day: &TopAstroDay, dhuhr_hour: f64) -> Result<f64, ()> {
+pub fn get_asr(params: &Params, top_astro_day: &TopAstroDay, dhuhr_hour: f64) -> Result<f64, ()> {
     let madhab = params.asr_shadow_ratio as u8 as f64;
     let lat_rads = f64::from(top_astro_day.coords().latitude).to_radians();
     let dec_rads = top_astro_day.astro().dec().to_radians();
+    // The sun stays below the horizon at its transit, so that it casts no shadow.
+    if (lat_rads - dec_rads).abs() >= RIGHT_ANG_DEG.to_radians() {
+        return Err(());
+    }
     let mut asr_hour = madhab + (lat_rads - dec_rads).abs().tan();
     asr_hour = (1. / asr_hour).atan();
     asr_hour = asr_hour.sin() - lat_rads.sin() * dec_rads.sin();
@@ -222,6 +339,30 @@ fn get_asr(params: &Params, top_astro_day: &TopAstroDay, dhuhr_hour: f64) -> Res
     }
 }
 
+// Returns the change in minutes between two times the shortest way around the clock, such as
+// Isha moving past midnight.
+pub fn get_time_change(before: NaiveTime, after: NaiveTime) -> f64 {
+    let mins_per_day = HRS_PER_DAY * MIN_SEC_PER_HR_MIN;
+    let mins = (time_to_mins(after) - time_to_mins(before)).rem_euclid(mins_per_day);
+    if mins > mins_per_day / 2. {
+        mins - mins_per_day
+    } else {
+        mins
+    }
+}
+
+// Returns the minutes of a time from midnight.
+pub fn time_to_mins(time: NaiveTime) -> f64 {
+    time.signed_duration_since(NaiveTime::MIN).num_seconds() as f64 / MIN_SEC_PER_HR_MIN
+}
+
+// Returns the time of an hour rounded to the nearest second.
+pub fn hour_to_exact_time(hour: f64) -> NaiveTime {
+    let secs = (hour * MIN_SEC_PER_HR_MIN * MIN_SEC_PER_HR_MIN).round();
+    let secs = secs.rem_euclid(HRS_PER_DAY * MIN_SEC_PER_HR_MIN * MIN_SEC_PER_HR_MIN);
+    NaiveTime::from_num_seconds_from_midnight_opt(secs as u32, 0).unwrap()
+}
+
 pub fn hour_to_time(params: &Params, prayer: Prayer, hour: f64) -> NaiveTime {
     use Prayer::*;
     use RoundSeconds::*;
@@ -1,13 +1,24 @@
 pub mod date;
+pub mod fasting;
 pub mod params;
+pub mod rule;
+pub mod season;
+pub mod solar;
+pub mod strategy;
 
 pub use date::*;
+pub use fasting::*;
 pub use params::*;
+pub use rule::*;
+pub use season::*;
+pub use solar::*;
+pub use strategy::*;
 
 use serde::{Deserialize, Serialize};
 
 mod ext_lat;
 mod hours;
+mod smoothing;
 
 use std::{
     collections::{BTreeMap, HashMap},
@@ -16,15 +27,24 @@ use std::{
     thread::{self},
 };
 
-use chrono::{NaiveDate, NaiveTime};
+use chrono::{Days, NaiveDate, NaiveTime};
 
 use crate::{
-    geo::{astro::TopAstroDay, coordinates::Location, julian_day::JulianDay},
-    prayer_times::{ext_lat::adj_for_ext_lat, hours::get_hours},
-    Weather,
+    geo::{
+        astro::{AstroCache, TopAstroDay},
+        coordinates::Location,
+        julian_day::JulianDay,
+    },
+    prayer_times::{
+        ext_lat::{adj_for_ext_lat, GoodDayCache},
+        hours::get_hours,
+        smoothing::smooth_times,
+        solar::get_solar_day,
+    },
+    Latitude, Weather, WeatherSeries,
 };
 
-use self::{ext_lat::PrayerHour, hours::hour_to_time};
+use self::hours::hour_to_time;
 
 /// An enumeration of Islamic prayer and related times.
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -55,12 +75,51 @@ impl Display for Prayer {
 ///
 /// See [`params` module level documentation](params) for more information on extreme latitude
 /// calculation.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct PrayerTime {
     /// The daily prayer time.
     pub time: NaiveTime,
     /// An extreme latitude method was used to calculate the prayer time.
     pub extreme: bool,
+    /// The extreme latitude rule and reference that produced the prayer time, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+}
+
+// The floating point fractions, minutes, and latitudes of a provenance are never NaN, so the
+// equality of a prayer time is reflexive.
+impl Eq for PrayerTime {}
+
+/// The [`ExtremeLatitudeMethod`] branch and its reference that produced an extreme [`PrayerTime`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// The extreme latitude method applied.
+    pub method: ExtremeLatitudeMethod,
+    /// The reference used by the extreme latitude method.
+    pub reference: Reference,
+}
+
+/// The reference used by an [`ExtremeLatitudeMethod`] to produce an extreme [`PrayerTime`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Reference {
+    /// The fraction of the night from sunset to sunrise (angle based, one-seventh and
+    /// half of the night).
+    NightFraction(f64),
+    /// The fraction of the day from sunrise to sunset (one-seventh of the day).
+    DayFraction(f64),
+    /// The prayer times of the `Latitude` (nearest latitude and nearest valid latitude).
+    Latitude(Latitude),
+    /// The prayer times of the reference city `Location` transferred to the local longitude
+    /// (nearest latitude).
+    City(Location),
+    /// The prayer times of the date (nearest good day).
+    Date(NaiveDate),
+    /// The minutes from sunrise or sunset (minutes from Maghrib).
+    Minutes(f64),
+    /// The minutes the prayer times of Mecca are shifted to the local solar noon (Mecca).
+    Mecca(f64),
+    /// The minutes from the solar noon (transit intervals).
+    Transit(f64),
 }
 
 impl Display for PrayerTime {
@@ -76,6 +135,8 @@ impl Display for PrayerTime {
 
 /// Returns a [`B-tree`] of [`NaiveDate`] keys to a [`B-tree`] of [`Prayer`] keys to [`PrayerTime`] values
 /// using the specified [`Params`] for a [`Location`] and [`DateRange`].
+/// The prayer times are smoothed over the [`Smoothing Days`](Params::smoothing_days) where an
+/// extreme latitude adjustment starts, ends, or switches.
 ///
 /// [`B-tree`]: std::collections::BTreeMap
 ///
@@ -108,14 +169,161 @@ pub fn prayer_times_dt_rng(
     location: Location,
     date_range: &DateRange,
 ) -> BTreeMap<NaiveDate, BTreeMap<Prayer, Result<PrayerTime, ()>>> {
+    prayer_times_dt_rng_with_weather(params, location, date_range, None)
+}
+
+/// Returns the prayer times of [`prayer_times_dt_rng`] for a [`DateRange`] and its (optional)
+/// [`WeatherSeries`]. A date without [`Weather`] in the series is calculated as if no current
+/// [`Weather`] is given.
+///
+/// # Examples
+/// ```
+/// # use std::error::Error;
+/// use chrono::NaiveDate;
+/// use islamic_prayer_times::*;
+///
+/// let params = Params::default();
+/// let latitude = Latitude::try_from(39.)?;
+/// let longitude = Longitude::try_from(-77.)?;
+/// let elevation = Elevation::try_from(0.)?;
+/// let coords = Coordinates::new(latitude, longitude, elevation);
+/// let gmt = Gmt::try_from(-5.)?;
+/// let location = Location { coords, gmt };
+/// let start_date = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+/// let end_date = NaiveDate::from_ymd_opt(2023, 1, 31).unwrap();
+/// let date_range = DateRange::from(start_date..=end_date);
+/// let weather_series = WeatherSeries::from_csv("2023-01-01,1021.3,-2.5\n")?;
+///
+/// let prayer_times_rng =
+///     prayer_times_dt_rng_with_weather(&params, location, &date_range, Some(&weather_series));
+///
+/// assert_eq!(31, prayer_times_rng.len());
+/// # Ok::<(), Box<dyn Error>>(())
+/// ```
+pub fn prayer_times_dt_rng_with_weather(
+    params: &Params,
+    location: Location,
+    date_range: &DateRange,
+    weather_series: Option<&WeatherSeries>,
+) -> BTreeMap<NaiveDate, BTreeMap<Prayer, Result<PrayerTime, ()>>> {
+    get_prayer_times_solar_rng(params, location, date_range, weather_series, None)
+}
+
+/// Returns the prayer times of [`prayer_times_dt_rng_with_weather`] together with a [`B-tree`] of
+/// [`NaiveDate`] keys to [`SolarDay`] values, where the [`SolarDay`] of a date is calculated from the same
+/// ephemerides as its prayer times.
+///
+/// [`B-tree`]: std::collections::BTreeMap
+///
+/// # Examples
+/// ```
+/// # use std::error::Error;
+/// use chrono::NaiveDate;
+/// use islamic_prayer_times::*;
+///
+/// let params = Params::default();
+/// let latitude = Latitude::try_from(39.)?;
+/// let longitude = Longitude::try_from(-77.)?;
+/// let elevation = Elevation::try_from(0.)?;
+/// let coords = Coordinates::new(latitude, longitude, elevation);
+/// let gmt = Gmt::try_from(-5.)?;
+/// let location = Location { coords, gmt };
+/// let start_date = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+/// let end_date = NaiveDate::from_ymd_opt(2023, 1, 31).unwrap();
+/// let date_range = DateRange::from(start_date..=end_date);
+///
+/// let (prayer_times_rng, solar_days) =
+///     prayer_times_solar_days_dt_rng(&params, location, &date_range, None);
+///
+/// assert_eq!(31, prayer_times_rng.len());
+/// assert_eq!(31, solar_days.len());
+/// # Ok::<(), Box<dyn Error>>(())
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn prayer_times_solar_days_dt_rng(
+    params: &Params,
+    location: Location,
+    date_range: &DateRange,
+    weather_series: Option<&WeatherSeries>,
+) -> (
+    BTreeMap<NaiveDate, BTreeMap<Prayer, Result<PrayerTime, ()>>>,
+    BTreeMap<NaiveDate, SolarDay>,
+) {
+    let mut solar_days = BTreeMap::new();
+    let times = get_prayer_times_solar_rng(
+        params,
+        location,
+        date_range,
+        weather_series,
+        Some(&mut solar_days),
+    );
+    solar_days.retain(|x, _| date_range.start_date() <= x && x <= date_range.end_date());
+    (times, solar_days)
+}
+
+fn get_prayer_times_solar_rng(
+    params: &Params,
+    location: Location,
+    date_range: &DateRange,
+    weather_series: Option<&WeatherSeries>,
+    solar_days: Option<&mut BTreeMap<NaiveDate, SolarDay>>,
+) -> BTreeMap<NaiveDate, BTreeMap<Prayer, Result<PrayerTime, ()>>> {
+    if params.smoothing_days == 0 {
+        return get_prayer_times_rng(params, location, date_range, weather_series, solar_days);
+    }
+
+    // Smooth the prayer times of the dates around the date range too, so that the prayer times of
+    // a date do not depend on where the date range starts or ends.
+    let days = Days::new(params.smoothing_days as u64);
+    let start_date = *date_range.start_date() - days;
+    let end_date = *date_range.end_date() + days;
+    let smooth_date_range = DateRange::from(start_date..=end_date);
+    let mut times = get_prayer_times_rng(
+        params,
+        location,
+        &smooth_date_range,
+        weather_series,
+        solar_days,
+    );
+    smooth_times(params, location, &mut times, weather_series);
+    times.retain(|x, _| date_range.start_date() <= x && x <= date_range.end_date());
+    times
+}
+
+// Returns the prayer times of the dates, and adds their solar summaries when requested, sharing
+// the ephemerides of each date between the two.
+fn get_prayer_times_rng(
+    params: &Params,
+    location: Location,
+    date_range: &DateRange,
+    weather_series: Option<&WeatherSeries>,
+    mut solar_days: Option<&mut BTreeMap<NaiveDate, SolarDay>>,
+) -> BTreeMap<NaiveDate, BTreeMap<Prayer, Result<PrayerTime, ()>>> {
+    let cache = RangeCache::new(params, location);
     let mut times = BTreeMap::new();
     for date in date_range
         .start_date()
         .iter_days()
         .take(date_range.num_days())
     {
-        let prayer_time = prayer_times_dt(params, location, date, None);
-        times.insert(date, prayer_time);
+        let weather = current_weather(
+            params,
+            location,
+            weather_series.and_then(|x| x.weather_on(date)),
+        );
+        let julian_day = JulianDay::new(date, location.gmt);
+        let top_astro_day = TopAstroDay::from_cache(&cache.astros, julian_day, location.coords);
+        times.insert(
+            date,
+            get_prayer_times(params, &top_astro_day, weather, &cache),
+        );
+        if let Some(solar_days) = solar_days.as_deref_mut() {
+            solar_days.insert(
+                date,
+                get_solar_day(params, location, &top_astro_day, weather),
+            );
+        }
+        cache.slide(date);
     }
     times
 }
@@ -131,6 +339,20 @@ pub fn prayer_times_dt_rng_block(
     location: Location,
     date_range: &DateRange,
     min_days_for_pll: usize,
+) -> BTreeMap<NaiveDate, BTreeMap<Prayer, Result<PrayerTime, ()>>> {
+    prayer_times_dt_rng_block_with_weather(params, location, date_range, None, min_days_for_pll)
+}
+
+/// Returns the prayer times of [`prayer_times_dt_rng_block`] for a [`DateRange`] and its
+/// (optional) [`WeatherSeries`] while maximizing parallelism for a (possibly) large number of
+/// calculations.
+///
+pub fn prayer_times_dt_rng_block_with_weather(
+    params: &Params,
+    location: Location,
+    date_range: &DateRange,
+    weather_series: Option<&WeatherSeries>,
+    min_days_for_pll: usize,
 ) -> BTreeMap<NaiveDate, BTreeMap<Prayer, Result<PrayerTime, ()>>> {
     // Determine parallelism.
     let avail_pll = if let Ok(count) = thread::available_parallelism() {
@@ -142,7 +364,7 @@ pub fn prayer_times_dt_rng_block(
 
     // No parallelism.
     if avail_pll == 1 || no_parallelism {
-        prayer_times_dt_rng(params, location, date_range)
+        prayer_times_dt_rng_with_weather(params, location, date_range, weather_series)
     } else {
         // Maximize parallelism through threads that calculate partial time results.
         thread::scope(|s| {
@@ -162,7 +384,12 @@ pub fn prayer_times_dt_rng_block(
             for date_range in date_ranges {
                 let tx = tx.clone();
                 s.spawn(move || {
-                    let partial_times = prayer_times_dt_rng(params, location, &date_range);
+                    let partial_times = prayer_times_dt_rng_with_weather(
+                        params,
+                        location,
+                        &date_range,
+                        weather_series,
+                    );
                     tx.send(partial_times).unwrap();
                 });
             }
@@ -177,6 +404,9 @@ pub fn prayer_times_dt_rng_block(
 
 /// Returns a [`B-tree`](std::collections::BTreeMap) of [`Prayer`] keys to [`PrayerTime`] values using the specified
 /// [`Params`] for a [`Location`], [`NaiveDate`], and its (optional) current [`Weather`].
+/// When no current [`Weather`] is given, the standard atmosphere at the [`Elevation`](crate::Elevation) of the
+/// [`Location`] is used if [`standard_atmosphere`](Params::standard_atmosphere) is set, and
+/// the default [`Weather`] otherwise.
 ///
 /// # Examples
 ///
@@ -205,46 +435,97 @@ pub fn prayer_times_dt(
     date: NaiveDate,
     weather: Option<Weather>,
 ) -> BTreeMap<Prayer, Result<PrayerTime, ()>> {
-    use Prayer::*;
+    let cache = RangeCache::new(params, location);
+    let weather = current_weather(params, location, weather);
+    let julian_day = JulianDay::new(date, location.gmt);
+    let top_astro_day = TopAstroDay::from_cache(&cache.astros, julian_day, location.coords);
+    get_prayer_times(params, &top_astro_day, weather, &cache)
+}
 
-    let weather = if let Some(weather) = weather {
-        weather
-    } else {
-        Weather::default()
-    };
+fn get_prayer_times(
+    params: &Params,
+    top_astro_day: &TopAstroDay,
+    weather: Weather,
+    cache: &RangeCache,
+) -> BTreeMap<Prayer, Result<PrayerTime, ()>> {
+    use Prayer::*;
 
-    let julian_day = JulianDay::new(date, location.gmt);
-    let top_astro_day = TopAstroDay::from_jd(julian_day, location.coords);
-    let hours = get_hours_adj_ext(params, &top_astro_day, weather);
+    let hours = get_hours_adj_ext(params, top_astro_day, weather, cache);
     let mut times = BTreeMap::from_iter(
         hours
             .iter()
             .map(|x| (*x.0, x.1.map(|y| to_prayer_time(params, *x.0, y)))),
     );
 
-    let imsaak = get_imsaak(params, &top_astro_day, weather);
+    let imsaak = get_imsaak(params, top_astro_day, weather, cache);
     times.insert(Imsaak, imsaak);
     times
 }
 
+// The caches shared by the days of a range of dates, of the geocentric `Astro`s of the dates and
+// of the good days of the nearest good day search.
+#[derive(Debug)]
+pub(crate) struct RangeCache {
+    astros: AstroCache,
+    good_days: GoodDayCache,
+}
+
+impl RangeCache {
+    fn new(params: &Params, location: Location) -> Self {
+        Self {
+            astros: AstroCache::new(location.gmt, params.delta_t, params.ephemeris),
+            good_days: GoodDayCache::default(),
+        }
+    }
+
+    // Drops the cached values of the dates before the windows ending at a date.
+    fn slide(&self, date: NaiveDate) {
+        self.astros.slide(date);
+        self.good_days.slide(date);
+    }
+}
+
+fn current_weather(params: &Params, location: Location, weather: Option<Weather>) -> Weather {
+    weather.unwrap_or_else(|| {
+        if params.standard_atmosphere {
+            Weather::standard_atmosphere(location.coords.elevation)
+        } else {
+            Weather::default()
+        }
+    })
+}
+
 fn get_hours_adj_ext(
     params: &Params,
     top_astro_day: &TopAstroDay,
     weather: Weather,
+    cache: &RangeCache,
 ) -> HashMap<Prayer, Result<PrayerHour, ()>> {
     let hours = get_hours(params, top_astro_day, weather);
-    adj_for_ext_lat(params, hours, top_astro_day, weather)
+    adj_for_ext_lat(params, hours, top_astro_day, weather, cache)
 }
 
 fn get_imsaak(
     params: &Params,
     top_astro_day: &TopAstroDay,
     weather: Weather,
+    cache: &RangeCache,
 ) -> Result<PrayerTime, ()> {
+    get_imsaak_hour(params, top_astro_day, weather, cache)
+        .map(|(params_adj, hour)| to_prayer_time(&params_adj, Prayer::Fajr, hour))
+}
+
+// Returns the hour of Imsaak and the params adjusted to calculate it as Fajr.
+fn get_imsaak_hour(
+    params: &Params,
+    top_astro_day: &TopAstroDay,
+    weather: Weather,
+    cache: &RangeCache,
+) -> Result<(Params, PrayerHour), ()> {
     use Prayer::*;
 
     let mut params_adj = params.clone();
-    if params.intervals[&Fajr] != 0. {
+    if params.intervals[&Fajr] != 0. && !params.rules.contains_key(&Fajr) {
         *params_adj.intervals.get_mut(&Fajr).unwrap() += if params.intervals[&Imsaak] == 0. {
             Params::DEF_IMSAAK_ANGLE
         } else {
@@ -252,13 +533,17 @@ fn get_imsaak(
         };
     } else if params.intervals[&Imsaak] != 0. {
         *params_adj.minutes.get_mut(&Fajr).unwrap() -= params.intervals[&Imsaak];
+    } else if let Some(rule) = params.rules.get(&Fajr) {
+        // A custom rule of Fajr may not use its angle, so Imsaak is lower than its Fajr instead.
+        let rule = ImsaakRule(rule.clone(), params.angles[&Imsaak]);
+        params_adj.rules.insert(Fajr, CustomRule::new(rule));
     } else {
         *params_adj.angles.get_mut(&Fajr).unwrap() += params.angles[&Imsaak];
     }
 
-    let mut hours = get_hours_adj_ext(&params_adj, top_astro_day, weather);
+    let mut hours = get_hours_adj_ext(&params_adj, top_astro_day, weather, cache);
     if let Ok(hour) = hours[&Fajr] {
-        if hour.extreme {
+        if hour.provenance.is_some() {
             params_adj = params.clone();
             *params_adj.minutes.get_mut(&Fajr).unwrap() -= if params.intervals[&Imsaak] == 0. {
                 Params::DEF_IMSAAK_ANGLE
@@ -266,16 +551,17 @@ fn get_imsaak(
                 params.intervals[&Imsaak]
             };
 
-            hours = get_hours_adj_ext(&params_adj, top_astro_day, weather);
+            hours = get_hours_adj_ext(&params_adj, top_astro_day, weather, cache);
         }
     }
 
-    hours[&Fajr].map(|x| to_prayer_time(&params_adj, Fajr, x))
+    hours[&Fajr].map(|x| (params_adj, x))
 }
 
 fn to_prayer_time(params: &Params, prayer: Prayer, prayer_hour: PrayerHour) -> PrayerTime {
     PrayerTime {
         time: hour_to_time(params, prayer, prayer_hour.value),
-        extreme: prayer_hour.extreme,
+        extreme: prayer_hour.provenance.is_some(),
+        provenance: prayer_hour.provenance,
     }
 }
@@ -3,14 +3,41 @@
 //! Type [`Params`] represents a set of configurable fields used in the calculation of Islamic prayer times:
 //!
 //! * [`Round Seconds`] represents how to round up a prayer time to the nearest minute when a specific
-//! threshold of seconds is exceeded for a set of prayer times.
+//!   threshold of seconds is exceeded for a set of prayer times.
 //! * [`Asr Shadow Ratio`] represents the Fiqh school to use when calculating Asr prayer time.
 //! * [`Extreme Latitude Method`] represents how to adjust a prayer time when its conventional calculation
-//! results in an invalid value due to an extreme latitude for a set of prayer times.
+//!   results in an invalid value due to an extreme latitude for a set of prayer times.
+//! * [`Horizon Dip`] represents whether to correct Shurooq and Maghrib prayer times for the dip of the
+//!   horizon seen by an observer elevated above the surrounding terrain.
+//! * [`Refraction Model`] represents the formula used to calculate the atmospheric refraction of the
+//!   Sun at Shurooq and Maghrib prayer times.
+//! * [`Standard Atmosphere`] represents whether to derive the [`Weather`] from the [`Elevation`] of a
+//!   location using the International Standard Atmosphere when no current [`Weather`] is given.
+//! * [`Delta T`] represents how to determine the difference between the dynamical time of the positions
+//!   of the Sun and the universal time of the prayer times.
+//! * [`Ephemeris`] represents the precision of the calculated position of the Sun.
+//! * [`Latitude Bands`] is a list of [`LatitudeBand`]s that choose the [`Extreme Latitude Method`] of
+//!   a location and day when it is [`Latitude Bands`](ExtremeLatitudeMethod::LatitudeBands).
+//! * [`Fasting Cap`] represents how to shorten a fast from [`Imsaak`] to [`Maghrib`] that is too long, such
+//!   as during the summer at a high latitude.
+//! * [`Nearest Good Day`] represents the limits and preferred direction of the search for the nearest
+//!   good day of the [`Extreme Latitude Method`].
+//! * [`Nearest Latitude`] represents the reference of the prayer times of the nearest latitude methods of
+//!   the [`Extreme Latitude Method`], such as a latitude chosen for the date or a reference city.
+//! * [`Smoothing Days`] is the number of days over which the prayer times of a date range blend into
+//!   those adjusted for an extreme latitude where the adjustment starts, ends, or switches, or 0 for
+//!   no smoothing. A blended prayer time keeps the order of the prayer times of its date and is never
+//!   before its conventionally calculated prayer time.
+//! * [`Custom Strategy`] is a user supplied [`ExtremeLatitudeStrategy`](super::ExtremeLatitudeStrategy) used
+//!   when the [`Extreme Latitude Method`] is [`Custom`](ExtremeLatitudeMethod::Custom). It is not serialized.
+//! * [`Rules`] is a [`map`] of [`Prayer`] keys to user supplied [`PrayerRule`](super::PrayerRule) values
+//!   used instead of the built-in rules of Fajr, Shurooq, Asr, Maghrib, and Isha, and of the [`Intervals`]
+//!   of Fajr and Isha. Imsaak without an interval is the angle of Imsaak lower than the Fajr of a rule. It
+//!   is not serialized.
 //! * [`Angles`] is a [`map`] of [`Prayer`] keys to angle values in degrees.
 //! * [`Intervals`] is a [`map`] of [`Prayer`] keys to interval values in minutes.
 //! * [`Minutes`] is a [`map`] of [`Prayer`] keys to minute values used to adjust a calculated
-//! prayer time by the keyed value when it is not approximate enough.
+//!   prayer time by the keyed value when it is not approximate enough.
 //!
 //! Type [`Params`] is instantiated using [`new`](Params::new) by providing it a Fiqh [`Method`]
 //! to initialize an instance of the type to the desired Fiqh school values. Fiqh [`Method`]
@@ -24,6 +51,19 @@
 //! * [`Round Seconds`] is set to [`Special Rounding`](RoundSeconds::SpecialRounding).
 //! * [`Asr Shadow Ratio`] is set to [`Shafi`](AsrShadowRatio::Shafi).
 //! * [`Extreme Latitude Method`] is set to [`Nearest Good Day Fajr Isha Invalid`](ExtremeLatitudeMethod::NearestGoodDayFajrIshaInvalid).
+//! * [`Horizon Dip`] is set to [`None`](HorizonDip::None).
+//! * [`Refraction Model`] is set to [`Itl`](RefractionModel::Itl).
+//! * [`Standard Atmosphere`] is set to false.
+//! * [`Delta T`] is set to [`Espenak Meeus`](DeltaT::EspenakMeeus).
+//! * [`Ephemeris`] is set to [`Vsop87`](Ephemeris::Vsop87).
+//! * [`Latitude Bands`] is set to empty.
+//! * [`Fasting Cap`] is set to [`None`](FastingCap::None).
+//! * [`Nearest Good Day`] is set to search up to 366 days before and after for the
+//!   [`Nearest`](SearchDirection::Nearest) good day.
+//! * [`Nearest Latitude`] is set to [`Fixed`](NearestLatitudeReference::Fixed).
+//! * [`Smoothing Days`] is set to 0.
+//! * [`Custom Strategy`] is set to `None`.
+//! * [`Rules`] is set to empty.
 //! * [`Angles`], [`Intervals`], and [`Minutes`] [`map`]s have their [`Prayer`] values set to 0.
 //!
 //! [`Egyptian`](Method::Egyptian)
@@ -74,20 +114,59 @@
 //!
 //! * Nearest Latitude (Aqrab Al-Bilaad): Calculate prayer times using a nearest [`Latitude`].
 //! * Nearest Good Day (Aqrab Al-Ayyam): Determine the closest previous or next day
-//! where [`Fajr`] and [`Isha`] prayer times are both valid.
+//!   where [`Fajr`] and [`Isha`] prayer times are both valid.
 //! * An amount of night or day: Unlike the above mentioned methods, the multiple
-//! methods in this category have no proof in traditional Fiqh resources. These
-//! methods were introduced by modern day Muslim scholars and scientists for
-//! practical reasons only.
+//!   methods in this category have no proof in traditional Fiqh resources. These
+//!   methods were introduced by modern day Muslim scholars and scientists for
+//!   practical reasons only.
 //! * Minutes from Shurooq/Maghrib: Use an interval time to calculate Fajr and Isha prayer
-//! times. This will set their calculated values to those of Shurooq and Maghrib respectively,
-//! then adjust them by minute vlaues found in their respective values in [`Intervals`].
+//!   times. This will set their calculated values to those of Shurooq and Maghrib respectively,
+//!   then adjust them by minute vlaues found in their respective values in [`Intervals`].
+//! * Polar day and night: Replace all prayer times of a day with an invalid prayer time, such
+//!   as when the Sun does not rise or set, by those of the nearest latitude at which all prayer
+//!   times are valid, by those of Mecca shifted to the local solar noon, or by fixed intervals
+//!   from the local solar noon.
+//! * Latitude bands: Apply one of the above methods chosen by the latitude of the location
+//!   and the conventional prayer times of each day, such as a tiered policy of conventional
+//!   times below 48.5 degrees, one-seventh of the night up to 60 degrees, and the nearest
+//!   [`Latitude`] above that:
+//!
+//! ```
+//! use islamic_prayer_times::*;
+//!
+//! let mut params = Params::new(Method::Isna);
+//! params.extreme_latitude_method = ExtremeLatitudeMethod::LatitudeBands;
+//! params.latitude_bands = vec![
+//!     LatitudeBand {
+//!         min_latitude: 48.5,
+//!         method: ExtremeLatitudeMethod::SeventhOfNightFajrIshaInvalid,
+//!         condition: LatitudeBandCondition::FajrIshaInvalid,
+//!     },
+//!     LatitudeBand {
+//!         min_latitude: 60.,
+//!         method: ExtremeLatitudeMethod::NearestLatitudeAllPrayersAlways(NEAREST_LATITUDE),
+//!         condition: LatitudeBandCondition::AnyInvalid,
+//!     },
+//! ];
+//! ```
 //!  
 //! [`Extreme Latitude Method`]: Params::extreme_latitude_method
 //! [`map`]: std::collections::HashMap
 //! [`Latitude`]: crate::geo::coordinates::Latitude
 //! [`Round Seconds`]: RoundSeconds
 //! [`Asr Shadow Ratio`]: AsrShadowRatio
+//! [`Horizon Dip`]: HorizonDip
+//! [`Refraction Model`]: RefractionModel
+//! [`Standard Atmosphere`]: Params::standard_atmosphere
+//! [`Delta T`]: DeltaT
+//! [`Latitude Bands`]: Params::latitude_bands
+//! [`Fasting Cap`]: FastingCap
+//! [`Nearest Good Day`]: NearestGoodDaySearch
+//! [`Nearest Latitude`]: NearestLatitudeReference
+//! [`Smoothing Days`]: Params::smoothing_days
+//! [`Custom Strategy`]: Params::custom_strategy
+//! [`Rules`]: Params::rules
+//! [`Weather`]: crate::geo::weather::Weather
 //! [`Angles`]: Params::angles
 //! [`Intervals`]: Params::intervals
 //! [`Minutes`]: Params::minutes
@@ -105,9 +184,17 @@ use std::collections::HashMap;
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 
-use crate::geo::coordinates::Latitude;
+use crate::{
+    error::OrderError,
+    geo::{
+        coordinates::{Coordinates, Elevation, Latitude, Location},
+        delta_t::DeltaT,
+        ephemeris::Ephemeris,
+        weather::RefractionModel,
+    },
+};
 
-use super::Prayer;
+use super::{rule::CustomRule, strategy::CustomStrategy, Prayer};
 
 /// The `Method` type. See [the module level documentation](self) for more.
 #[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Serialize, Deserialize)]
@@ -184,6 +271,251 @@ pub enum ExtremeLatitudeMethod {
     /// Apply [`Fajr`](super::Prayer::Fajr) and [`Isha`](super::Prayer::Isha) values of [`Intervals`](Params::intervals)
     /// only to and invalid Fajr or Isha prayer time, respectively.
     MinutesFromMaghribFajrIshaInvalid,
+    /// Apply the prayer times of the nearest latitude toward the equator at which Shurooq, Asr,
+    /// and Maghrib prayer times are valid, that is where the Sun rises high enough and sets
+    /// during a polar day or night, to all prayer times of a day with an invalid Shurooq, Asr, or
+    /// Maghrib prayer time. A Fajr or Isha prayer time also invalid at that latitude falls back
+    /// to the [`Angle Based`](ExtremeLatitudeMethod::AngleBased) fraction of its night.
+    NearestValidLatitudeAllPrayersInvalid,
+    /// Apply the prayer times of Mecca, shifted by the difference between the local solar noon and
+    /// that of Mecca, to all prayer times of a day with an invalid prayer time.
+    MeccaAllPrayersInvalid,
+    /// Apply [`TransitIntervals`] from the solar noon to all prayer times of a day with an invalid
+    /// prayer time.
+    TransitIntervalsAllPrayersInvalid(TransitIntervals),
+    /// Apply the method of the highest [`Latitude Bands`](Params::latitude_bands) band at or below
+    /// the latitude of the location whose condition holds on the day, and no adjustment otherwise.
+    LatitudeBands,
+    /// Apply the [`Custom Strategy`](Params::custom_strategy), and no adjustment without one.
+    Custom,
+}
+
+/// The intervals in minutes of prayer times from the solar noon (Dhuhr) used on a day with an
+/// invalid prayer time, negative before it and positive after it.
+///
+/// The intervals are ordered as their prayer times, Fajr before Shurooq before the solar noon,
+/// and the solar noon before Asr before Maghrib before Isha.
+///
+/// The [`Default`] intervals are approximately those of an equinox at a latitude of 45 degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawTransitIntervals", into = "RawTransitIntervals")]
+pub struct TransitIntervals {
+    fajr: f64,
+    shurooq: f64,
+    asr: f64,
+    maghrib: f64,
+    isha: f64,
+}
+
+impl TransitIntervals {
+    /// Returns the [`TransitIntervals`] of the specified Fajr, Shurooq, Asr, Maghrib, and Isha
+    /// intervals, or an [`OrderError`] when they are not ordered as their prayer times.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use islamic_prayer_times::TransitIntervals;
+    ///
+    /// let transit_intervals = TransitIntervals::new(-480., -390., 180., 390., 480.)?;
+    ///
+    /// assert_eq!(-480., transit_intervals.fajr());
+    /// assert!(TransitIntervals::new(-480., -390., 180., 390., 300.).is_err());
+    /// # Ok::<(), Box<dyn Error>>(())
+    /// ```
+    pub fn new(
+        fajr: f64,
+        shurooq: f64,
+        asr: f64,
+        maghrib: f64,
+        isha: f64,
+    ) -> Result<Self, OrderError> {
+        if fajr < shurooq && shurooq < 0. && 0. < asr && asr < maghrib && maghrib < isha {
+            Ok(Self {
+                fajr,
+                shurooq,
+                asr,
+                maghrib,
+                isha,
+            })
+        } else {
+            Err(OrderError(format!(
+                "The transit intervals [{fajr}, {shurooq}, {asr}, {maghrib}, {isha}] are not in \
+                 the order of Fajr < Shurooq < 0 < Asr < Maghrib < Isha"
+            )))
+        }
+    }
+
+    /// Returns the interval of Fajr prayer time.
+    pub fn fajr(&self) -> f64 {
+        self.fajr
+    }
+
+    /// Returns the interval of Shurooq prayer time.
+    pub fn shurooq(&self) -> f64 {
+        self.shurooq
+    }
+
+    /// Returns the interval of Asr prayer time.
+    pub fn asr(&self) -> f64 {
+        self.asr
+    }
+
+    /// Returns the interval of Maghrib prayer time.
+    pub fn maghrib(&self) -> f64 {
+        self.maghrib
+    }
+
+    /// Returns the interval of Isha prayer time.
+    pub fn isha(&self) -> f64 {
+        self.isha
+    }
+}
+
+impl Default for TransitIntervals {
+    fn default() -> Self {
+        Self {
+            fajr: -450.,
+            shurooq: -360.,
+            asr: 200.,
+            maghrib: 360.,
+            isha: 450.,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct RawTransitIntervals {
+    fajr: f64,
+    shurooq: f64,
+    asr: f64,
+    maghrib: f64,
+    isha: f64,
+}
+
+impl TryFrom<RawTransitIntervals> for TransitIntervals {
+    type Error = OrderError;
+
+    fn try_from(value: RawTransitIntervals) -> Result<Self, Self::Error> {
+        Self::new(
+            value.fajr,
+            value.shurooq,
+            value.asr,
+            value.maghrib,
+            value.isha,
+        )
+    }
+}
+
+impl From<TransitIntervals> for RawTransitIntervals {
+    fn from(value: TransitIntervals) -> Self {
+        Self {
+            fajr: value.fajr,
+            shurooq: value.shurooq,
+            asr: value.asr,
+            maghrib: value.maghrib,
+            isha: value.isha,
+        }
+    }
+}
+
+/// The reference of the prayer times of the [`Nearest Latitude`](ExtremeLatitudeMethod::NearestLatitudeFajrIshaInvalid)
+/// extreme latitude methods.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum NearestLatitudeReference {
+    /// The prayer times of the `Latitude` of the method.
+    #[default]
+    Fixed,
+    /// The prayer times of the highest latitude, from that of the location toward the equator,
+    /// at which Fajr and Isha prayer times are valid on the date.
+    Automatic,
+    /// The prayer times of a reference city [`Location`] on the day of the location, transferred
+    /// to the longitude of the location so that they keep the same solar time.
+    City(Location),
+}
+
+/// The search for the nearest good day of the [`Nearest Good Day`](ExtremeLatitudeMethod::NearestGoodDayFajrIshaInvalid)
+/// extreme latitude methods, that is the nearest date on which both Fajr and Isha prayer times
+/// are valid.
+///
+/// The [`Default`] search looks up to a year before and after the date for the nearest good day.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NearestGoodDaySearch {
+    /// The most days before the date to search.
+    pub max_days_before: u32,
+    /// The most days after the date to search.
+    pub max_days_after: u32,
+    /// The preferred direction of the search.
+    pub direction: SearchDirection,
+}
+
+impl Default for NearestGoodDaySearch {
+    fn default() -> Self {
+        Self {
+            max_days_before: 366,
+            max_days_after: 366,
+            direction: SearchDirection::Nearest,
+        }
+    }
+}
+
+/// The preferred direction of the search for the nearest good day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SearchDirection {
+    /// Prefer the nearer good day, and the earlier one when both are as near.
+    #[default]
+    Nearest,
+    /// Prefer the good day before the date, and the one after it when there is none.
+    Earlier,
+    /// Prefer the good day after the date, and the one before it when there is none.
+    Later,
+}
+
+/// The `FastingCap` type. See [the module level documentation](self) for more.
+///
+/// A fast from Imsaak to Maghrib longer than the threshold of the cap is shortened by rescaling
+/// it about the solar noon (Dhuhr) to the target length of the cap. The prayer times are not
+/// changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum FastingCap {
+    /// No cap on the length of a fast.
+    #[default]
+    None,
+    /// Cap a fast longer than a number of hours at that number of hours.
+    Hours(f64),
+    /// Cap a fast longer than a threshold in hours at the length of the fast in Mecca on the
+    /// same date.
+    Mecca(f64),
+    /// Cap a fast longer than a threshold in hours at the length of the fast at reference
+    /// [`Coordinates`], such as of a city at a moderate latitude, on the same date.
+    Reference(Coordinates, f64),
+}
+
+/// A band of latitudes from which an [`ExtremeLatitudeMethod`] applies to the prayer times of a
+/// day that meets its [`LatitudeBandCondition`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LatitudeBand {
+    /// The absolute latitude in degrees from which the band applies, in both hemispheres.
+    pub min_latitude: f64,
+    /// The extreme latitude method applied in the band. [`LatitudeBands`](ExtremeLatitudeMethod::LatitudeBands)
+    /// makes no adjustment.
+    pub method: ExtremeLatitudeMethod,
+    /// The condition the conventional prayer times of a day meet to apply the method.
+    pub condition: LatitudeBandCondition,
+}
+
+/// The `LatitudeBandCondition` type. See [the module level documentation](self) for more.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LatitudeBandCondition {
+    /// Apply the method to every day.
+    Always,
+    /// Apply the method only to a day whose conventional Fajr or Isha prayer time is invalid.
+    FajrIshaInvalid,
+    /// Apply the method only to a day with any invalid conventional prayer time.
+    AnyInvalid,
+    /// Apply the method only to a polar day or night, whose conventional Shurooq or Maghrib prayer
+    /// time is invalid.
+    ShurooqMaghribInvalid,
 }
 
 /// The `RoundSeconds` type. See [the module level documentation](self) for more.
@@ -211,12 +543,82 @@ pub enum AsrShadowRatio {
     Hanafi,
 }
 
+/// The `HorizonDip` type. See [the module level documentation](self) for more.
+///
+/// An observer on a tower or a mountain sees the horizon below the astronomical horizon,
+/// and so sees the Sun rise earlier and set later. The dip of the horizon depends on the
+/// height of the observer above the surrounding terrain rather than above sea level, which
+/// is the [`Elevation`] of the location less the local terrain height.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum HorizonDip {
+    /// No correction for the dip of the horizon.
+    #[default]
+    None,
+    /// Correct Shurooq and Maghrib prayer times for the dip of the horizon seen from the
+    /// [`Elevation`] of the location above the local terrain height (an `Elevation` too).
+    TerrainHeight(Elevation),
+}
+
+impl HorizonDip {
+    // Dip of the horizon in minutes of arc per square root of a meter of height, including
+    // the standard terrestrial refraction
+    const DIP_ARC_MIN_PER_SQRT_M: f64 = 1.76;
+
+    /// Returns the dip of the horizon in degrees seen from an [`Elevation`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// use islamic_prayer_times::*;
+    ///
+    /// let horizon_dip = HorizonDip::TerrainHeight(Elevation::try_from(100.)?);
+    ///
+    /// assert_eq!(0., horizon_dip.degrees(Elevation::try_from(50.)?));
+    /// assert!((horizon_dip.degrees(Elevation::try_from(200.)?) - 0.2933).abs() < 0.0001);
+    /// # Ok::<(), Box<dyn Error>>(())
+    /// ```
+    pub fn degrees(&self, elevation: Elevation) -> f64 {
+        match self {
+            HorizonDip::None => 0.,
+            HorizonDip::TerrainHeight(terrain_height) => {
+                let height = (f64::from(elevation) - f64::from(*terrain_height)).max(0.);
+                Self::DIP_ARC_MIN_PER_SQRT_M * height.sqrt() / 60.
+            }
+        }
+    }
+}
+
 /// The `Params` type. See [the module level documentation](self) for more.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Params {
     pub round_seconds: RoundSeconds,
     pub asr_shadow_ratio: AsrShadowRatio,
     pub extreme_latitude_method: ExtremeLatitudeMethod,
+    #[serde(default)]
+    pub horizon_dip: HorizonDip,
+    #[serde(default)]
+    pub refraction_model: RefractionModel,
+    #[serde(default)]
+    pub standard_atmosphere: bool,
+    #[serde(default)]
+    pub delta_t: DeltaT,
+    #[serde(default)]
+    pub ephemeris: Ephemeris,
+    #[serde(default)]
+    pub latitude_bands: Vec<LatitudeBand>,
+    #[serde(default)]
+    pub fasting_cap: FastingCap,
+    #[serde(default)]
+    pub nearest_good_day: NearestGoodDaySearch,
+    #[serde(default)]
+    pub nearest_latitude: NearestLatitudeReference,
+    #[serde(default)]
+    pub smoothing_days: u32,
+    #[serde(skip)]
+    pub custom_strategy: Option<CustomStrategy>,
+    #[serde(skip)]
+    pub rules: HashMap<Prayer, CustomRule>,
     pub angles: HashMap<Prayer, f64>,
     pub intervals: HashMap<Prayer, f64>,
     pub minutes: HashMap<Prayer, f64>,
@@ -307,6 +709,18 @@ impl Params {
             round_seconds: SpecialRounding,
             asr_shadow_ratio,
             extreme_latitude_method: ExtremeLatitudeMethod::NearestGoodDayFajrIshaInvalid,
+            horizon_dip: HorizonDip::None,
+            refraction_model: RefractionModel::Itl,
+            standard_atmosphere: false,
+            delta_t: DeltaT::EspenakMeeus,
+            ephemeris: Ephemeris::Vsop87,
+            latitude_bands: Vec::new(),
+            fasting_cap: FastingCap::None,
+            nearest_good_day: NearestGoodDaySearch::default(),
+            nearest_latitude: NearestLatitudeReference::Fixed,
+            smoothing_days: 0,
+            custom_strategy: Option::None,
+            rules: HashMap::new(),
             angles,
             intervals,
             minutes,
@@ -0,0 +1,214 @@
+//! Prayer time calculation rules.
+//!
+//! A [`PrayerRule`] calculates the hour of a [`Prayer`] from the solar events of a
+//! [`PrayerDay`], such as its solar noon (Dhuhr), sunrise, and sunset. The built-in rules are
+//! [`AngleRule`] for Fajr and Isha, [`ShadowRatioRule`] for Asr, and [`SunriseSunsetRule`] for
+//! Shurooq and Maghrib. A rule of its own, such as Isha at the later of 17 degrees or 80 minutes
+//! after Maghrib, is used for a prayer through [`Rules`](Params::rules).
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::error::Error;
+//! use chrono::NaiveDate;
+//! use islamic_prayer_times::*;
+//!
+//! // Isha at the later of 17 degrees or 80 minutes after Maghrib.
+//! #[derive(Debug)]
+//! struct LaterIsha;
+//!
+//! impl PrayerRule for LaterIsha {
+//!     fn hour(&self, _prayer: Prayer, day: &PrayerDay) -> Result<f64, ()> {
+//!         let after_maghrib = day.sunset()? + 80. / 60.;
+//!         Ok(match day.altitude_hours(-17.).1 {
+//!             Ok(angle) => angle.max(after_maghrib),
+//!             Err(()) => after_maghrib,
+//!         })
+//!     }
+//! }
+//!
+//! let mut params = Params::new(Method::Isna);
+//! params.rules.insert(Prayer::Isha, CustomRule::new(LaterIsha));
+//! let latitude = Latitude::try_from(39.)?;
+//! let longitude = Longitude::try_from(-77.)?;
+//! let elevation = Elevation::try_from(0.)?;
+//! let coords = Coordinates::new(latitude, longitude, elevation);
+//! let gmt = Gmt::try_from(-5.)?;
+//! let location = Location { coords, gmt };
+//! let date = NaiveDate::from_ymd_opt(2023, 2, 6).unwrap();
+//!
+//! let prayer_times = prayer_times_dt(&params, location, date, None);
+//!
+//! let maghrib = prayer_times[&Prayer::Maghrib].unwrap().time;
+//! let isha = prayer_times[&Prayer::Isha].unwrap().time;
+//! assert!(isha.signed_duration_since(maghrib).num_minutes() >= 80);
+//! # Ok::<(), Box<dyn Error>>(())
+//! ```
+
+use std::{
+    fmt::{self, Debug},
+    sync::Arc,
+};
+
+use chrono::NaiveDate;
+
+use crate::{
+    geo::{astro::TopAstroDay, coordinates::Location},
+    CrossingError, Weather,
+};
+
+use super::{
+    hours::{get_alt_hours, get_asr, get_fajr_isha, get_hour_alt},
+    Params, Prayer,
+};
+
+/// The day whose prayer hours a [`PrayerRule`] calculates, where an hour is in hours from local
+/// midnight.
+#[derive(Debug, Clone, Copy)]
+pub struct PrayerDay<'a> {
+    pub(crate) params: &'a Params,
+    pub(crate) top_astro_day: &'a TopAstroDay,
+    pub(crate) weather: Weather,
+    pub(crate) dhuhr_hour: f64,
+    pub(crate) shur_magh_res: Result<(f64, f64), CrossingError>,
+}
+
+impl PrayerDay<'_> {
+    /// Returns the [`Params`] of the prayer times.
+    pub fn params(&self) -> &Params {
+        self.params
+    }
+
+    /// Returns the date of the day.
+    pub fn date(&self) -> NaiveDate {
+        self.top_astro_day.julian_day().date
+    }
+
+    /// Returns the [`Location`] of the day.
+    pub fn location(&self) -> Location {
+        Location {
+            coords: self.top_astro_day.coords(),
+            gmt: self.top_astro_day.julian_day().gmt,
+        }
+    }
+
+    /// Returns the current [`Weather`] of the day.
+    pub fn weather(&self) -> Weather {
+        self.weather
+    }
+
+    /// Returns the declination of the Sun in degrees.
+    pub fn declination(&self) -> f64 {
+        self.top_astro_day.astro().dec()
+    }
+
+    /// Returns the hour of the solar noon (Dhuhr).
+    pub fn dhuhr(&self) -> f64 {
+        self.dhuhr_hour
+    }
+
+    /// Returns the hour of sunrise, or an error when the Sun does not rise.
+    #[allow(clippy::result_unit_err)]
+    pub fn sunrise(&self) -> Result<f64, ()> {
+        self.shur_magh_res.map(|x| x.0).map_err(|_| ())
+    }
+
+    /// Returns the hour of sunset, or an error when the Sun does not set.
+    #[allow(clippy::result_unit_err)]
+    pub fn sunset(&self) -> Result<f64, ()> {
+        self.shur_magh_res.map(|x| x.1).map_err(|_| ())
+    }
+
+    /// Returns the hours before and after the solar noon the center of the Sun is at an altitude
+    /// in degrees, such as -18 for astronomical twilight, without refraction, or an error when
+    /// it is not.
+    pub fn altitude_hours(&self, altitude: f64) -> (Result<f64, ()>, Result<f64, ()>) {
+        get_alt_hours(self.top_astro_day, self.dhuhr_hour, altitude)
+    }
+}
+
+/// A rule that calculates the hour of a [`Prayer`].
+pub trait PrayerRule: Debug + Send + Sync {
+    /// Returns the hour of the [`Prayer`] on the [`PrayerDay`], or an error when it is invalid,
+    /// such as when the Sun does not reach an angle below the horizon.
+    #[allow(clippy::result_unit_err)]
+    fn hour(&self, prayer: Prayer, day: &PrayerDay) -> Result<f64, ()>;
+}
+
+/// The built-in rule of Fajr and Isha at the [`Angles`](Params::angles) of the Sun below the
+/// horizon before and after the solar noon.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AngleRule;
+
+impl PrayerRule for AngleRule {
+    fn hour(&self, prayer: Prayer, day: &PrayerDay) -> Result<f64, ()> {
+        let (fajr_hour_res, isha_hour_res) =
+            get_fajr_isha(day.params, day.top_astro_day, day.dhuhr_hour);
+        if prayer == Prayer::Fajr {
+            fajr_hour_res
+        } else {
+            isha_hour_res
+        }
+    }
+}
+
+/// The built-in rule of Asr when the shadow of an object is its
+/// [`Asr Shadow Ratio`](Params::asr_shadow_ratio) longer than at the solar noon.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShadowRatioRule;
+
+impl PrayerRule for ShadowRatioRule {
+    fn hour(&self, _prayer: Prayer, day: &PrayerDay) -> Result<f64, ()> {
+        get_asr(day.params, day.top_astro_day, day.dhuhr_hour)
+    }
+}
+
+/// The built-in rule of Shurooq at sunrise and Maghrib at sunset.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SunriseSunsetRule;
+
+impl PrayerRule for SunriseSunsetRule {
+    fn hour(&self, prayer: Prayer, day: &PrayerDay) -> Result<f64, ()> {
+        if prayer == Prayer::Shurooq {
+            day.sunrise()
+        } else {
+            day.sunset()
+        }
+    }
+}
+
+// The rule of Imsaak when the Sun is an angle in degrees lower than at the Fajr of a custom rule.
+#[derive(Debug)]
+pub(crate) struct ImsaakRule(pub(crate) CustomRule, pub(crate) f64);
+
+impl PrayerRule for ImsaakRule {
+    fn hour(&self, prayer: Prayer, day: &PrayerDay) -> Result<f64, ()> {
+        let fajr_hour = self.0 .0.hour(prayer, day)?;
+        let fajr_alt = get_hour_alt(day.top_astro_day, day.dhuhr_hour, fajr_hour);
+        day.altitude_hours(fajr_alt - self.1).0
+    }
+}
+
+/// A shared user supplied [`PrayerRule`].
+#[derive(Clone)]
+pub struct CustomRule(pub Arc<dyn PrayerRule>);
+
+impl CustomRule {
+    /// Creates a new [`CustomRule`] from a [`PrayerRule`].
+    pub fn new(rule: impl PrayerRule + 'static) -> Self {
+        Self(Arc::new(rule))
+    }
+}
+
+impl Debug for CustomRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PartialEq for CustomRule {
+    /// Two [`CustomRule`]s are equal when they share the same rule.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
@@ -0,0 +1,157 @@
+//! Seasonal extreme latitude report of a geographical location.
+//!
+//! [`extreme_latitude_spans`] returns the spans of consecutive dates in which a prayer time
+//! cannot be calculated conventionally with the angles of the [`Params`], such as Fajr and
+//! Isha during the summer in Juneau, Oslo, or Edinburgh, or Shurooq and Maghrib during the
+//! polar day and night of the Arctic. Each [`ExtremeLatitudeSpan`] tells which adjustment
+//! of the [`ExtremeLatitudeMethod`](crate::ExtremeLatitudeMethod) the prayer time falls back
+//! to, and how far the prayer time jumps at the boundaries of the span, to help choose a
+//! method for a location.
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    geo::{astro::TopAstroDay, coordinates::Location, julian_day::JulianDay},
+    DateRange, WeatherSeries,
+};
+
+use super::{
+    current_weather,
+    ext_lat::inv_prayers,
+    get_prayer_times,
+    hours::{get_hours, get_time_change},
+    Params, Prayer, PrayerTime, Provenance, RangeCache,
+};
+
+/// A span of consecutive dates in which a [`Prayer`] time cannot be calculated conventionally.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExtremeLatitudeSpan {
+    /// The prayer that cannot be calculated conventionally.
+    pub prayer: Prayer,
+    /// The first date of the span.
+    pub start_date: NaiveDate,
+    /// The last date of the span.
+    pub end_date: NaiveDate,
+    /// The adjustment the prayer time falls back to on the first date of the span, or `None`
+    /// when the prayer time remains invalid.
+    pub fallback: Option<Provenance>,
+    /// The change in minutes of the prayer time from the date before the span to its first
+    /// date, or `None` when either prayer time is invalid or outside the date range.
+    pub start_discontinuity: Option<f64>,
+    /// The largest change in minutes of the prayer time between consecutive dates of the span,
+    /// such as when the nearest good day switches from before to after the span, or `None`
+    /// when no two consecutive prayer times of the span are valid.
+    pub largest_discontinuity: Option<f64>,
+    /// The change in minutes of the prayer time from the last date of the span to the date
+    /// after it, or `None` when either prayer time is invalid or outside the date range.
+    pub end_discontinuity: Option<f64>,
+}
+
+/// Returns the [`ExtremeLatitudeSpan`]s, ordered by start date, in which a [`Prayer`] time
+/// cannot be calculated conventionally using the specified [`Params`] for a [`Location`],
+/// [`DateRange`], and its (optional) [`WeatherSeries`].
+///
+/// A span that continues past either end of the [`DateRange`] is cut at it.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// use chrono::NaiveDate;
+/// use islamic_prayer_times::*;
+///
+/// let params = Params::new(Method::Isna);
+/// let latitude = Latitude::try_from(58.3)?;
+/// let longitude = Longitude::try_from(-134.42)?;
+/// let elevation = Elevation::try_from(0.)?;
+/// let coords = Coordinates::new(latitude, longitude, elevation);
+/// let gmt = Gmt::try_from(-9.)?;
+/// let location = Location { coords, gmt };
+/// let start_date = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+/// let end_date = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+/// let date_range = DateRange::from(start_date..=end_date);
+///
+/// let spans = extreme_latitude_spans(&params, location, &date_range, None);
+///
+/// assert!(spans.iter().any(|x| x.prayer == Prayer::Isha));
+/// # Ok::<(), Box<dyn Error>>(())
+/// ```
+pub fn extreme_latitude_spans(
+    params: &Params,
+    location: Location,
+    date_range: &DateRange,
+    weather_series: Option<&WeatherSeries>,
+) -> Vec<ExtremeLatitudeSpan> {
+    use Prayer::*;
+
+    let cache = RangeCache::new(params, location);
+    let mut days = Vec::new();
+    for date in date_range
+        .start_date()
+        .iter_days()
+        .take(date_range.num_days())
+    {
+        let weather = current_weather(
+            params,
+            location,
+            weather_series.and_then(|x| x.weather_on(date)),
+        );
+        let julian_day = JulianDay::new(date, location.gmt);
+        let top_astro_day = TopAstroDay::from_cache(&cache.astros, julian_day, location.coords);
+        let inv = inv_prayers(&get_hours(params, &top_astro_day, weather));
+        let times = get_prayer_times(params, &top_astro_day, weather, &cache);
+        days.push((date, inv, times));
+        cache.slide(date);
+    }
+
+    let mut spans = Vec::new();
+    for prayer in &[Fajr, Shurooq, Dhuhr, Asr, Maghrib, Isha] {
+        let mut i = 0;
+        while i < days.len() {
+            if !days[i].1.contains(prayer) {
+                i += 1;
+                continue;
+            }
+
+            let start = i;
+            while i < days.len() && days[i].1.contains(prayer) {
+                i += 1;
+            }
+            let end = i - 1;
+
+            spans.push(ExtremeLatitudeSpan {
+                prayer: *prayer,
+                start_date: days[start].0,
+                end_date: days[end].0,
+                fallback: days[start].2[prayer].ok().and_then(|x| x.provenance),
+                start_discontinuity: start
+                    .checked_sub(1)
+                    .and_then(|x| get_discontinuity(&days[x].2, &days[start].2, *prayer)),
+                largest_discontinuity: (start..end)
+                    .filter_map(|x| get_discontinuity(&days[x].2, &days[x + 1].2, *prayer))
+                    .max_by(|x, y| x.abs().total_cmp(&y.abs())),
+                end_discontinuity: days
+                    .get(end + 1)
+                    .and_then(|x| get_discontinuity(&days[end].2, &x.2, *prayer)),
+            });
+        }
+    }
+
+    spans.sort_by_key(|x| (x.start_date, x.prayer));
+    spans
+}
+
+fn get_discontinuity(
+    before: &BTreeMap<Prayer, Result<PrayerTime, ()>>,
+    after: &BTreeMap<Prayer, Result<PrayerTime, ()>>,
+    prayer: Prayer,
+) -> Option<f64> {
+    let (Ok(before), Ok(after)) = (before[&prayer], after[&prayer]) else {
+        return None;
+    };
+
+    Some(get_time_change(before.time, after.time))
+}
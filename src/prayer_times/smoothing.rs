@@ -0,0 +1,175 @@
+// Continuity smoothing of the prayer times of a date range where an extreme latitude adjustment
+// starts, ends, or switches, such as Isha jumping by half an hour to its nearest good day.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Duration, NaiveDate, NaiveTime};
+
+use crate::{
+    geo::{
+        astro::{AstroCache, TopAstroDay},
+        coordinates::Location,
+        julian_day::JulianDay,
+    },
+    WeatherSeries,
+};
+
+use super::{
+    current_weather,
+    hours::{
+        get_hours, get_time_change, hour_to_time, time_to_mins, HRS_PER_DAY, MIN_SEC_PER_HR_MIN,
+    },
+    Params, Prayer, PrayerTime, Provenance, Reference,
+};
+
+// Blends the prayer times of the dates next to a change of adjustment from the prayer time before
+// the change to the adjusted prayer times over the smoothing days of the `Params`. A blended
+// prayer time keeps the order of the prayer times of its date and is never before its
+// conventionally calculated prayer time.
+pub fn smooth_times(
+    params: &Params,
+    location: Location,
+    times: &mut BTreeMap<NaiveDate, BTreeMap<Prayer, Result<PrayerTime, ()>>>,
+    weather_series: Option<&WeatherSeries>,
+) {
+    use Prayer::*;
+
+    let prayers = [Fajr, Shurooq, Dhuhr, Asr, Maghrib, Isha];
+    let dates: Vec<_> = times.keys().copied().collect();
+    let min_times = get_min_times(params, location, &dates, weather_series);
+    let max_days = params.smoothing_days as usize;
+
+    for prayer in prayers {
+        let day_times: Vec<_> = dates.iter().map(|x| times[x][&prayer]).collect();
+        let mut offsets = vec![0.; dates.len()];
+        for k in 1..day_times.len() {
+            let (Ok(before), Ok(after)) = (day_times[k - 1], day_times[k]) else {
+                continue;
+            };
+            if is_same_adj(before.provenance, after.provenance) {
+                continue;
+            }
+
+            // The blend covers the smoothing days of the adjustment alone, whatever the dates
+            // around it, so that it does not depend on where the dates start or end.
+            let jump = get_time_change(before.time, after.time);
+            let weight = |j: usize| (max_days - j) as f64 / (max_days + 1) as f64;
+            if after.provenance.is_some() {
+                // Blend the adjusted prayer times from the change onward.
+                for j in (0..max_days)
+                    .take_while(|j| k + j < day_times.len())
+                    .take_while(|j| is_adj_like(day_times[k + j], after.provenance))
+                {
+                    offsets[k + j] -= jump * weight(j);
+                }
+            } else {
+                // Blend the adjusted prayer times up to the change.
+                for j in (0..max_days)
+                    .take_while(|j| *j < k)
+                    .take_while(|j| is_adj_like(day_times[k - 1 - j], before.provenance))
+                {
+                    offsets[k - 1 - j] += jump * weight(j);
+                }
+            }
+        }
+
+        for (i, date) in dates.iter().enumerate() {
+            let offset = offsets[i].round();
+            if offset != 0. {
+                offset_time(times.get_mut(date).unwrap(), &min_times[i], prayer, offset);
+            }
+        }
+    }
+}
+
+// Offsets a prayer time of a date by minutes, clamped to not before its minimum and to between
+// the prayer times before and after it so that it stays in order.
+fn offset_time(
+    day_times: &mut BTreeMap<Prayer, Result<PrayerTime, ()>>,
+    min_times: &HashMap<Prayer, NaiveTime>,
+    prayer: Prayer,
+    offset: f64,
+) {
+    use Prayer::*;
+
+    let Ok(prayer_time) = day_times[&prayer] else {
+        return;
+    };
+    let mut shift = offset;
+    if let Some(min_time) = min_times.get(&prayer) {
+        shift = shift.max(get_time_change(prayer_time.time, *min_time).min(0.));
+    }
+
+    // The other valid prayer times of the date in order after the prayer time, around the clock.
+    let prayers = [Fajr, Shurooq, Dhuhr, Asr, Maghrib, Isha];
+    let index = prayers.iter().position(|x| *x == prayer).unwrap();
+    let others: Vec<_> = (1..prayers.len())
+        .filter_map(|i| day_times[&prayers[(index + i) % prayers.len()]].ok())
+        .map(|x| x.time)
+        .collect();
+    if let (Some(next), Some(prev)) = (others.first(), others.last()) {
+        let mins_per_day = HRS_PER_DAY * MIN_SEC_PER_HR_MIN;
+        let mins = time_to_mins(prayer_time.time);
+        let max_shift = (time_to_mins(*next) - mins).rem_euclid(mins_per_day);
+        let min_shift = -(mins - time_to_mins(*prev)).rem_euclid(mins_per_day);
+        shift = shift.clamp(min_shift, max_shift);
+    }
+
+    let shift = Duration::seconds((shift * MIN_SEC_PER_HR_MIN).round() as i64);
+    day_times.get_mut(&prayer).unwrap().as_mut().unwrap().time += shift;
+    if prayer == Fajr {
+        if let Ok(imsaak) = day_times.get_mut(&Imsaak).unwrap() {
+            imsaak.time += shift;
+        }
+    }
+}
+
+// Returns the conventionally calculated prayer times of the dates, which are the earliest ones.
+fn get_min_times(
+    params: &Params,
+    location: Location,
+    dates: &[NaiveDate],
+    weather_series: Option<&WeatherSeries>,
+) -> Vec<HashMap<Prayer, NaiveTime>> {
+    let cache = AstroCache::new(location.gmt, params.delta_t, params.ephemeris);
+    dates
+        .iter()
+        .map(|date| {
+            let weather = current_weather(
+                params,
+                location,
+                weather_series.and_then(|x| x.weather_on(*date)),
+            );
+            let julian_day = JulianDay::new(*date, location.gmt);
+            let top_astro_day = TopAstroDay::from_cache(&cache, julian_day, location.coords);
+            let hours = get_hours(params, &top_astro_day, weather);
+            cache.slide(*date);
+            hours
+                .into_iter()
+                .filter_map(|(prayer, hour)| {
+                    hour.ok().map(|x| (prayer, hour_to_time(params, prayer, x)))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// Whether two prayer times are adjusted the same way, where the reference of some adjustments
+// changes gradually from day to day while that of the nearest good day jumps.
+fn is_same_adj(before: Option<Provenance>, after: Option<Provenance>) -> bool {
+    match (before, after) {
+        (None, None) => true,
+        (Some(before), Some(after)) => {
+            before.method == after.method
+                && match (before.reference, after.reference) {
+                    (Reference::Date(x), Reference::Date(y)) => x == y,
+                    (x, y) => std::mem::discriminant(&x) == std::mem::discriminant(&y),
+                }
+        }
+        _ => false,
+    }
+}
+
+fn is_adj_like(prayer_time: Result<PrayerTime, ()>, provenance: Option<Provenance>) -> bool {
+    prayer_time.is_ok_and(|x| is_same_adj(x.provenance, provenance))
+}
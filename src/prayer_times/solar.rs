@@ -0,0 +1,419 @@
+//! Solar events of a day at a geographical location.
+//!
+//! [`solar_day`] returns a [`SolarDay`] summary of solar noon, sunrise and sunset, day
+//! length, equation of time, declination, maximum altitude, and the civil, nautical, and
+//! astronomical twilight bands of a day, such as for an almanac page.
+//!
+//! [`altitude_crossings`] returns the local times the center of the Sun crosses an
+//! arbitrary altitude while rising and while setting, such as for civil (-6°), nautical
+//! (-12°), and astronomical (-18°) twilight, experimental Fajr angles, or Duha. A crossing
+//! that does not happen on the day is returned as a [`CrossingError`] that tells whether
+//! the Sun stays above or below the altitude all day.
+//!
+//! [`visible_sunrise_sunset`] returns the times the Sun appears above and disappears behind
+//! the obstructed horizon of a [`HorizonProfile`], such as in a mountain valley, next to the
+//! standard sunrise and sunset.
+
+use std::collections::BTreeMap;
+
+use chrono::{NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    angle::{RIGHT_ANG_DEG, TWO_PI_DEG},
+    geo::{
+        astro::{AstroCache, TopAstroDay},
+        coordinates::Location,
+        horizon::HorizonProfile,
+        julian_day::JulianDay,
+        root::bisect,
+        sun::SunPosition,
+    },
+    CrossingError, DateRange, Weather, WeatherSeries,
+};
+
+use super::{
+    current_weather,
+    hours::{
+        get_dec_at, get_rise_set, get_shur_dhuhr_magh, hour_to_exact_time, HRS_PER_DAY,
+        MIN_SEC_PER_HR_MIN, SUN_SEMI_DIAMETER,
+    },
+    Params,
+};
+
+/// The local times the center of the Sun crosses an altitude on a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AltitudeCrossings {
+    /// The time the Sun crosses the altitude while rising.
+    pub rising: Result<NaiveTime, CrossingError>,
+    /// The time the Sun crosses the altitude while setting.
+    pub setting: Result<NaiveTime, CrossingError>,
+}
+
+/// Returns the [`AltitudeCrossings`] of an altitude in degrees of the center of the Sun using
+/// the [`Refraction Model`](Params::refraction_model), [`Delta T`](Params::delta_t), and
+/// [`Ephemeris`](Params::ephemeris) of the specified [`Params`] for a [`Location`],
+/// [`NaiveDate`], and its (optional) current [`Weather`].
+///
+/// With the default [`Itl`](crate::RefractionModel::Itl) refraction model, an altitude of -0.83337
+/// degrees accounts for the semi-diameter of the Sun and the standard refraction at the
+/// horizon, and gives the same times as Shurooq and Maghrib before rounding.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// use chrono::NaiveDate;
+/// use islamic_prayer_times::*;
+///
+/// let latitude = Latitude::try_from(39.)?;
+/// let longitude = Longitude::try_from(-77.)?;
+/// let elevation = Elevation::try_from(0.)?;
+/// let coords = Coordinates::new(latitude, longitude, elevation);
+/// let gmt = Gmt::try_from(-5.)?;
+/// let location = Location { coords, gmt };
+/// let date = NaiveDate::from_ymd_opt(2023, 2, 6).unwrap();
+///
+/// let civil_twilight = altitude_crossings(&Params::default(), location, date, -6., None);
+///
+/// assert!(civil_twilight.rising.unwrap() < civil_twilight.setting.unwrap());
+/// # Ok::<(), Box<dyn Error>>(())
+/// ```
+pub fn altitude_crossings(
+    params: &Params,
+    location: Location,
+    date: NaiveDate,
+    altitude: f64,
+    weather: Option<Weather>,
+) -> AltitudeCrossings {
+    let julian_day = JulianDay::new(date, location.gmt);
+    let top_astro_day = TopAstroDay::from_jd(
+        julian_day,
+        location.coords,
+        params.delta_t,
+        params.ephemeris,
+    );
+    get_altitude_crossings(
+        params,
+        &top_astro_day,
+        altitude,
+        current_weather(params, location, weather),
+    )
+}
+
+fn get_altitude_crossings(
+    params: &Params,
+    top_astro_day: &TopAstroDay,
+    altitude: f64,
+    weather: Weather,
+) -> AltitudeCrossings {
+    match get_rise_set(top_astro_day, weather, params.refraction_model, altitude) {
+        Ok((rise_hour, set_hour)) => AltitudeCrossings {
+            rising: Ok(hour_to_exact_time(rise_hour)),
+            setting: Ok(hour_to_exact_time(set_hour)),
+        },
+        Err(err) => AltitudeCrossings {
+            rising: Err(err),
+            setting: Err(err),
+        },
+    }
+}
+
+/// A summary of the solar events of a day at a [`Location`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SolarDay {
+    /// The time the Sun transits the meridian (Dhuhr before any adjustment).
+    pub solar_noon: NaiveTime,
+    /// The time of sunrise (Shurooq before any adjustment).
+    pub sunrise: Result<NaiveTime, CrossingError>,
+    /// The time of sunset (Maghrib before any adjustment).
+    pub sunset: Result<NaiveTime, CrossingError>,
+    /// The hours between sunrise and sunset, which are 24 when the Sun does not set and 0
+    /// when it does not rise.
+    pub day_length: f64,
+    /// The minutes apparent solar time is ahead of local mean time.
+    pub equation_of_time: f64,
+    /// The declination of the Sun in degrees at solar noon.
+    pub declination: f64,
+    /// The altitude of the Sun in degrees at solar noon, corrected for atmospheric refraction.
+    pub max_altitude: f64,
+    /// Civil dawn and dusk.
+    pub civil_twilight: AltitudeCrossings,
+    /// Nautical dawn and dusk.
+    pub nautical_twilight: AltitudeCrossings,
+    /// Astronomical dawn and dusk.
+    pub astronomical_twilight: AltitudeCrossings,
+}
+
+impl SolarDay {
+    /// Altitude of the Sun at civil twilight.
+    pub const CIVIL_TWILIGHT_ANGLE: f64 = -6.;
+    /// Altitude of the Sun at nautical twilight.
+    pub const NAUTICAL_TWILIGHT_ANGLE: f64 = -12.;
+    /// Altitude of the Sun at astronomical twilight.
+    pub const ASTRONOMICAL_TWILIGHT_ANGLE: f64 = -18.;
+}
+
+/// Returns the [`SolarDay`] using the [`Refraction Model`](Params::refraction_model),
+/// [`Delta T`](Params::delta_t), [`Ephemeris`](Params::ephemeris), and
+/// [`Standard Atmosphere`](Params::standard_atmosphere) of the specified [`Params`] for a
+/// [`Location`], [`NaiveDate`], and its (optional) current [`Weather`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// use chrono::NaiveDate;
+/// use islamic_prayer_times::*;
+///
+/// let latitude = Latitude::try_from(39.)?;
+/// let longitude = Longitude::try_from(-77.)?;
+/// let elevation = Elevation::try_from(0.)?;
+/// let coords = Coordinates::new(latitude, longitude, elevation);
+/// let gmt = Gmt::try_from(-5.)?;
+/// let location = Location { coords, gmt };
+/// let date = NaiveDate::from_ymd_opt(2023, 2, 6).unwrap();
+///
+/// let solar_day = solar_day(&Params::default(), location, date, None);
+///
+/// assert!(solar_day.day_length > 10. && solar_day.day_length < 11.);
+/// # Ok::<(), Box<dyn Error>>(())
+/// ```
+pub fn solar_day(
+    params: &Params,
+    location: Location,
+    date: NaiveDate,
+    weather: Option<Weather>,
+) -> SolarDay {
+    let julian_day = JulianDay::new(date, location.gmt);
+    let top_astro_day = TopAstroDay::from_jd(
+        julian_day,
+        location.coords,
+        params.delta_t,
+        params.ephemeris,
+    );
+    get_solar_day(
+        params,
+        location,
+        &top_astro_day,
+        current_weather(params, location, weather),
+    )
+}
+
+/// Returns a [`B-tree`](std::collections::BTreeMap) of [`NaiveDate`] keys to [`SolarDay`] values
+/// using the [`Refraction Model`](Params::refraction_model), [`Delta T`](Params::delta_t),
+/// [`Ephemeris`](Params::ephemeris), and
+/// [`Standard Atmosphere`](Params::standard_atmosphere) of the specified [`Params`] for a
+/// [`Location`], [`DateRange`], and its (optional) [`WeatherSeries`]. Use
+/// [`prayer_times_solar_days_dt_rng`](super::prayer_times_solar_days_dt_rng) to calculate the prayer
+/// times of the same dates from the same ephemerides.
+pub fn solar_days_dt_rng(
+    params: &Params,
+    location: Location,
+    date_range: &DateRange,
+    weather_series: Option<&WeatherSeries>,
+) -> BTreeMap<NaiveDate, SolarDay> {
+    let cache = AstroCache::new(location.gmt, params.delta_t, params.ephemeris);
+    let mut solar_days = BTreeMap::new();
+    for date in date_range
+        .start_date()
+        .iter_days()
+        .take(date_range.num_days())
+    {
+        let weather = current_weather(
+            params,
+            location,
+            weather_series.and_then(|x| x.weather_on(date)),
+        );
+        let julian_day = JulianDay::new(date, location.gmt);
+        let top_astro_day = TopAstroDay::from_cache(&cache, julian_day, location.coords);
+        solar_days.insert(
+            date,
+            get_solar_day(params, location, &top_astro_day, weather),
+        );
+        cache.slide(date);
+    }
+    solar_days
+}
+
+pub(super) fn get_solar_day(
+    params: &Params,
+    location: Location,
+    top_astro_day: &TopAstroDay,
+    weather: Weather,
+) -> SolarDay {
+    let (shur_magh_res, dhuhr_hour) =
+        get_shur_dhuhr_magh(top_astro_day, weather, params.refraction_model, 0.);
+
+    let day_length = match shur_magh_res {
+        Ok((shur_hour, magh_hour)) => (magh_hour - shur_hour).rem_euclid(HRS_PER_DAY),
+        Err(CrossingError::AlwaysAbove) => HRS_PER_DAY,
+        Err(CrossingError::AlwaysBelow | CrossingError::NotOnDay) => 0.,
+    };
+
+    // Local mean time of the transit of the mean sun
+    let mean_noon_hour = HRS_PER_DAY / 2. + f64::from(location.gmt)
+        - f64::from(location.coords.longitude) * HRS_PER_DAY / TWO_PI_DEG;
+    let equation_of_time = (mean_noon_hour - dhuhr_hour) * MIN_SEC_PER_HR_MIN;
+
+    let declination = get_dec_at(top_astro_day, dhuhr_hour);
+    let airless_alt = RIGHT_ANG_DEG - (f64::from(location.coords.latitude) - declination).abs();
+    let max_altitude = airless_alt
+        + params
+            .refraction_model
+            .for_sun_position()
+            .degrees(airless_alt, weather);
+
+    SolarDay {
+        solar_noon: hour_to_exact_time(dhuhr_hour),
+        sunrise: shur_magh_res.map(|x| hour_to_exact_time(x.0)),
+        sunset: shur_magh_res.map(|x| hour_to_exact_time(x.1)),
+        day_length,
+        equation_of_time,
+        declination,
+        max_altitude,
+        civil_twilight: get_altitude_crossings(
+            params,
+            top_astro_day,
+            SolarDay::CIVIL_TWILIGHT_ANGLE,
+            weather,
+        ),
+        nautical_twilight: get_altitude_crossings(
+            params,
+            top_astro_day,
+            SolarDay::NAUTICAL_TWILIGHT_ANGLE,
+            weather,
+        ),
+        astronomical_twilight: get_altitude_crossings(
+            params,
+            top_astro_day,
+            SolarDay::ASTRONOMICAL_TWILIGHT_ANGLE,
+            weather,
+        ),
+    }
+}
+
+/// The standard and visible sunrise and sunset of a day at a [`Location`] with an obstructed
+/// horizon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VisibleSunriseSunset {
+    /// The time of sunrise over the astronomical horizon.
+    pub sunrise: Result<NaiveTime, CrossingError>,
+    /// The time of sunset below the astronomical horizon.
+    pub sunset: Result<NaiveTime, CrossingError>,
+    /// The time the upper limb of the Sun first appears above the obstructed horizon.
+    pub visible_sunrise: Result<NaiveTime, CrossingError>,
+    /// The time the upper limb of the Sun last disappears behind the obstructed horizon.
+    pub visible_sunset: Result<NaiveTime, CrossingError>,
+}
+
+impl VisibleSunriseSunset {
+    // Days between samples of the path of the Sun
+    const STEP: f64 = 2. / 1440.;
+    // Tolerance in days of a visible sunrise or sunset
+    const TOLERANCE: f64 = 1. / 86400.;
+}
+
+/// Returns the [`VisibleSunriseSunset`] using the
+/// [`Refraction Model`](Params::refraction_model), [`Delta T`](Params::delta_t),
+/// [`Ephemeris`](Params::ephemeris), and [`Standard Atmosphere`](Params::standard_atmosphere)
+/// of the specified [`Params`] for a [`Location`], [`NaiveDate`], [`HorizonProfile`], and its
+/// (optional) current [`Weather`].
+///
+/// The visible sunrise and sunset follow the path of the Sun in azimuth and refracted altitude
+/// over the day, so a Sun that dips behind a peak and reappears keeps the first appearance and
+/// the last disappearance. A visible sunrise or sunset that misses the day while the other one
+/// happens is a [`CrossingError::NotOnDay`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// use chrono::NaiveDate;
+/// use islamic_prayer_times::*;
+///
+/// let latitude = Latitude::try_from(39.)?;
+/// let longitude = Longitude::try_from(-77.)?;
+/// let elevation = Elevation::try_from(0.)?;
+/// let coords = Coordinates::new(latitude, longitude, elevation);
+/// let gmt = Gmt::try_from(-5.)?;
+/// let location = Location { coords, gmt };
+/// let date = NaiveDate::from_ymd_opt(2023, 2, 6).unwrap();
+/// let horizon_profile = HorizonProfile::from_csv("0,5\n180,5\n")?;
+///
+/// let sunrise_sunset =
+///     visible_sunrise_sunset(&Params::default(), location, date, &horizon_profile, None);
+///
+/// assert!(sunrise_sunset.visible_sunrise.unwrap() > sunrise_sunset.sunrise.unwrap());
+/// assert!(sunrise_sunset.visible_sunset.unwrap() < sunrise_sunset.sunset.unwrap());
+/// # Ok::<(), Box<dyn Error>>(())
+/// ```
+pub fn visible_sunrise_sunset(
+    params: &Params,
+    location: Location,
+    date: NaiveDate,
+    horizon_profile: &HorizonProfile,
+    weather: Option<Weather>,
+) -> VisibleSunriseSunset {
+    let weather = current_weather(params, location, weather);
+    let julian_day = JulianDay::new(date, location.gmt);
+    let top_astro_day = TopAstroDay::from_jd(
+        julian_day,
+        location.coords,
+        params.delta_t,
+        params.ephemeris,
+    );
+    let (shur_magh_res, _) =
+        get_shur_dhuhr_magh(&top_astro_day, weather, params.refraction_model, 0.);
+
+    // Altitude of the upper limb of the Sun above the obstructed horizon
+    let clearance = |jd: f64| {
+        let sun_position = SunPosition::from_jd(
+            location.coords,
+            jd,
+            weather,
+            params.refraction_model.for_sun_position(),
+            params.delta_t,
+            params.ephemeris,
+        );
+        sun_position.altitude + SUN_SEMI_DIAMETER
+            - horizon_profile.elevation_at(sun_position.azimuth)
+    };
+
+    let start_jd = f64::from(julian_day);
+    let end_jd = start_jd + 1.;
+    let mut jd = start_jd;
+    let mut prev_clearance = clearance(jd);
+    let start_clearance = prev_clearance;
+    let (mut rise_jd, mut set_jd) = (None, None);
+    while jd < end_jd {
+        let next_jd = (jd + VisibleSunriseSunset::STEP).min(end_jd);
+        let next_clearance = clearance(next_jd);
+        if (prev_clearance > 0.) != (next_clearance > 0.) {
+            let crossing_jd = bisect(clearance, jd, next_jd, VisibleSunriseSunset::TOLERANCE);
+            if next_clearance > 0. {
+                rise_jd.get_or_insert(crossing_jd);
+            } else {
+                set_jd = Some(crossing_jd);
+            }
+        }
+        jd = next_jd;
+        prev_clearance = next_clearance;
+    }
+
+    // Each event is missing on its own, such as a visible sunset that moves past midnight
+    // while the visible sunrise remains on the day.
+    let to_time = |crossing_jd: Option<f64>| match crossing_jd {
+        Some(crossing_jd) => Ok(hour_to_exact_time((crossing_jd - start_jd) * HRS_PER_DAY)),
+        None if rise_jd.or(set_jd).is_some() => Err(CrossingError::NotOnDay),
+        None if start_clearance > 0. => Err(CrossingError::AlwaysAbove),
+        None => Err(CrossingError::AlwaysBelow),
+    };
+
+    VisibleSunriseSunset {
+        sunrise: shur_magh_res.map(|x| hour_to_exact_time(x.0)),
+        sunset: shur_magh_res.map(|x| hour_to_exact_time(x.1)),
+        visible_sunrise: to_time(rise_jd),
+        visible_sunset: to_time(set_jd),
+    }
+}
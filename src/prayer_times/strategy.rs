@@ -0,0 +1,198 @@
+//! Extreme latitude strategies.
+//!
+//! An [`ExtremeLatitudeStrategy`] adjusts the conventionally calculated prayer hours of an
+//! [`ExtremeLatitudeDay`], such as when Fajr and Isha prayer times are invalid during the
+//! summer at a high latitude, and records the [`Provenance`] of each adjusted [`PrayerHour`].
+//! Each [`ExtremeLatitudeMethod`](crate::ExtremeLatitudeMethod) is a built-in strategy. A strategy of its own, such as the
+//! rule of a regional council, is used through [`Custom Strategy`](Params::custom_strategy)
+//! when the [`Extreme Latitude Method`](Params::extreme_latitude_method) is
+//! [`Custom`](crate::ExtremeLatitudeMethod::Custom).
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::error::Error;
+//! use std::collections::HashMap;
+//!
+//! use chrono::NaiveDate;
+//! use islamic_prayer_times::*;
+//!
+//! // Isha 90 minutes after Maghrib whenever it is invalid.
+//! #[derive(Debug)]
+//! struct IshaAfterMaghrib;
+//!
+//! impl ExtremeLatitudeStrategy for IshaAfterMaghrib {
+//!     fn adjust(
+//!         &self,
+//!         day: &ExtremeLatitudeDay,
+//!         hours: &HashMap<Prayer, Result<f64, ()>>,
+//!     ) -> HashMap<Prayer, Result<PrayerHour, ()>> {
+//!         let mut adj_hours: HashMap<_, _> = hours
+//!             .iter()
+//!             .map(|(prayer, hour)| (*prayer, hour.map(PrayerHour::new)))
+//!             .collect();
+//!         if let (Err(()), Ok(maghrib)) = (hours[&Prayer::Isha], hours[&Prayer::Maghrib]) {
+//!             let provenance = Provenance {
+//!                 method: day.params().extreme_latitude_method,
+//!                 reference: Reference::Minutes(90.),
+//!             };
+//!             let isha = PrayerHour::new_extreme(maghrib + 1.5, provenance);
+//!             adj_hours.insert(Prayer::Isha, Ok(isha));
+//!         }
+//!         adj_hours
+//!     }
+//! }
+//!
+//! let mut params = Params::new(Method::Isna);
+//! params.extreme_latitude_method = ExtremeLatitudeMethod::Custom;
+//! params.custom_strategy = Some(CustomStrategy::new(IshaAfterMaghrib));
+//! let latitude = Latitude::try_from(58.3)?;
+//! let longitude = Longitude::try_from(-134.42)?;
+//! let elevation = Elevation::try_from(0.)?;
+//! let coords = Coordinates::new(latitude, longitude, elevation);
+//! let gmt = Gmt::try_from(-9.)?;
+//! let location = Location { coords, gmt };
+//! let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+//!
+//! let prayer_times = prayer_times_dt(&params, location, date, None);
+//!
+//! assert!(prayer_times[&Prayer::Isha].unwrap().extreme);
+//! # Ok::<(), Box<dyn Error>>(())
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug},
+    sync::Arc,
+};
+
+use chrono::NaiveDate;
+
+use crate::{
+    geo::{
+        astro::TopAstroDay,
+        coordinates::{Coordinates, Location},
+        julian_day::JulianDay,
+    },
+    Weather,
+};
+
+use super::{hours::get_hours, Params, Prayer, Provenance, RangeCache};
+
+/// A prayer hour, in hours from local midnight, and the [`Provenance`] of its adjustment for an
+/// extreme latitude, or `None` when it is calculated conventionally.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrayerHour {
+    /// The hour of the prayer time.
+    pub value: f64,
+    /// The adjustment of the prayer hour.
+    pub provenance: Option<Provenance>,
+}
+
+impl PrayerHour {
+    /// Creates a conventionally calculated [`PrayerHour`].
+    pub fn new(hour: f64) -> Self {
+        Self {
+            value: hour,
+            provenance: None,
+        }
+    }
+
+    /// Creates a [`PrayerHour`] adjusted for an extreme latitude.
+    pub fn new_extreme(hour: f64, provenance: Provenance) -> Self {
+        Self {
+            value: hour,
+            provenance: Some(provenance),
+        }
+    }
+}
+
+/// The day whose prayer hours an [`ExtremeLatitudeStrategy`] adjusts.
+#[derive(Debug, Clone, Copy)]
+pub struct ExtremeLatitudeDay<'a> {
+    pub(crate) params: &'a Params,
+    pub(crate) top_astro_day: &'a TopAstroDay,
+    pub(crate) weather: Weather,
+    pub(crate) cache: &'a RangeCache,
+}
+
+impl ExtremeLatitudeDay<'_> {
+    /// Returns the [`Params`] of the prayer times.
+    pub fn params(&self) -> &Params {
+        self.params
+    }
+
+    /// Returns the date of the day.
+    pub fn date(&self) -> NaiveDate {
+        self.top_astro_day.julian_day().date
+    }
+
+    /// Returns the [`Location`] of the day.
+    pub fn location(&self) -> Location {
+        Location {
+            coords: self.top_astro_day.coords(),
+            gmt: self.top_astro_day.julian_day().gmt,
+        }
+    }
+
+    /// Returns the current [`Weather`] of the day.
+    pub fn weather(&self) -> Weather {
+        self.weather
+    }
+
+    /// Returns the conventionally calculated prayer hours of the day at other [`Coordinates`],
+    /// such as at a nearer latitude, in hours from local midnight of the location.
+    pub fn hours_at(&self, coords: Coordinates) -> HashMap<Prayer, Result<f64, ()>> {
+        get_hours(
+            self.params,
+            &self.top_astro_day.new_coords(coords),
+            self.weather,
+        )
+    }
+
+    /// Returns the conventionally calculated prayer hours of the location on another date, such
+    /// as a nearby good day.
+    pub fn hours_on(&self, date: NaiveDate) -> HashMap<Prayer, Result<f64, ()>> {
+        let julian_day = JulianDay::new(date, self.top_astro_day.julian_day().gmt);
+        let top_astro_day =
+            TopAstroDay::from_cache(&self.cache.astros, julian_day, self.top_astro_day.coords());
+        get_hours(self.params, &top_astro_day, self.weather)
+    }
+}
+
+/// A strategy that adjusts the prayer hours of a day for an extreme latitude.
+pub trait ExtremeLatitudeStrategy: Debug + Send + Sync {
+    /// Returns the [`PrayerHour`]s of Fajr, Shurooq, Dhuhr, Asr, Maghrib, and Isha adjusted from
+    /// the conventionally calculated prayer hours of the [`ExtremeLatitudeDay`], where an
+    /// invalid prayer hour is an error. A prayer left out of those of a
+    /// [`CustomStrategy`] keeps its conventionally calculated hour.
+    fn adjust(
+        &self,
+        day: &ExtremeLatitudeDay,
+        hours: &HashMap<Prayer, Result<f64, ()>>,
+    ) -> HashMap<Prayer, Result<PrayerHour, ()>>;
+}
+
+/// A shared user supplied [`ExtremeLatitudeStrategy`].
+#[derive(Clone)]
+pub struct CustomStrategy(pub Arc<dyn ExtremeLatitudeStrategy>);
+
+impl CustomStrategy {
+    /// Creates a new [`CustomStrategy`] from an [`ExtremeLatitudeStrategy`].
+    pub fn new(strategy: impl ExtremeLatitudeStrategy + 'static) -> Self {
+        Self(Arc::new(strategy))
+    }
+}
+
+impl Debug for CustomStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PartialEq for CustomStrategy {
+    /// Two [`CustomStrategy`]s are equal when they share the same strategy.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
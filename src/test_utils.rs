@@ -1,3 +1,2 @@
 #[cfg(test)]
-
 pub(crate) const EPSILON_TEST: f64 = 0.00000001;
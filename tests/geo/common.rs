@@ -0,0 +1,11 @@
+use islamic_prayer_times::geo::coordinates::{
+    Coordinates, Elevation, Gmt, Latitude, Location, Longitude,
+};
+
+pub fn potomac_md() -> Location {
+    let latitude = Latitude::try_from(39.0181651).unwrap();
+    let longitude = Longitude::try_from(-77.2085914).unwrap();
+    let coords = Coordinates::new(latitude, longitude, Elevation::default());
+    let gmt = Gmt::try_from(-5.).unwrap();
+    Location { coords, gmt }
+}
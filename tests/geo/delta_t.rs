@@ -0,0 +1,79 @@
+use chrono::NaiveDate;
+use islamic_prayer_times::DeltaT;
+
+fn assert_delta_t(year: i32, expected: f64, tolerance: f64) {
+    let date = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+    let delta_t = DeltaT::EspenakMeeus.seconds(date);
+    assert!(
+        (delta_t - expected).abs() <= tolerance,
+        "ΔT in {} is {} and not {} ± {}",
+        year,
+        delta_t,
+        expected,
+        tolerance
+    );
+}
+
+#[test]
+fn test_espenak_meeus_observed() {
+    // Arrange
+    // Act
+    // Assert
+    // Observed values of ΔT at the start of the year
+    assert_delta_t(1700, 9., 1.);
+    assert_delta_t(1800, 13.7, 0.5);
+    assert_delta_t(1900, -2.72, 0.1);
+    assert_delta_t(1950, 29.15, 0.1);
+    assert_delta_t(1980, 50.54, 0.1);
+    assert_delta_t(2000, 63.83, 0.1);
+}
+
+#[test]
+fn test_espenak_meeus_historical() {
+    // Arrange
+    // Act
+    // Assert
+    // Values of ΔT derived from historical eclipse records
+    assert_delta_t(-500, 17190., 20.);
+    assert_delta_t(0, 10580., 20.);
+    assert_delta_t(500, 5710., 20.);
+    assert_delta_t(1000, 1570., 10.);
+    assert_delta_t(1500, 200., 5.);
+    assert_delta_t(1600, 120., 5.);
+}
+
+#[test]
+fn test_espenak_meeus_extrapolated() {
+    // Arrange
+    // Act
+    // Assert
+    assert_delta_t(2100, 203., 5.);
+    assert_delta_t(-1000, 25400., 50.);
+}
+
+#[test]
+fn test_espenak_meeus_continuous() {
+    // Arrange
+    let years = [
+        1600, 1700, 1800, 1860, 1900, 1920, 1941, 1961, 1986, 2005, 2050, 2150,
+    ];
+    // Act
+    // Assert
+    for year in years {
+        let before =
+            DeltaT::EspenakMeeus.seconds(NaiveDate::from_ymd_opt(year - 1, 12, 1).unwrap());
+        let after = DeltaT::EspenakMeeus.seconds(NaiveDate::from_ymd_opt(year, 1, 1).unwrap());
+        assert!((after - before).abs() < 2., "ΔT jumps in {}", year);
+    }
+}
+
+#[test]
+fn test_override() {
+    // Arrange
+    let date = NaiveDate::from_ymd_opt(1024, 6, 1).unwrap();
+    // Act
+    // Assert
+    assert_eq!(1500., DeltaT::Seconds(1500.).seconds(date));
+    assert_eq!(0., DeltaT::None.seconds(date));
+    assert_eq!(DeltaT::EspenakMeeus, DeltaT::default());
+}
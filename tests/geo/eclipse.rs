@@ -0,0 +1,96 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use islamic_prayer_times::geo::eclipse::*;
+use islamic_prayer_times::DateRange;
+
+use crate::common::potomac_md;
+
+fn assert_within_minutes(expected: NaiveDateTime, actual: NaiveDateTime, minutes: i64) {
+    assert!(
+        (expected - actual).num_minutes().abs() <= minutes,
+        "expected {} but was {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_total_lunar_eclipse() {
+    // Arrange
+    let location = potomac_md();
+    let date = NaiveDate::from_ymd_opt(2022, 11, 8).unwrap();
+    let date_range = DateRange::from(date..=date);
+    // Act
+    let eclipses = eclipses_dt_rng(location, &date_range);
+    // Assert
+    assert_eq!(1, eclipses.len());
+    let eclipse = eclipses[0];
+    assert_eq!(EclipseKind::Lunar, eclipse.kind);
+    assert_eq!(EclipseType::Total, eclipse.eclipse_type);
+    assert!((eclipse.magnitude - 1.359).abs() < 0.01);
+    assert_within_minutes(date.and_hms_opt(4, 9, 0).unwrap(), eclipse.begin, 2);
+    assert_within_minutes(date.and_hms_opt(5, 59, 0).unwrap(), eclipse.maximum, 2);
+    assert_within_minutes(date.and_hms_opt(7, 49, 0).unwrap(), eclipse.end, 2);
+    assert!(eclipse.visible);
+}
+
+#[test]
+fn test_partial_solar_eclipse() {
+    // Arrange
+    let location = potomac_md();
+    let date = NaiveDate::from_ymd_opt(2024, 4, 8).unwrap();
+    let date_range = DateRange::from(date..=date);
+    // Act
+    let eclipses = eclipses_dt_rng(location, &date_range);
+    // Assert
+    assert_eq!(1, eclipses.len());
+    let eclipse = eclipses[0];
+    assert_eq!(EclipseKind::Solar, eclipse.kind);
+    assert_eq!(EclipseType::Partial, eclipse.eclipse_type);
+    assert!((eclipse.magnitude - 0.87).abs() < 0.03);
+    assert_within_minutes(date.and_hms_opt(13, 4, 0).unwrap(), eclipse.begin, 3);
+    assert_within_minutes(date.and_hms_opt(14, 20, 0).unwrap(), eclipse.maximum, 3);
+    assert_within_minutes(date.and_hms_opt(15, 33, 0).unwrap(), eclipse.end, 3);
+    assert!(eclipse.visible);
+}
+
+#[test]
+fn test_solar_eclipse_not_reaching_location() {
+    // Arrange
+    let location = potomac_md();
+    let date = NaiveDate::from_ymd_opt(2024, 10, 2).unwrap();
+    let date_range = DateRange::from(date..=date);
+    // Act
+    let eclipses = eclipses_dt_rng(location, &date_range);
+    // Assert
+    assert_eq!(1, eclipses.len());
+    let eclipse = eclipses[0];
+    assert_eq!(EclipseKind::Solar, eclipse.kind);
+    assert_eq!(EclipseType::Annular, eclipse.eclipse_type);
+    assert_eq!(0., eclipse.magnitude);
+    assert!(!eclipse.visible);
+}
+
+#[test]
+fn test_eclipses_for_year() {
+    use EclipseKind::*;
+    use EclipseType::*;
+
+    // Arrange
+    let location = potomac_md();
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+    let date_range = DateRange::from(start_date..=end_date);
+    // Act
+    let eclipses = eclipses_dt_rng(location, &date_range);
+    // Assert
+    let kinds: Vec<_> = eclipses.iter().map(|x| (x.kind, x.eclipse_type)).collect();
+    assert_eq!(
+        vec![
+            (Lunar, Penumbral),
+            (Solar, Partial),
+            (Lunar, Partial),
+            (Solar, Annular)
+        ],
+        kinds
+    );
+}
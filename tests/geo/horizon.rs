@@ -0,0 +1,60 @@
+use islamic_prayer_times::geo::horizon::*;
+
+#[test]
+fn test_from_csv() {
+    // Arrange
+    let csv = "# Valley\nazimuth, elevation\n\n270, 12.5\n0, 2.5\n90, 8\n180, 4\n";
+    // Act
+    let horizon_profile = HorizonProfile::from_csv(csv).unwrap();
+    // Assert
+    assert_eq!(2.5, horizon_profile.elevation_at(0.));
+    assert_eq!(5.25, horizon_profile.elevation_at(45.));
+    assert_eq!(8.25, horizon_profile.elevation_at(225.));
+    // Wrapping around North
+    assert_eq!(7.5, horizon_profile.elevation_at(315.));
+    assert_eq!(7.5, horizon_profile.elevation_at(-45.));
+}
+
+#[test]
+fn test_from_csv_single_point() {
+    // Arrange
+    let csv = "120,3";
+    // Act
+    let horizon_profile = HorizonProfile::from_csv(csv).unwrap();
+    // Assert
+    assert_eq!(3., horizon_profile.elevation_at(0.));
+    assert_eq!(3., horizon_profile.elevation_at(200.));
+}
+
+#[test]
+fn test_from_csv_invalid_line() {
+    // Arrange
+    let csv = "0,2.5\n90,eight\n";
+    // Act
+    let result = HorizonProfile::from_csv(csv);
+    // Assert
+    assert_eq!(
+        "Invalid horizon profile line 2: 90,eight",
+        result.unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_new_invalid_elevation() {
+    // Arrange
+    let points = vec![(0., 2.5), (90., 95.)];
+    // Act
+    let result = HorizonProfile::new(points);
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_new_empty() {
+    // Arrange
+    let points = vec![];
+    // Act
+    let result = HorizonProfile::new(points);
+    // Assert
+    assert!(result.is_err());
+}
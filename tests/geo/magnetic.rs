@@ -0,0 +1,51 @@
+use chrono::NaiveDate;
+use float_cmp::assert_approx_eq;
+use islamic_prayer_times::geo::{coordinates::*, magnetic::*};
+
+#[test]
+fn test_magnetic_declination_east() {
+    // Arrange
+    let coords = Coordinates::new(
+        Latitude::try_from(40.015).unwrap(),
+        Longitude::try_from(-105.27).unwrap(),
+        Elevation::try_from(1655.).unwrap(),
+    );
+    let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    // Act
+    let declination = magnetic_declination(coords, date).unwrap();
+    // Assert
+    assert_approx_eq!(f64, 7.804347, declination, epsilon = 0.000001);
+}
+
+#[test]
+fn test_magnetic_declination_southern_hemisphere() {
+    // Arrange
+    let coords = Coordinates::new(
+        Latitude::try_from(-33.87).unwrap(),
+        Longitude::try_from(151.21).unwrap(),
+        Elevation::default(),
+    );
+    let date = NaiveDate::from_ymd_opt(2026, 7, 1).unwrap();
+    // Act
+    let declination = magnetic_declination(coords, date).unwrap();
+    // Assert
+    assert_approx_eq!(f64, 12.821620, declination, epsilon = 0.000001);
+}
+
+#[test]
+fn test_magnetic_declination_outside_model() {
+    // Arrange
+    let coords = Coordinates::new(
+        Latitude::try_from(40.015).unwrap(),
+        Longitude::try_from(-105.27).unwrap(),
+        Elevation::try_from(1655.).unwrap(),
+    );
+    let before_date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+    let last_date = NaiveDate::from_ymd_opt(2029, 12, 31).unwrap();
+    let after_date = NaiveDate::from_ymd_opt(2030, 1, 1).unwrap();
+    // Act
+    // Assert
+    assert_eq!(None, magnetic_declination(coords, before_date));
+    assert!(magnetic_declination(coords, last_date).is_some());
+    assert_eq!(None, magnetic_declination(coords, after_date));
+}
@@ -1,3 +1,9 @@
+mod common;
 mod coordinates;
+mod delta_t;
+mod eclipse;
+mod horizon;
+mod magnetic;
 mod qibla;
+mod sun;
 mod weather;
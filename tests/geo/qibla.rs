@@ -1,5 +1,9 @@
+use chrono::{Duration, NaiveDate};
 use float_cmp::assert_approx_eq;
-use islamic_prayer_times::geo::{coordinates::*, qibla::*};
+use islamic_prayer_times::{
+    geo::{coordinates::*, qibla::*, sun::*},
+    DateRange,
+};
 
 #[test]
 fn test() {
@@ -16,3 +20,251 @@ fn test() {
     assert_eq!(coords, qibla.coords());
     assert_eq!("56.4° CW", qibla.to_string());
 }
+
+#[test]
+fn test_rasd_times_over_kaaba() {
+    // Arrange
+    let coords = Coordinates::new(
+        Latitude::try_from(39.0181651).unwrap(),
+        Longitude::try_from(-77.2085914).unwrap(),
+        Elevation::default(),
+    );
+    let gmt = Gmt::try_from(-5.).unwrap();
+    let date_range = DateRange::from(
+        NaiveDate::from_ymd_opt(2024, 5, 20).unwrap()
+            ..=NaiveDate::from_ymd_opt(2024, 5, 31).unwrap(),
+    );
+    // Act
+    let rasd_times = Qibla::new(coords).rasd_times(gmt, &date_range);
+    // Assert
+    let over_kaaba: Vec<&RasdQibla> = rasd_times
+        .iter()
+        .filter(|x| x.alignment == QiblaAlignment::OverKaaba)
+        .collect();
+    assert_eq!(1, over_kaaba.len());
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 5, 27)
+            .unwrap()
+            .and_hms_opt(4, 17, 55)
+            .unwrap(),
+        over_kaaba[0].date_time
+    );
+    assert!(over_kaaba[0].sun_altitude < 0.);
+}
+
+#[test]
+fn test_rasd_times_shadow() {
+    // Arrange
+    let coords = Coordinates::new(
+        Latitude::try_from(39.0181651).unwrap(),
+        Longitude::try_from(-77.2085914).unwrap(),
+        Elevation::default(),
+    );
+    let gmt = Gmt::try_from(-5.).unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+    let date_range = DateRange::from(date..=date);
+    // Act
+    let rasd_times = Qibla::new(coords).rasd_times(gmt, &date_range);
+    // Assert
+    assert_eq!(1, rasd_times.len());
+    assert_eq!(QiblaAlignment::Shadow, rasd_times[0].alignment);
+    assert_eq!(
+        date.and_hms_opt(15, 39, 27).unwrap(),
+        rasd_times[0].date_time
+    );
+    let instant = (rasd_times[0].date_time - Duration::hours(-5)).and_utc();
+    let sun_position = SunPosition::new(coords, instant, None);
+    assert_approx_eq!(
+        f64,
+        180. + 56.43742554,
+        sun_position.azimuth,
+        epsilon = 0.01
+    );
+}
+
+#[test]
+fn test_rasd_times_sun() {
+    // Arrange
+    let coords = Coordinates::new(
+        Latitude::try_from(3.139).unwrap(),
+        Longitude::try_from(101.69).unwrap(),
+        Elevation::default(),
+    );
+    let gmt = Gmt::try_from(8.).unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 4, 15).unwrap();
+    let date_range = DateRange::from(date..=date);
+    // Act
+    let rasd_times = Qibla::new(coords).rasd_times(gmt, &date_range);
+    // Assert
+    let sun: Vec<&RasdQibla> = rasd_times
+        .iter()
+        .filter(|x| x.alignment == QiblaAlignment::Sun)
+        .collect();
+    assert_eq!(1, sun.len());
+    assert_eq!(date.and_hms_opt(14, 22, 12).unwrap(), sun[0].date_time);
+    assert!(sun[0].sun_altitude > 0.);
+}
+
+#[test]
+fn test_geodesic() {
+    // Arrange
+    let coords = Coordinates::new(
+        Latitude::try_from(39.0181651).unwrap(),
+        Longitude::try_from(-77.2085914).unwrap(),
+        Elevation::default(),
+    );
+    // Act
+    let qibla = Qibla::new(coords);
+    // Assert
+    assert_approx_eq!(
+        f64,
+        -56.35110100,
+        qibla.geodesic_degrees(),
+        epsilon = 0.00000001
+    );
+    assert_approx_eq!(f64, 10655833.200, qibla.distance(), epsilon = 0.001);
+    assert_approx_eq!(
+        f64,
+        10637794.738,
+        qibla.great_circle_distance(),
+        epsilon = 0.001
+    );
+}
+
+#[test]
+fn test_geodesic_near_antipode() {
+    // Arrange
+    let coords = Coordinates::new(
+        Latitude::try_from(-21.4).unwrap(),
+        Longitude::try_from(-140.2).unwrap(),
+        Elevation::default(),
+    );
+    // Act
+    let qibla = Qibla::new(coords);
+    // Assert
+    assert!(qibla.geodesic_degrees().is_finite());
+    assert!(qibla.distance() > 19_900_000. && qibla.distance() < 20_004_000.);
+    assert!(qibla.great_circle_distance() > 19_900_000.);
+}
+
+#[test]
+fn test_azimuth() {
+    // Arrange
+    let coords = Coordinates::new(
+        Latitude::try_from(39.0181651).unwrap(),
+        Longitude::try_from(-77.2085914).unwrap(),
+        Elevation::default(),
+    );
+    let date = NaiveDate::from_ymd_opt(2026, 7, 1).unwrap();
+    // Act
+    let qibla = Qibla::new(coords);
+    // Assert
+    assert_approx_eq!(f64, 56.43742554, qibla.azimuth(), epsilon = 0.00000001);
+    assert_approx_eq!(
+        f64,
+        56.35110100,
+        qibla.geodesic_azimuth(),
+        epsilon = 0.00000001
+    );
+    assert_approx_eq!(
+        f64,
+        67.06084931,
+        qibla.magnetic_azimuth(date).unwrap(),
+        epsilon = 0.00000001
+    );
+}
+
+#[test]
+fn test_azimuth_southern_half() {
+    // Arrange
+    let coords = Coordinates::new(
+        Latitude::try_from(55.7558).unwrap(),
+        Longitude::try_from(37.6173).unwrap(),
+        Elevation::default(),
+    );
+    // Act
+    let qibla = Qibla::new(coords);
+    // Assert
+    assert_approx_eq!(f64, -176.36092188, qibla.degrees(), epsilon = 0.00000001);
+    assert_eq!(Rotation::Cw, qibla.rotation());
+    assert_approx_eq!(f64, 176.36092188, qibla.azimuth(), epsilon = 0.00000001);
+    assert_approx_eq!(
+        f64,
+        176.35023591,
+        qibla.geodesic_azimuth(),
+        epsilon = 0.00000001
+    );
+}
+
+#[test]
+fn test_path() {
+    // Arrange
+    let coords = Coordinates::new(
+        Latitude::try_from(39.0181651).unwrap(),
+        Longitude::try_from(-77.2085914).unwrap(),
+        Elevation::default(),
+    );
+    let qibla = Qibla::new(coords);
+    // Act
+    let qibla_path = qibla.path(100, 10_000.);
+    // Assert
+    assert_eq!(101, qibla_path.great_circle.len());
+    assert_eq!((39.0181651, -77.2085914), qibla_path.great_circle[0]);
+    assert_eq!((21.423333, 39.823333), qibla_path.great_circle[100]);
+    assert_approx_eq!(
+        f64,
+        qibla.great_circle_distance(),
+        qibla_path.distance,
+        epsilon = 0.000001
+    );
+    assert_approx_eq!(f64, qibla.azimuth(), qibla_path.azimuth, epsilon = 0.000001);
+    let (lat, lon) = qibla_path.bearing_line[1];
+    let bearing_line_coords = Coordinates::new(
+        Latitude::try_from(lat).unwrap(),
+        Longitude::try_from(lon).unwrap(),
+        Elevation::default(),
+    );
+    // The end of the bearing line lies on the great circle to the Kaaba.
+    assert_approx_eq!(
+        f64,
+        qibla.great_circle_distance() - 10_000.,
+        Qibla::new(bearing_line_coords).great_circle_distance(),
+        epsilon = 0.01
+    );
+}
+
+#[test]
+fn test_path_geojson_antimeridian() {
+    // Arrange
+    let coords = Coordinates::new(
+        Latitude::try_from(21.3).unwrap(),
+        Longitude::try_from(-157.8).unwrap(),
+        Elevation::default(),
+    );
+    let qibla_path = Qibla::new(coords).path(100, 10_000.);
+    // Act
+    let geojson = qibla_path.to_geojson();
+    // Assert
+    assert!(geojson.contains(r#""type":"FeatureCollection""#));
+    assert!(geojson.contains(r#""type":"MultiLineString""#));
+    assert!(geojson.contains("[180.0,"));
+    assert!(geojson.contains("[-180.0,"));
+}
+
+#[test]
+fn test_path_kml() {
+    // Arrange
+    let coords = Coordinates::new(
+        Latitude::try_from(39.0181651).unwrap(),
+        Longitude::try_from(-77.2085914).unwrap(),
+        Elevation::default(),
+    );
+    let qibla_path = Qibla::new(coords).path(2, 10_000.);
+    // Act
+    let kml = qibla_path.to_kml();
+    // Assert
+    assert!(kml.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+    assert!(kml.contains("<coordinates>-77.2085914,39.0181651 "));
+    assert!(kml.contains("<coordinates>39.823333,21.423333</coordinates>"));
+    assert!(kml.ends_with("</kml>\n"));
+}
@@ -0,0 +1,70 @@
+use chrono::{TimeZone, Utc};
+use float_cmp::assert_approx_eq;
+use islamic_prayer_times::geo::{sun::*, weather::*};
+
+use crate::common::potomac_md;
+
+#[test]
+fn test_sun_position_morning() {
+    // Arrange
+    let coords = potomac_md().coords;
+    let instant = Utc.with_ymd_and_hms(2023, 1, 25, 14, 0, 0).unwrap();
+    // Act
+    let sun_position = SunPosition::new(coords, instant, None);
+    // Assert
+    assert_approx_eq!(f64, 15.45, sun_position.altitude, epsilon = 0.02);
+    assert_approx_eq!(f64, 131.02, sun_position.azimuth, epsilon = 0.02);
+    assert_approx_eq!(
+        f64,
+        3.618,
+        sun_position.shadow_ratio.unwrap(),
+        epsilon = 0.01
+    );
+}
+
+#[test]
+fn test_sun_position_afternoon() {
+    // Arrange
+    let coords = potomac_md().coords;
+    let instant = Utc.with_ymd_and_hms(2023, 3, 20, 22, 0, 0).unwrap();
+    // Act
+    let sun_position = SunPosition::new(coords, instant, None);
+    // Assert
+    assert_approx_eq!(f64, 14.77, sun_position.altitude, epsilon = 0.02);
+    assert_approx_eq!(f64, 257.74, sun_position.azimuth, epsilon = 0.02);
+    assert_approx_eq!(
+        f64,
+        3.793,
+        sun_position.shadow_ratio.unwrap(),
+        epsilon = 0.01
+    );
+}
+
+#[test]
+fn test_sun_position_refraction_by_weather() {
+    // Arrange
+    let coords = potomac_md().coords;
+    let instant = Utc.with_ymd_and_hms(2023, 1, 25, 14, 0, 0).unwrap();
+    let weather = Weather {
+        pressure: Pressure::try_from(1010.).unwrap(),
+        temperature: Temperature::try_from(-30.).unwrap(),
+    };
+    // Act
+    let cold = SunPosition::new(coords, instant, Some(weather));
+    let default = SunPosition::new(coords, instant, None);
+    // Assert
+    assert!(cold.altitude > default.altitude);
+    assert_eq!(cold.azimuth, default.azimuth);
+}
+
+#[test]
+fn test_sun_position_at_night() {
+    // Arrange
+    let coords = potomac_md().coords;
+    let instant = Utc.with_ymd_and_hms(2023, 1, 25, 5, 0, 0).unwrap();
+    // Act
+    let sun_position = SunPosition::new(coords, instant, None);
+    // Assert
+    assert!(sun_position.altitude < 0.);
+    assert_eq!(None, sun_position.shadow_ratio);
+}
@@ -1,4 +1,7 @@
-use islamic_prayer_times::{Pressure, Temperature, Weather};
+use chrono::NaiveDate;
+use islamic_prayer_times::{
+    Elevation, Pressure, RefractionModel, Temperature, Weather, WeatherSeries,
+};
 
 #[test]
 fn should_new_pressure() {
@@ -72,3 +75,169 @@ fn test_default_weather() {
     assert_eq!(Pressure::try_from(1010.).unwrap(), weather.pressure);
     assert_eq!(Temperature::try_from(14.).unwrap(), weather.temperature);
 }
+
+#[test]
+fn test_standard_atmosphere_sea_level() {
+    // Arrange
+    // Act
+    let weather = Weather::standard_atmosphere(Elevation::default());
+    // Assert
+    assert_eq!(Pressure::try_from(1013.25).unwrap(), weather.pressure);
+    assert_eq!(Temperature::try_from(15.).unwrap(), weather.temperature);
+}
+
+#[test]
+fn test_standard_atmosphere_below_sea_level() {
+    // Arrange
+    // Act
+    let weather = Weather::standard_atmosphere(Elevation::try_from(-420.).unwrap());
+    // Assert
+    assert_eq!(Pressure::try_from(1050.).unwrap(), weather.pressure);
+    assert!((f64::from(weather.temperature) - 17.73).abs() < 0.001);
+}
+
+#[test]
+fn test_standard_atmosphere_everest() {
+    // Arrange
+    // Act
+    let weather = Weather::standard_atmosphere(Elevation::try_from(8848.).unwrap());
+    // Assert
+    assert!((f64::from(weather.pressure) - 314.).abs() < 0.5);
+    assert!((f64::from(weather.temperature) + 42.512).abs() < 0.001);
+}
+
+#[test]
+fn test_refraction_models_below_horizon() {
+    // Arrange
+    let weather = Weather::default();
+    // Act
+    // Assert
+    assert_eq!(0., RefractionModel::Saemundsson.degrees(-2.5, weather));
+    assert_eq!(0., RefractionModel::Bennett.degrees(-2.5, weather));
+    assert_eq!(34. / 60., RefractionModel::Standard.degrees(-2.5, weather));
+}
+
+#[test]
+fn test_refraction_models_at_zenith() {
+    // Arrange
+    let weather = Weather::default();
+    // Act
+    // Assert
+    assert!(RefractionModel::Saemundsson.degrees(90., weather).abs() < 1e-6);
+    assert!(RefractionModel::Bennett.degrees(90., weather).abs() < 1e-6);
+}
+
+#[test]
+fn test_refraction_scaled_by_weather() {
+    // Arrange
+    let cold = Weather {
+        pressure: Pressure::try_from(1010.).unwrap(),
+        temperature: Temperature::try_from(-30.).unwrap(),
+    };
+    // Act
+    let default_refraction = RefractionModel::Saemundsson.degrees(0., Weather::default());
+    let cold_refraction = RefractionModel::Saemundsson.degrees(0., cold);
+    // Assert
+    assert!((cold_refraction / default_refraction - 287. / 243.).abs() < 1e-12);
+    assert_eq!(
+        RefractionModel::Standard.degrees(0., Weather::default()),
+        RefractionModel::Standard.degrees(0., cold)
+    );
+}
+
+#[test]
+fn test_weather_series_from_csv_daily() {
+    // Arrange
+    let csv =
+        "# Daily\ndate,pressure,temperature\n2024-01-01, 1021.3, -2.5\n\n2024-01-02,1016.8,1.2\n";
+    // Act
+    let weather_series = WeatherSeries::from_csv(csv).unwrap();
+    // Assert
+    let weather = weather_series
+        .weather_on(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+        .unwrap();
+    assert_eq!(Pressure::try_from(1021.3).unwrap(), weather.pressure);
+    assert_eq!(Temperature::try_from(-2.5).unwrap(), weather.temperature);
+    assert_eq!(
+        None,
+        weather_series.weather_on(NaiveDate::from_ymd_opt(2024, 1, 3).unwrap())
+    );
+}
+
+#[test]
+fn test_weather_series_from_csv_monthly() {
+    // Arrange
+    let csv = (1..=12)
+        .rev()
+        .map(|month| format!("{},1010,{}\n", month, month * 2))
+        .collect::<String>();
+    // Act
+    let weather_series = WeatherSeries::from_csv(&csv).unwrap();
+    // Assert
+    let weather = weather_series
+        .weather_on(NaiveDate::from_ymd_opt(2031, 3, 15).unwrap())
+        .unwrap();
+    assert_eq!(Temperature::try_from(6.).unwrap(), weather.temperature);
+}
+
+#[test]
+fn test_weather_series_from_csv_missing_months() {
+    // Arrange
+    let csv = "1,1010,0\n2,1010,2\n";
+    // Act
+    let result = WeatherSeries::from_csv(csv);
+    // Assert
+    assert_eq!(
+        "The monthly weather series does not have all 12 months",
+        result.unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_weather_series_from_csv_invalid_line() {
+    // Arrange
+    let csv = "2024-01-01,1021.3,-2.5\n2024-01-02,2000,1.2\n";
+    // Act
+    let result = WeatherSeries::from_csv(csv);
+    // Assert
+    assert_eq!(
+        "Invalid weather series line 2: 2024-01-02,2000,1.2",
+        result.unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_weather_series_from_csv_invalid_first_line() {
+    // Arrange
+    let csv = "2024-01-01,1200,15\n2024-01-02,1016.8,1.2\n";
+    // Act
+    let result = WeatherSeries::from_csv(csv);
+    // Assert
+    assert_eq!(
+        "Invalid weather series line 1: 2024-01-01,1200,15",
+        result.unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_weather_series_from_csv_semicolon_first_line() {
+    // Arrange
+    let csv = "2024-01-01;1021.3;-2.5\n2024-01-02,1016.8,1.2\n";
+    // Act
+    let result = WeatherSeries::from_csv(csv);
+    // Assert
+    assert_eq!(
+        "Invalid weather series line 1: 2024-01-01;1021.3;-2.5",
+        result.unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_weather_series_from_csv_mixed() {
+    // Arrange
+    let csv = "2024-01-01,1021.3,-2.5\n1,1010,0\n";
+    // Act
+    let result = WeatherSeries::from_csv(csv);
+    // Assert
+    assert!(result.is_err());
+}
@@ -0,0 +1,51 @@
+use chrono::NaiveDate;
+use islamic_prayer_times::{
+    Coordinates, DateRange, Elevation, Gmt, Latitude, Location, Longitude, Method, Params,
+    RoundSeconds,
+};
+
+pub fn location(latitude: f64, longitude: f64, elevation: f64, gmt: f64) -> Location {
+    let latitude = Latitude::try_from(latitude).unwrap();
+    let longitude = Longitude::try_from(longitude).unwrap();
+    let elevation = Elevation::try_from(elevation).unwrap();
+    let coords = Coordinates::new(latitude, longitude, elevation);
+    let gmt = Gmt::try_from(gmt).unwrap();
+    Location { coords, gmt }
+}
+
+pub fn juneau_ak() -> Location {
+    location(58.3019444, -134.4197222, 87., -9.)
+}
+
+pub fn potomac_md(elevation: f64) -> Location {
+    location(39.0181651, -77.2085914, elevation, -5.)
+}
+
+pub fn cairo() -> Location {
+    location(30.0444, 31.2357, 23., 2.)
+}
+
+pub fn oslo() -> Location {
+    location(59.91, 10.75, 0., 2.)
+}
+
+pub fn tromso() -> Location {
+    location(69.6496, 18.956, 0., 1.)
+}
+
+pub fn svalbard() -> Location {
+    location(78.2232, 15.6267, 0., 1.)
+}
+
+pub fn year_2023() -> DateRange {
+    let start_date = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+    DateRange::from(start_date..=end_date)
+}
+
+pub fn unrounded_params(method: Method) -> Params {
+    Params {
+        round_seconds: RoundSeconds::None,
+        ..Params::new(method)
+    }
+}
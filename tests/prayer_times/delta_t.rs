@@ -0,0 +1,77 @@
+use chrono::NaiveDate;
+use islamic_prayer_times::{
+    prayer_times_dt, solar_day, DeltaT, Method, Params, Prayer, RoundSeconds,
+};
+
+use crate::common::{cairo, unrounded_params};
+
+#[test]
+fn test_historical_delta_t() {
+    use Prayer::*;
+
+    // Arrange
+    let location = cairo();
+    let date = NaiveDate::from_ymd_opt(1024, 3, 15).unwrap();
+    let params = Params {
+        delta_t: DeltaT::EspenakMeeus,
+        ..unrounded_params(Method::Egyptian)
+    };
+    let override_params = Params {
+        delta_t: DeltaT::Seconds(DeltaT::EspenakMeeus.seconds(date)),
+        ..params.clone()
+    };
+    let ut_params = Params {
+        delta_t: DeltaT::None,
+        ..params.clone()
+    };
+    // Act
+    let pts = prayer_times_dt(&params, location, date, None);
+    let override_pts = prayer_times_dt(&override_params, location, date, None);
+    let ut_pts = prayer_times_dt(&ut_params, location, date, None);
+    // Assert
+    assert_eq!(pts, override_pts);
+    // The Sun is about 26 minutes further along its path in TT, so Dhuhr is about 4 seconds later.
+    let dhuhr_diff = (pts[&Dhuhr].unwrap().time - ut_pts[&Dhuhr].unwrap().time).num_seconds();
+    assert!((3..=6).contains(&dhuhr_diff));
+}
+
+#[test]
+fn test_historical_delta_t_solar_day() {
+    // Arrange
+    let location = cairo();
+    let date = NaiveDate::from_ymd_opt(1024, 3, 15).unwrap();
+    let params = Params {
+        delta_t: DeltaT::EspenakMeeus,
+        ..unrounded_params(Method::Egyptian)
+    };
+    let ut_params = Params {
+        delta_t: DeltaT::None,
+        ..params.clone()
+    };
+    // Act
+    let solar_day_tt = solar_day(&params, location, date, None);
+    let solar_day_ut = solar_day(&ut_params, location, date, None);
+    // Assert
+    let noon_diff = (solar_day_tt.solar_noon - solar_day_ut.solar_noon).num_seconds();
+    assert!((3..=6).contains(&noon_diff));
+}
+
+#[test]
+fn test_modern_delta_t() {
+    // Arrange
+    let location = cairo();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let rounded_params = Params {
+        round_seconds: RoundSeconds::SpecialRounding,
+        delta_t: DeltaT::EspenakMeeus,
+        ..Params::new(Method::Egyptian)
+    };
+    let mut rounded_ut_params = rounded_params.clone();
+    rounded_ut_params.delta_t = DeltaT::None;
+    // Act
+    let pts = prayer_times_dt(&rounded_params, location, date, None);
+    let ut_pts = prayer_times_dt(&rounded_ut_params, location, date, None);
+    // Assert
+    // ΔT of about a minute moves the Sun too little to change a prayer time to the minute.
+    assert_eq!(pts, ut_pts);
+}
@@ -0,0 +1,50 @@
+use chrono::NaiveDate;
+use islamic_prayer_times::{prayer_times_dt_rng, DateRange, Ephemeris, Method, Params};
+
+use crate::common::{location, unrounded_params};
+
+#[test]
+fn test_low_precision_within_30_seconds() {
+    // Arrange
+    let locations = [
+        location(21.4225, 39.8262, 0., 3.),
+        location(30.0444, 31.2357, 0., 2.),
+        location(-33.8688, 151.2093, 0., 10.),
+        location(39.0181651, -77.2085914, 0., -5.),
+        location(48.8566, 2.3522, 0., 1.),
+    ];
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+    let date_range = DateRange::from(start_date..=end_date);
+    let params = Params {
+        ephemeris: Ephemeris::Vsop87,
+        ..unrounded_params(Method::Mwl)
+    };
+    let low_params = Params {
+        ephemeris: Ephemeris::LowPrecision,
+        ..params.clone()
+    };
+    // Act
+    // Assert
+    for location in locations {
+        let pts_by_day = prayer_times_dt_rng(&params, location, &date_range);
+        let low_pts_by_day = prayer_times_dt_rng(&low_params, location, &date_range);
+        for (date, pts) in pts_by_day {
+            for (prayer, pt) in pts {
+                let low_pt = low_pts_by_day[&date][&prayer];
+                assert_eq!(pt.is_ok(), low_pt.is_ok());
+                if let (Ok(pt), Ok(low_pt)) = (pt, low_pt) {
+                    let diff = (pt.time - low_pt.time).num_seconds().abs();
+                    assert!(
+                        diff <= 30,
+                        "{} on {} differs by {} seconds at {:?}",
+                        prayer,
+                        date,
+                        diff,
+                        location.coords
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use chrono::{NaiveDate, NaiveTime};
+use islamic_prayer_times::{
+    fasting_times_dt, fasting_times_dt_rng, prayer_times_dt, Coordinates, DateRange, Elevation,
+    FastingCap, Latitude, Longitude, Method, Params, Prayer,
+};
+
+use crate::common::{location, oslo};
+
+#[test]
+fn test_fasting_no_cap() {
+    // Arrange
+    let params = Params::new(Method::Isna);
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+
+    // Act
+    let fasting_times = fasting_times_dt(&params, oslo(), date, None).unwrap();
+    let pts = prayer_times_dt(&params, oslo(), date, None);
+
+    // Assert
+    assert_eq!(pts[&Prayer::Imsaak].unwrap().time, fasting_times.imsaak);
+    assert_eq!(pts[&Prayer::Maghrib].unwrap().time, fasting_times.iftar);
+    let secs = (fasting_times.iftar - fasting_times.imsaak).num_seconds();
+    assert_eq!(secs as f64 / 3600., fasting_times.hours);
+    assert!((fasting_times.hours - (21. + 5. / 60.)).abs() < 1e-9);
+    assert_eq!(false, fasting_times.capped);
+}
+
+#[test]
+fn test_fasting_cap_hours() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.fasting_cap = FastingCap::Hours(18.);
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+
+    // Act
+    let fasting_times = fasting_times_dt(&params, oslo(), date, None).unwrap();
+    let pts = prayer_times_dt(&params, oslo(), date, None);
+
+    // Assert
+    assert_eq!(
+        NaiveTime::from_hms_opt(3, 20, 0).unwrap(),
+        fasting_times.imsaak
+    );
+    assert_eq!(
+        NaiveTime::from_hms_opt(21, 22, 0).unwrap(),
+        fasting_times.iftar
+    );
+    assert!((fasting_times.hours - (18. + 2. / 60.)).abs() < 1e-9);
+    assert_eq!(true, fasting_times.capped);
+    assert_eq!(
+        NaiveTime::from_hms_opt(1, 39, 0).unwrap(),
+        pts[&Prayer::Imsaak].unwrap().time
+    );
+    assert_eq!(
+        NaiveTime::from_hms_opt(22, 44, 0).unwrap(),
+        pts[&Prayer::Maghrib].unwrap().time
+    );
+}
+
+#[test]
+fn test_fasting_cap_mecca() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.fasting_cap = FastingCap::Mecca(18.);
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+
+    // Act
+    let fasting_times = fasting_times_dt(&params, oslo(), date, None).unwrap();
+
+    // Assert
+    assert_eq!(
+        NaiveTime::from_hms_opt(5, 9, 0).unwrap(),
+        fasting_times.imsaak
+    );
+    assert_eq!(
+        NaiveTime::from_hms_opt(19, 54, 0).unwrap(),
+        fasting_times.iftar
+    );
+    assert!((fasting_times.hours - (14. + 45. / 60.)).abs() < 1e-9);
+    assert_eq!(true, fasting_times.capped);
+}
+
+#[test]
+fn test_fasting_cap_reference() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    let latitude = Latitude::try_from(41.01).unwrap();
+    let longitude = Longitude::try_from(28.98).unwrap();
+    let elevation = Elevation::try_from(0.).unwrap();
+    params.fasting_cap =
+        FastingCap::Reference(Coordinates::new(latitude, longitude, elevation), 18.);
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+
+    // Act
+    let fasting_times = fasting_times_dt(&params, oslo(), date, None).unwrap();
+
+    // Assert
+    assert_eq!(
+        NaiveTime::from_hms_opt(3, 53, 0).unwrap(),
+        fasting_times.imsaak
+    );
+    assert_eq!(
+        NaiveTime::from_hms_opt(20, 56, 0).unwrap(),
+        fasting_times.iftar
+    );
+    assert!((fasting_times.hours - (17. + 3. / 60.)).abs() < 1e-9);
+    assert_eq!(true, fasting_times.capped);
+}
+
+#[test]
+fn test_fasting_cap_winter_uncapped() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.fasting_cap = FastingCap::Mecca(18.);
+    let start_date = NaiveDate::from_ymd_opt(2023, 12, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+    let date_range = DateRange::from(start_date..=end_date);
+
+    // Act
+    let fasting_times = fasting_times_dt_rng(&params, oslo(), &date_range, None);
+
+    // Assert
+    assert_eq!(31, fasting_times.len());
+    for fasting_time in fasting_times.values() {
+        assert_eq!(false, fasting_time.unwrap().capped);
+    }
+}
+
+#[test]
+fn test_fasting_cap_below_threshold_uncapped() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.fasting_cap = FastingCap::Mecca(18.);
+    let london = location(51.51, -0.13, 0., 1.);
+    let date = NaiveDate::from_ymd_opt(2023, 4, 10).unwrap();
+
+    // Act
+    let fasting_times = fasting_times_dt(&params, london, date, None).unwrap();
+    let pts = prayer_times_dt(&params, london, date, None);
+
+    // Assert
+    // The fast is longer than that in Mecca, but not longer than the threshold.
+    assert!(fasting_times.hours > 14. && fasting_times.hours < 18.);
+    assert!(!fasting_times.capped);
+    assert_eq!(pts[&Prayer::Imsaak].unwrap().time, fasting_times.imsaak);
+    assert_eq!(pts[&Prayer::Maghrib].unwrap().time, fasting_times.iftar);
+}
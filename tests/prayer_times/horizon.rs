@@ -0,0 +1,147 @@
+use chrono::{NaiveDate, NaiveTime};
+use islamic_prayer_times::{
+    prayer_times_dt, solar_day, visible_sunrise_sunset, CrossingError, Elevation, HorizonDip,
+    HorizonProfile, Method, Params, Prayer, RefractionModel, RoundSeconds,
+};
+
+use crate::common::{location, potomac_md};
+
+#[test]
+fn test_horizon_dip_earlier_shurooq_later_maghrib() {
+    // Arrange
+    let location = potomac_md(1000.);
+    let date = NaiveDate::from_ymd_opt(2023, 3, 20).unwrap();
+    let mut params = Params::new(Method::Isna);
+    params.round_seconds = RoundSeconds::None;
+    let mut dip_params = params.clone();
+    dip_params.horizon_dip = HorizonDip::TerrainHeight(Elevation::default());
+    // Act
+    let pts = prayer_times_dt(&params, location, date, None);
+    let dip_pts = prayer_times_dt(&dip_params, location, date, None);
+    // Assert
+    use Prayer::*;
+    let shurooq_diff = pts[&Shurooq].unwrap().time - dip_pts[&Shurooq].unwrap().time;
+    let maghrib_diff = dip_pts[&Maghrib].unwrap().time - pts[&Maghrib].unwrap().time;
+    // A dip of 0.93 degrees is about 4.8 minutes at the equinox at this latitude.
+    assert!(shurooq_diff.num_seconds() > 280 && shurooq_diff.num_seconds() < 300);
+    assert!(maghrib_diff.num_seconds() > 280 && maghrib_diff.num_seconds() < 300);
+    for prayer in [Fajr, Dhuhr, Asr, Isha] {
+        assert_eq!(pts[&prayer], dip_pts[&prayer]);
+    }
+}
+
+#[test]
+fn test_horizon_dip_at_terrain_height() {
+    // Arrange
+    let location = potomac_md(1000.);
+    let date = NaiveDate::from_ymd_opt(2023, 3, 20).unwrap();
+    let params = Params::new(Method::Isna);
+    let mut dip_params = params.clone();
+    dip_params.horizon_dip = HorizonDip::TerrainHeight(Elevation::try_from(1200.).unwrap());
+    // Act
+    let pts = prayer_times_dt(&params, location, date, None);
+    let dip_pts = prayer_times_dt(&dip_params, location, date, None);
+    // Assert
+    assert_eq!(pts, dip_pts);
+}
+
+#[test]
+fn test_visible_sunrise_sunset_flat_horizon() {
+    // Arrange
+    let location = potomac_md(0.);
+    let date = NaiveDate::from_ymd_opt(2023, 3, 20).unwrap();
+    let horizon_profile = HorizonProfile::from_csv("0,0").unwrap();
+    // Act
+    let sunrise_sunset =
+        visible_sunrise_sunset(&Params::default(), location, date, &horizon_profile, None);
+    // Assert
+    let rise_diff = sunrise_sunset.sunrise.unwrap() - sunrise_sunset.visible_sunrise.unwrap();
+    let set_diff = sunrise_sunset.visible_sunset.unwrap() - sunrise_sunset.sunset.unwrap();
+    assert!(rise_diff.num_seconds().abs() <= 30);
+    assert!(set_diff.num_seconds().abs() <= 30);
+}
+
+#[test]
+fn test_visible_sunrise_sunset_valley() {
+    // Arrange
+    let location = potomac_md(0.);
+    let date = NaiveDate::from_ymd_opt(2023, 3, 20).unwrap();
+    let horizon_profile = HorizonProfile::from_csv("90,10\n180,30\n270,5").unwrap();
+    // Act
+    let sunrise_sunset =
+        visible_sunrise_sunset(&Params::default(), location, date, &horizon_profile, None);
+    // Assert
+    assert_eq!(
+        NaiveTime::from_hms_opt(7, 18, 21).unwrap(),
+        sunrise_sunset.visible_sunrise.unwrap()
+    );
+    assert_eq!(
+        NaiveTime::from_hms_opt(17, 45, 44).unwrap(),
+        sunrise_sunset.visible_sunset.unwrap()
+    );
+    assert_eq!(
+        NaiveTime::from_hms_opt(6, 12, 34).unwrap(),
+        sunrise_sunset.sunrise.unwrap()
+    );
+}
+
+#[test]
+fn test_visible_sunrise_sunset_always_below() {
+    // Arrange
+    let location = potomac_md(0.);
+    let date = NaiveDate::from_ymd_opt(2023, 12, 21).unwrap();
+    let horizon_profile = HorizonProfile::from_csv("0,30").unwrap();
+    // Act
+    let sunrise_sunset =
+        visible_sunrise_sunset(&Params::default(), location, date, &horizon_profile, None);
+    // Assert
+    assert!(sunrise_sunset.sunrise.is_ok());
+    assert_eq!(
+        Err(CrossingError::AlwaysBelow),
+        sunrise_sunset.visible_sunrise
+    );
+    assert_eq!(
+        Err(CrossingError::AlwaysBelow),
+        sunrise_sunset.visible_sunset
+    );
+}
+
+#[test]
+fn test_visible_sunset_not_on_day() {
+    // Arrange
+    let location = location(69.6492, 18.9553, 0., 2.);
+    let date = NaiveDate::from_ymd_opt(2023, 5, 16).unwrap();
+    let horizon_profile = HorizonProfile::from_csv("0,0").unwrap();
+    // Act
+    let sunrise_sunset =
+        visible_sunrise_sunset(&Params::default(), location, date, &horizon_profile, None);
+    // Assert
+    // The visible sunset moves past midnight to the next day.
+    assert_eq!(
+        NaiveTime::from_hms_opt(1, 32, 30).unwrap(),
+        sunrise_sunset.visible_sunrise.unwrap()
+    );
+    assert_eq!(Err(CrossingError::NotOnDay), sunrise_sunset.visible_sunset);
+}
+
+#[test]
+fn test_visible_sunrise_sunset_no_refraction() {
+    // Arrange
+    let location = potomac_md(0.);
+    let date = NaiveDate::from_ymd_opt(2023, 2, 6).unwrap();
+    let horizon_profile = HorizonProfile::from_csv("0,0").unwrap();
+    let params = Params {
+        refraction_model: RefractionModel::None,
+        ..Default::default()
+    };
+    // Act
+    let sunrise_sunset = visible_sunrise_sunset(&params, location, date, &horizon_profile, None);
+    let solar_day = solar_day(&params, location, date, None);
+    // Assert
+    let rise_diff = sunrise_sunset.sunrise.unwrap() - sunrise_sunset.visible_sunrise.unwrap();
+    let set_diff = sunrise_sunset.visible_sunset.unwrap() - sunrise_sunset.sunset.unwrap();
+    assert!(rise_diff.num_seconds().abs() <= 1);
+    assert!(set_diff.num_seconds().abs() <= 1);
+    let airless_alt = 90. - (39.0181651 - solar_day.declination).abs();
+    assert!((solar_day.max_altitude - airless_alt).abs() < 1e-9);
+}
@@ -1,7 +1,7 @@
 use chrono::{NaiveDate, NaiveTime};
 use islamic_prayer_times::{
-    prayer_times_dt, Coordinates, Elevation, ExtremeLatitudeMethod, Gmt, Latitude, Location,
-    Longitude, Method, Params, Prayer, NEAREST_LATITUDE,
+    prayer_times_dt, prayer_times_dt_rng, Coordinates, DateRange, Elevation, ExtremeLatitudeMethod,
+    Gmt, Latitude, Location, Longitude, Method, Params, Prayer, NEAREST_LATITUDE,
 };
 
 #[test]
@@ -541,3 +541,27 @@ fn test_juneau_ak_default_params_mins_from_fajr_isha_inv() {
     assert_eq!(NaiveTime::from_hms_opt(21, 12, 0).unwrap(), isha.time);
     assert_eq!(true, isha.extreme);
 }
+
+#[test]
+fn test_juneau_ak_default_params_range() {
+    // Arrange
+    let params = Params::new(Method::Isna); // NearestGoodDayFajrIshaInvalid
+    let latitude = Latitude::try_from(58.3019444).unwrap();
+    let longitude = Longitude::try_from(-134.4197222).unwrap();
+    let elevation = Elevation::try_from(87.).unwrap();
+    let coords = Coordinates::new(latitude, longitude, elevation);
+    let gmt = Gmt::try_from(-9.).unwrap();
+    let location = Location { coords, gmt };
+    let start_date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+    let date_range = DateRange::from(start_date..=end_date);
+
+    // Act
+    let pts_by_day = prayer_times_dt_rng(&params, location, &date_range);
+
+    // Assert
+    assert_eq!(730, pts_by_day.len());
+    for (date, pts) in pts_by_day {
+        assert_eq!(prayer_times_dt(&params, location, date, None), pts);
+    }
+}
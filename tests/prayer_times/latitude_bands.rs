@@ -0,0 +1,136 @@
+use chrono::NaiveDate;
+use islamic_prayer_times::{
+    prayer_times_dt, prayer_times_dt_rng, DateRange, ExtremeLatitudeMethod, LatitudeBand,
+    LatitudeBandCondition, Method, Params, Prayer, Provenance, Reference, NEAREST_LATITUDE,
+};
+
+use crate::common::{location, tromso};
+
+fn tiered_params() -> Params {
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::LatitudeBands;
+    params.latitude_bands = vec![
+        LatitudeBand {
+            min_latitude: 60.,
+            method: ExtremeLatitudeMethod::NearestLatitudeAllPrayersAlways(NEAREST_LATITUDE),
+            condition: LatitudeBandCondition::AnyInvalid,
+        },
+        LatitudeBand {
+            min_latitude: 48.5,
+            method: ExtremeLatitudeMethod::SeventhOfNightFajrIshaInvalid,
+            condition: LatitudeBandCondition::FajrIshaInvalid,
+        },
+    ];
+    params
+}
+
+#[test]
+fn test_latitude_bands_middle_band() {
+    // Arrange
+    let params = tiered_params();
+    let location = location(58.3019444, -134.4197222, 0., -9.);
+    let summer = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+    let winter = NaiveDate::from_ymd_opt(2022, 1, 6).unwrap();
+
+    // Act
+    let pts_summer = prayer_times_dt(&params, location, summer, None);
+    let pts_winter = prayer_times_dt(&params, location, winter, None);
+
+    // Assert
+    let expected = Some(Provenance {
+        method: ExtremeLatitudeMethod::SeventhOfNightFajrIshaInvalid,
+        reference: Reference::NightFraction(1. / 7.),
+    });
+    assert_eq!(expected, pts_summer[&Prayer::Fajr].unwrap().provenance);
+    assert_eq!(expected, pts_summer[&Prayer::Isha].unwrap().provenance);
+    assert_eq!(None, pts_summer[&Prayer::Maghrib].unwrap().provenance);
+    for pt in pts_winter.values() {
+        assert_eq!(None, pt.unwrap().provenance);
+    }
+}
+
+#[test]
+fn test_latitude_bands_upper_band() {
+    // Arrange
+    let params = tiered_params();
+    let location = tromso();
+    let polar_day = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+    let polar_night = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+    let equinox = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+
+    // Act
+    let pts_polar_day = prayer_times_dt(&params, location, polar_day, None);
+    let pts_polar_night = prayer_times_dt(&params, location, polar_night, None);
+    let pts_equinox = prayer_times_dt(&params, location, equinox, None);
+
+    // Assert
+    let expected = Some(Provenance {
+        method: ExtremeLatitudeMethod::NearestLatitudeAllPrayersAlways(NEAREST_LATITUDE),
+        reference: Reference::Latitude(NEAREST_LATITUDE),
+    });
+    for pt in pts_polar_day.values().chain(pts_polar_night.values()) {
+        assert_eq!(expected, pt.unwrap().provenance);
+    }
+    for pt in pts_equinox.values() {
+        assert_eq!(None, pt.unwrap().provenance);
+    }
+}
+
+#[test]
+fn test_latitude_bands_southern_hemisphere() {
+    // Arrange
+    let params = tiered_params();
+    let location = location(-54.8, -68.3, 0., -3.);
+    let date = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, location, date, None);
+
+    // Assert
+    let expected = Some(Provenance {
+        method: ExtremeLatitudeMethod::SeventhOfNightFajrIshaInvalid,
+        reference: Reference::NightFraction(1. / 7.),
+    });
+    assert_eq!(expected, pts[&Prayer::Fajr].unwrap().provenance);
+    assert_eq!(expected, pts[&Prayer::Isha].unwrap().provenance);
+}
+
+#[test]
+fn test_latitude_bands_single_band_equals_method() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::LatitudeBands;
+    params.latitude_bands = vec![LatitudeBand {
+        min_latitude: 0.,
+        method: ExtremeLatitudeMethod::NearestGoodDayFajrIshaInvalid,
+        condition: LatitudeBandCondition::Always,
+    }];
+    let params_method = Params::new(Method::Isna); // NearestGoodDayFajrIshaInvalid
+    let location = location(58.3019444, -134.4197222, 0., -9.);
+    let start_date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+    let date_range = DateRange::from(start_date..=end_date);
+
+    // Act
+    let pts = prayer_times_dt_rng(&params, location, &date_range);
+    let pts_method = prayer_times_dt_rng(&params_method, location, &date_range);
+
+    // Assert
+    assert_eq!(pts_method, pts);
+}
+
+#[test]
+fn test_latitude_bands_empty() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::LatitudeBands;
+    let location = location(58.3019444, -134.4197222, 0., -9.);
+    let date = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, location, date, None);
+
+    // Assert
+    assert!(pts[&Prayer::Fajr].is_err());
+    assert!(pts[&Prayer::Isha].is_err());
+}
@@ -1,5 +1,26 @@
+mod common;
+#[allow(clippy::needless_range_loop)]
 mod date;
+mod delta_t;
+mod ephemeris;
+mod fasting;
+mod horizon;
+#[allow(clippy::bool_assert_comparison)]
 mod juneau_ak;
+mod latitude_bands;
+mod nearest_good_day;
+mod nearest_latitude;
 mod params;
+mod polar;
+#[allow(clippy::bool_assert_comparison)]
 mod potomac_md;
+mod provenance;
+mod refraction;
+mod rules;
+mod season;
+mod smoothing;
+mod solar;
+mod strategy;
+mod weather_series;
+#[allow(clippy::bool_assert_comparison)]
 mod world;
@@ -0,0 +1,112 @@
+use chrono::NaiveDate;
+use islamic_prayer_times::{
+    prayer_times_dt, prayer_times_dt_rng, DateRange, Method, NearestGoodDaySearch, Params, Prayer,
+    Reference, SearchDirection,
+};
+
+use crate::common::juneau_ak;
+
+fn good_day(params: &Params, date: NaiveDate) -> Result<Reference, ()> {
+    prayer_times_dt(params, juneau_ak(), date, None)[&Prayer::Fajr]
+        .map(|x| x.provenance.unwrap().reference)
+}
+
+#[test]
+fn test_near_good_direction() {
+    // Arrange
+    let date = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+    let before = Reference::Date(NaiveDate::from_ymd_opt(2022, 5, 6).unwrap());
+    let after = Reference::Date(NaiveDate::from_ymd_opt(2022, 8, 6).unwrap());
+    let cases = [
+        (SearchDirection::Nearest, after),
+        (SearchDirection::Earlier, before),
+        (SearchDirection::Later, after),
+    ];
+
+    for (direction, expected) in cases {
+        let mut params = Params::new(Method::Isna);
+        params.nearest_good_day.direction = direction;
+
+        // Act
+        let reference = good_day(&params, date);
+
+        // Assert
+        assert_eq!(Ok(expected), reference);
+    }
+}
+
+#[test]
+fn test_near_good_limits() {
+    // Arrange
+    let date = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+    let mut params_after = Params::new(Method::Isna);
+    params_after.nearest_good_day.max_days_after = 20;
+    let mut params_both = Params::new(Method::Isna);
+    params_both.nearest_good_day = NearestGoodDaySearch {
+        max_days_before: 30,
+        max_days_after: 20,
+        direction: SearchDirection::Nearest,
+    };
+
+    // Act
+    let reference_after = good_day(&params_after, date);
+    let reference_both = good_day(&params_both, date);
+
+    // Assert
+    assert_eq!(
+        Ok(Reference::Date(
+            NaiveDate::from_ymd_opt(2022, 5, 6).unwrap()
+        )),
+        reference_after
+    );
+    assert_eq!(Err(()), reference_both);
+}
+
+#[test]
+fn test_near_good_range_equals_dates() {
+    // Arrange
+    let start_date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+    let date_range = DateRange::from(start_date..=end_date);
+
+    for direction in [
+        SearchDirection::Nearest,
+        SearchDirection::Earlier,
+        SearchDirection::Later,
+    ] {
+        let mut params = Params::new(Method::Isna);
+        params.nearest_good_day.direction = direction;
+
+        // Act
+        let pts_by_date = prayer_times_dt_rng(&params, juneau_ak(), &date_range);
+
+        // Assert
+        for (date, pts) in &pts_by_date {
+            assert_eq!(prayer_times_dt(&params, juneau_ak(), *date, None), *pts);
+        }
+    }
+}
+
+#[test]
+fn test_near_good_range_equals_dates_limited() {
+    // Arrange
+    let start_date = NaiveDate::from_ymd_opt(2022, 4, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2022, 9, 30).unwrap();
+    let date_range = DateRange::from(start_date..=end_date);
+    let mut params = Params::new(Method::Isna);
+    params.nearest_good_day = NearestGoodDaySearch {
+        max_days_before: 30,
+        max_days_after: 20,
+        direction: SearchDirection::Later,
+    };
+
+    // Act
+    let pts_by_date = prayer_times_dt_rng(&params, juneau_ak(), &date_range);
+
+    // Assert
+    // Some dates have a good day on only one side within the limits, and some on neither.
+    assert!(pts_by_date.values().any(|x| x[&Prayer::Fajr].is_err()));
+    for (date, pts) in &pts_by_date {
+        assert_eq!(prayer_times_dt(&params, juneau_ak(), *date, None), *pts);
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{NaiveDate, NaiveTime};
+use islamic_prayer_times::{
+    prayer_times_dt, ExtremeLatitudeMethod, Latitude, Method, NearestLatitudeReference, Params,
+    Prayer, Reference, NEAREST_LATITUDE,
+};
+
+use crate::common::{juneau_ak, location};
+
+fn near_lat_params(reference: NearestLatitudeReference) -> Params {
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method =
+        ExtremeLatitudeMethod::NearestLatitudeFajrIshaInvalid(NEAREST_LATITUDE);
+    params.nearest_latitude = reference;
+    params
+}
+
+#[test]
+fn test_near_lat_automatic() {
+    // Arrange
+    let params = near_lat_params(NearestLatitudeReference::Automatic);
+    let date = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, juneau_ak(), date, None);
+
+    // Assert
+    let fajr = pts[&Prayer::Fajr].unwrap();
+    assert_eq!(NaiveTime::from_hms_opt(0, 35, 0).unwrap(), fajr.time);
+    assert_eq!(
+        Reference::Latitude(Latitude::try_from(52.).unwrap()),
+        fajr.provenance.unwrap().reference
+    );
+    let isha = pts[&Prayer::Isha].unwrap();
+    assert_eq!(NaiveTime::from_hms_opt(23, 30, 0).unwrap(), isha.time);
+    assert_eq!(None, pts[&Prayer::Maghrib].unwrap().provenance);
+}
+
+#[test]
+fn test_near_lat_automatic_valid() {
+    // Arrange
+    let mut params = near_lat_params(NearestLatitudeReference::Automatic);
+    params.extreme_latitude_method =
+        ExtremeLatitudeMethod::NearestLatitudeAllPrayersAlways(NEAREST_LATITUDE);
+    let params_none = Params::new(Method::Isna);
+    let date = NaiveDate::from_ymd_opt(2022, 1, 6).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, juneau_ak(), date, None);
+    let pts_none = prayer_times_dt(&params_none, juneau_ak(), date, None);
+
+    // Assert
+    // Imsaak of an adjusted Fajr is calculated from minutes rather than an angle.
+    for (prayer, pt) in pts.iter().filter(|x| *x.0 != Prayer::Imsaak) {
+        let pt = pt.unwrap();
+        assert_eq!(pts_none[prayer].unwrap().time, pt.time);
+        assert_eq!(
+            Reference::Latitude(juneau_ak().coords.latitude),
+            pt.provenance.unwrap().reference
+        );
+    }
+}
+
+#[test]
+fn test_near_lat_city() {
+    // Arrange
+    let params_fixed = near_lat_params(NearestLatitudeReference::Fixed);
+    let city = location(48.5, 2.35, 87., 2.);
+    let params_city = near_lat_params(NearestLatitudeReference::City(city));
+    let date = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+
+    // Act
+    let pts_fixed = prayer_times_dt(&params_fixed, juneau_ak(), date, None);
+    let pts_city = prayer_times_dt(&params_city, juneau_ak(), date, None);
+
+    // Assert
+    for prayer in [Prayer::Fajr, Prayer::Isha] {
+        let pt_city = pts_city[&prayer].unwrap();
+        assert_eq!(pts_fixed[&prayer].unwrap().time, pt_city.time);
+        assert_eq!(Reference::City(city), pt_city.provenance.unwrap().reference);
+    }
+}
+
+#[test]
+fn test_near_lat_city_london() {
+    // Arrange
+    let city = location(51.5072, -0.1276, 11., 1.);
+    let params = near_lat_params(NearestLatitudeReference::City(city));
+    let date = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, juneau_ak(), date, None);
+
+    // Assert
+    assert_eq!(
+        NaiveTime::from_hms_opt(0, 53, 0).unwrap(),
+        pts[&Prayer::Fajr].unwrap().time
+    );
+    assert_eq!(
+        NaiveTime::from_hms_opt(23, 12, 0).unwrap(),
+        pts[&Prayer::Isha].unwrap().time
+    );
+}
@@ -1,7 +1,8 @@
 use islamic_prayer_times::prayer_times::{
-    params::{AsrShadowRatio, ExtremeLatitudeMethod, Method, Params, RoundSeconds},
+    params::{AsrShadowRatio, ExtremeLatitudeMethod, HorizonDip, Method, Params, RoundSeconds},
     Prayer,
 };
+use islamic_prayer_times::{DeltaT, Ephemeris, RefractionModel};
 
 #[test]
 fn test_default() {
@@ -161,6 +162,26 @@ fn test_method_fixed_isha() {
     assert_eq!(AsrShadowRatio::Shafi, params.asr_shadow_ratio);
 }
 
+#[test]
+fn test_deserialize_without_optional_fields() {
+    // Arrange
+    let mut json = serde_json::to_value(Params::new(Method::Isna)).unwrap();
+    let object = json.as_object_mut().unwrap();
+    object.remove("horizon_dip");
+    object.remove("refraction_model");
+    object.remove("standard_atmosphere");
+    object.remove("delta_t");
+    object.remove("ephemeris");
+    // Act
+    let params: Params = serde_json::from_value(json).unwrap();
+    // Assert
+    assert_eq!(HorizonDip::None, params.horizon_dip);
+    assert_eq!(RefractionModel::Itl, params.refraction_model);
+    assert!(!params.standard_atmosphere);
+    assert_eq!(DeltaT::EspenakMeeus, params.delta_t);
+    assert_eq!(Ephemeris::Vsop87, params.ephemeris);
+}
+
 fn assert_default(params: &Params) {
     use ExtremeLatitudeMethod::*;
     use Prayer::*;
@@ -181,4 +202,9 @@ fn assert_default(params: &Params) {
         params.extreme_latitude_method
     );
     assert_eq!(SpecialRounding, params.round_seconds);
+    assert_eq!(HorizonDip::None, params.horizon_dip);
+    assert_eq!(RefractionModel::Itl, params.refraction_model);
+    assert!(!params.standard_atmosphere);
+    assert_eq!(DeltaT::EspenakMeeus, params.delta_t);
+    assert_eq!(Ephemeris::Vsop87, params.ephemeris);
 }
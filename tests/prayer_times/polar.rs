@@ -0,0 +1,259 @@
+use chrono::{NaiveDate, NaiveTime};
+use islamic_prayer_times::{
+    prayer_times_dt, prayer_times_dt_rng, ExtremeLatitudeMethod, Latitude, LatitudeBand,
+    LatitudeBandCondition, Method, Params, Prayer, Reference, TransitIntervals,
+};
+
+use crate::common::{location, svalbard, tromso, year_2023};
+
+#[test]
+fn test_polar_complete_ordered_year() {
+    // Arrange
+    let methods = [
+        ExtremeLatitudeMethod::NearestValidLatitudeAllPrayersInvalid,
+        ExtremeLatitudeMethod::MeccaAllPrayersInvalid,
+        ExtremeLatitudeMethod::TransitIntervalsAllPrayersInvalid(TransitIntervals::default()),
+    ];
+
+    for location in [svalbard(), tromso()] {
+        for method in methods {
+            let mut params = Params::new(Method::Isna);
+            params.extreme_latitude_method = method;
+            if method == ExtremeLatitudeMethod::NearestValidLatitudeAllPrayersInvalid {
+                // The nearest valid latitude leaves Fajr and Isha of a white night to a band.
+                params.extreme_latitude_method = ExtremeLatitudeMethod::LatitudeBands;
+                params.latitude_bands = vec![
+                    LatitudeBand {
+                        min_latitude: 48.5,
+                        method,
+                        condition: LatitudeBandCondition::AnyInvalid,
+                    },
+                    LatitudeBand {
+                        min_latitude: 48.6,
+                        method: ExtremeLatitudeMethod::AngleBased,
+                        condition: LatitudeBandCondition::FajrIshaInvalid,
+                    },
+                    LatitudeBand {
+                        min_latitude: 66.,
+                        method,
+                        condition: LatitudeBandCondition::ShurooqMaghribInvalid,
+                    },
+                ];
+            }
+
+            // Act
+            let pts_by_date = prayer_times_dt_rng(&params, location, &year_2023());
+
+            // Assert
+            for pts in pts_by_date.values() {
+                let mins: Vec<_> = [
+                    Prayer::Fajr,
+                    Prayer::Shurooq,
+                    Prayer::Dhuhr,
+                    Prayer::Asr,
+                    Prayer::Maghrib,
+                    Prayer::Isha,
+                ]
+                .iter()
+                .map(|x| {
+                    let pt = pts[x].unwrap();
+                    assert_eq!(pt.extreme, pt.provenance.is_some());
+                    if let Some(provenance) = pt.provenance {
+                        assert!(
+                            method == provenance.method
+                                || params
+                                    .latitude_bands
+                                    .iter()
+                                    .any(|x| x.method == provenance.method)
+                        );
+                    }
+                    pt.time.signed_duration_since(NaiveTime::MIN).num_minutes()
+                })
+                .collect();
+
+                // The prayer times go once around the clock in order, such as Isha past midnight.
+                let cycle: i64 = (0..mins.len())
+                    .map(|i| (mins[(i + 1) % mins.len()] - mins[i]).rem_euclid(1440))
+                    .sum();
+                assert_eq!(1440, cycle);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_polar_near_valid_lat() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::NearestValidLatitudeAllPrayersInvalid;
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, svalbard(), date, None);
+
+    // Assert
+    let shurooq = pts[&Prayer::Shurooq].unwrap();
+    assert_eq!(NaiveTime::from_hms_opt(0, 31, 0).unwrap(), shurooq.time);
+    assert_eq!(
+        Reference::Latitude(Latitude::try_from(65.5).unwrap()),
+        shurooq.provenance.unwrap().reference
+    );
+    let maghrib = pts[&Prayer::Maghrib].unwrap();
+    assert_eq!(NaiveTime::from_hms_opt(23, 27, 0).unwrap(), maghrib.time);
+    let fajr = pts[&Prayer::Fajr].unwrap();
+    assert_eq!(NaiveTime::from_hms_opt(0, 16, 0).unwrap(), fajr.time);
+    assert_eq!(
+        Reference::NightFraction(0.25),
+        fajr.provenance.unwrap().reference
+    );
+    let isha = pts[&Prayer::Isha].unwrap();
+    assert_eq!(NaiveTime::from_hms_opt(23, 43, 0).unwrap(), isha.time);
+}
+
+#[test]
+fn test_polar_near_valid_lat_fajr_isha_invalid() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::NearestValidLatitudeAllPrayersInvalid;
+    let oslo = location(59.9139, 10.7522, 0., 1.);
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, oslo, date, None);
+
+    // Assert
+    assert!(pts[&Prayer::Isha].is_err());
+    let maghrib = pts[&Prayer::Maghrib].unwrap();
+    assert_eq!(NaiveTime::from_hms_opt(21, 44, 0).unwrap(), maghrib.time);
+    assert_eq!(None, maghrib.provenance);
+}
+
+#[test]
+fn test_polar_mecca() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::MeccaAllPrayersInvalid;
+    let date = NaiveDate::from_ymd_opt(2023, 12, 21).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, svalbard(), date, None);
+
+    // Assert
+    let shurooq = pts[&Prayer::Shurooq].unwrap();
+    assert_eq!(NaiveTime::from_hms_opt(6, 30, 0).unwrap(), shurooq.time);
+    let Reference::Mecca(shift) = shurooq.provenance.unwrap().reference else {
+        panic!("Expected the shift of the prayer times of Mecca");
+    };
+    assert!((shift - 96.82).abs() < 0.01);
+    let dhuhr = pts[&Prayer::Dhuhr].unwrap();
+    assert_eq!(NaiveTime::from_hms_opt(11, 55, 0).unwrap(), dhuhr.time);
+    assert_eq!(None, dhuhr.provenance);
+    let maghrib = pts[&Prayer::Maghrib].unwrap();
+    assert_eq!(NaiveTime::from_hms_opt(17, 20, 0).unwrap(), maghrib.time);
+}
+
+#[test]
+fn test_polar_transit_intervals() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method =
+        ExtremeLatitudeMethod::TransitIntervalsAllPrayersInvalid(TransitIntervals::default());
+    let date = NaiveDate::from_ymd_opt(2023, 12, 21).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, svalbard(), date, None);
+
+    // Assert
+    for (prayer, time, interval) in [
+        (Prayer::Fajr, (4, 25), -450.),
+        (Prayer::Shurooq, (5, 55), -360.),
+        (Prayer::Asr, (15, 15), 200.),
+        (Prayer::Maghrib, (17, 55), 360.),
+        (Prayer::Isha, (19, 25), 450.),
+    ] {
+        let pt = pts[&prayer].unwrap();
+        assert_eq!(NaiveTime::from_hms_opt(time.0, time.1, 0).unwrap(), pt.time);
+        assert_eq!(
+            Reference::Transit(interval),
+            pt.provenance.unwrap().reference
+        );
+    }
+}
+
+#[test]
+fn test_polar_latitude_bands() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::LatitudeBands;
+    params.latitude_bands = vec![
+        LatitudeBand {
+            min_latitude: 48.5,
+            method: ExtremeLatitudeMethod::SeventhOfNightFajrIshaInvalid,
+            condition: LatitudeBandCondition::FajrIshaInvalid,
+        },
+        LatitudeBand {
+            min_latitude: 66.,
+            method: ExtremeLatitudeMethod::MeccaAllPrayersInvalid,
+            condition: LatitudeBandCondition::ShurooqMaghribInvalid,
+        },
+    ];
+    let polar_day = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+    let white_night = NaiveDate::from_ymd_opt(2023, 5, 1).unwrap();
+
+    // Act
+    let pts_polar_day = prayer_times_dt(&params, tromso(), polar_day, None);
+    let pts_white_night = prayer_times_dt(&params, tromso(), white_night, None);
+
+    // Assert
+    let provenance = pts_polar_day[&Prayer::Maghrib].unwrap().provenance.unwrap();
+    assert_eq!(
+        ExtremeLatitudeMethod::MeccaAllPrayersInvalid,
+        provenance.method
+    );
+    let provenance = pts_white_night[&Prayer::Isha].unwrap().provenance.unwrap();
+    assert_eq!(
+        ExtremeLatitudeMethod::SeventhOfNightFajrIshaInvalid,
+        provenance.method
+    );
+    assert_eq!(None, pts_white_night[&Prayer::Maghrib].unwrap().provenance);
+}
+
+#[test]
+fn test_polar_night_invalid() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::None;
+    let date = NaiveDate::from_ymd_opt(2023, 11, 1).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, svalbard(), date, None);
+
+    // Assert
+    assert!(pts[&Prayer::Fajr].is_ok());
+    assert!(pts[&Prayer::Shurooq].is_err());
+    assert!(pts[&Prayer::Dhuhr].is_ok());
+    assert!(pts[&Prayer::Asr].is_err());
+    assert!(pts[&Prayer::Maghrib].is_err());
+    assert!(pts[&Prayer::Isha].is_ok());
+}
+
+#[test]
+fn test_transit_intervals_order() {
+    // Arrange
+    let intervals = [
+        (-480., -390., 180., 390., 480.),
+        (-390., -480., 180., 390., 480.),
+        (-480., 30., 180., 390., 480.),
+        (-480., -390., 0., 390., 480.),
+        (-480., -390., 180., 480., 390.),
+    ];
+    // Act
+    let transit_intervals =
+        intervals.map(|(f, s, a, m, i)| TransitIntervals::new(f, s, a, m, i).is_ok());
+    let deserialized = serde_json::from_str::<TransitIntervals>(
+        r#"{"fajr":-480,"shurooq":-390,"asr":180,"maghrib":480,"isha":390}"#,
+    );
+    // Assert
+    assert_eq!([true, false, false, false, false], transit_intervals);
+    assert!(deserialized.is_err());
+}
@@ -0,0 +1,123 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use chrono::NaiveDate;
+use islamic_prayer_times::{
+    prayer_times_dt, ExtremeLatitudeMethod, Latitude, Method, Params, Prayer, Provenance, Reference,
+};
+
+use crate::common::juneau_ak;
+
+#[test]
+fn test_provenance_near_good_day() {
+    // Arrange
+    let params = Params::new(Method::Isna); // NearestGoodDayFajrIshaInvalid
+    let date = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, juneau_ak(), date, None);
+
+    // Assert
+    let expected = Some(Provenance {
+        method: ExtremeLatitudeMethod::NearestGoodDayFajrIshaInvalid,
+        reference: Reference::Date(NaiveDate::from_ymd_opt(2022, 8, 6).unwrap()),
+    });
+    assert_eq!(expected, pts[&Prayer::Imsaak].unwrap().provenance);
+    assert_eq!(expected, pts[&Prayer::Fajr].unwrap().provenance);
+    assert_eq!(None, pts[&Prayer::Shurooq].unwrap().provenance);
+    assert_eq!(None, pts[&Prayer::Dhuhr].unwrap().provenance);
+    assert_eq!(None, pts[&Prayer::Asr].unwrap().provenance);
+    assert_eq!(None, pts[&Prayer::Maghrib].unwrap().provenance);
+    assert_eq!(expected, pts[&Prayer::Isha].unwrap().provenance);
+}
+
+#[test]
+fn test_provenance_near_lat() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    let nearest_latitude = Latitude::try_from(48.5).unwrap();
+    params.extreme_latitude_method =
+        ExtremeLatitudeMethod::NearestLatitudeAllPrayersAlways(nearest_latitude);
+    let date = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, juneau_ak(), date, None);
+
+    // Assert
+    let expected = Some(Provenance {
+        method: params.extreme_latitude_method,
+        reference: Reference::Latitude(nearest_latitude),
+    });
+    for pt in pts.values() {
+        assert_eq!(expected, pt.unwrap().provenance);
+        assert_eq!(true, pt.unwrap().extreme);
+    }
+}
+
+#[test]
+fn test_provenance_sev_half() {
+    // Arrange
+    let date = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+    let cases = [
+        (
+            ExtremeLatitudeMethod::SeventhOfNightFajrIshaInvalid,
+            Reference::NightFraction(1. / 7.),
+        ),
+        (
+            ExtremeLatitudeMethod::SeventhOfDayFajrIshaAlways,
+            Reference::DayFraction(1. / 7.),
+        ),
+        (
+            ExtremeLatitudeMethod::HalfOfNightFajrIshaInvalid,
+            Reference::NightFraction(0.5),
+        ),
+    ];
+
+    for (method, reference) in cases {
+        let mut params = Params::new(Method::Isna);
+        params.extreme_latitude_method = method;
+
+        // Act
+        let pts = prayer_times_dt(&params, juneau_ak(), date, None);
+
+        // Assert
+        let expected = Some(Provenance { method, reference });
+        assert_eq!(expected, pts[&Prayer::Fajr].unwrap().provenance);
+        assert_eq!(None, pts[&Prayer::Maghrib].unwrap().provenance);
+        assert_eq!(expected, pts[&Prayer::Isha].unwrap().provenance);
+    }
+}
+
+#[test]
+fn test_provenance_none() {
+    // Arrange
+    let params = Params::new(Method::Isna);
+    let date = NaiveDate::from_ymd_opt(2022, 1, 6).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, juneau_ak(), date, None);
+
+    // Assert
+    for pt in pts.values() {
+        assert_eq!(None, pt.unwrap().provenance);
+        assert_eq!(false, pt.unwrap().extreme);
+    }
+}
+
+#[test]
+fn test_provenance_serialize() {
+    // Arrange
+    let params = Params::new(Method::Isna);
+    let date = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+    let pts = prayer_times_dt(&params, juneau_ak(), date, None);
+
+    // Act
+    let isha = serde_json::to_string(&pts[&Prayer::Isha].unwrap()).unwrap();
+    let maghrib = serde_json::to_string(&pts[&Prayer::Maghrib].unwrap()).unwrap();
+
+    // Assert
+    assert_eq!(
+        r#"{"time":"23:51:00","extreme":true,"provenance":{"method":"NearestGoodDayFajrIshaInvalid","reference":{"Date":"2022-08-06"}}}"#,
+        isha
+    );
+    assert_eq!(r#"{"time":"21:02:00","extreme":false}"#, maghrib);
+}
@@ -0,0 +1,97 @@
+use chrono::{NaiveDate, NaiveTime};
+use islamic_prayer_times::{
+    prayer_times_dt, visible_sunrise_sunset, HorizonProfile, Method, Params, Prayer,
+    RefractionModel, Weather,
+};
+
+use crate::common::{potomac_md, unrounded_params};
+
+#[test]
+fn test_refraction_models() {
+    use Prayer::*;
+
+    // Arrange
+    let location = potomac_md(0.);
+    let date = NaiveDate::from_ymd_opt(2023, 3, 20).unwrap();
+    let params = unrounded_params(Method::Isna);
+    let model_pts = |refraction_model| {
+        let params = Params {
+            refraction_model,
+            ..params.clone()
+        };
+        prayer_times_dt(&params, location, date, None)
+    };
+    // Act
+    let none_pts = model_pts(RefractionModel::None);
+    let itl_pts = model_pts(RefractionModel::Itl);
+    let saemundsson_pts = model_pts(RefractionModel::Saemundsson);
+    let bennett_pts = model_pts(RefractionModel::Bennett);
+    let standard_pts = model_pts(RefractionModel::Standard);
+    // Assert
+    let time = |h, m, s| NaiveTime::from_hms_opt(h, m, s).unwrap();
+    let expected = [
+        (&none_pts, time(6, 15, 32), time(18, 17, 40)),
+        (&itl_pts, time(6, 12, 34), time(18, 20, 39)),
+        (&saemundsson_pts, time(6, 12, 21), time(18, 20, 52)),
+        (&bennett_pts, time(6, 12, 18), time(18, 20, 54)),
+        (&standard_pts, time(6, 12, 37), time(18, 20, 36)),
+    ];
+    for (pts, shurooq, maghrib) in expected {
+        assert_eq!(shurooq, pts[&Shurooq].unwrap().time);
+        assert_eq!(maghrib, pts[&Maghrib].unwrap().time);
+        for prayer in [Imsaak, Fajr, Dhuhr, Asr, Isha] {
+            assert_eq!(none_pts[&prayer], pts[&prayer]);
+        }
+    }
+}
+
+#[test]
+fn test_refraction_model_flat_horizon() {
+    use Prayer::*;
+
+    // Arrange
+    let location = potomac_md(0.);
+    let date = NaiveDate::from_ymd_opt(2023, 3, 20).unwrap();
+    let params = Params {
+        refraction_model: RefractionModel::Saemundsson,
+        ..unrounded_params(Method::Isna)
+    };
+    let horizon_profile = HorizonProfile::from_csv("0,0").unwrap();
+    // Act
+    let pts = prayer_times_dt(&params, location, date, None);
+    let sunrise_sunset = visible_sunrise_sunset(&params, location, date, &horizon_profile, None);
+    // Assert
+    // The upper limb of the Sun refracted by the same model touches a flat horizon at Shurooq
+    // and Maghrib.
+    let shurooq_diff = pts[&Shurooq].unwrap().time - sunrise_sunset.visible_sunrise.unwrap();
+    let maghrib_diff = pts[&Maghrib].unwrap().time - sunrise_sunset.visible_sunset.unwrap();
+    assert!(shurooq_diff.num_seconds().abs() <= 1);
+    assert!(maghrib_diff.num_seconds().abs() <= 1);
+}
+
+#[test]
+fn test_standard_atmosphere() {
+    use Prayer::*;
+
+    // Arrange
+    let location = potomac_md(3000.);
+    let date = NaiveDate::from_ymd_opt(2023, 3, 20).unwrap();
+    let params = Params {
+        refraction_model: RefractionModel::Saemundsson,
+        ..unrounded_params(Method::Isna)
+    };
+    let mut isa_params = params.clone();
+    isa_params.standard_atmosphere = true;
+    let isa_weather = Weather::standard_atmosphere(location.coords.elevation);
+    // Act
+    let pts = prayer_times_dt(&params, location, date, None);
+    let isa_pts = prayer_times_dt(&isa_params, location, date, None);
+    let weather_pts = prayer_times_dt(&params, location, date, Some(isa_weather));
+    let default_weather_pts =
+        prayer_times_dt(&isa_params, location, date, Some(Weather::default()));
+    // Assert
+    // Less refraction in the thinner air delays Shurooq.
+    assert!(isa_pts[&Shurooq].unwrap().time > pts[&Shurooq].unwrap().time);
+    assert_eq!(weather_pts, isa_pts);
+    assert_eq!(default_weather_pts, pts);
+}
@@ -0,0 +1,228 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use chrono::{NaiveDate, NaiveTime};
+use islamic_prayer_times::{
+    prayer_times_dt, prayer_times_dt_rng, AngleRule, CustomRule, DateRange, ExtremeLatitudeMethod,
+    Method, Params, Prayer, PrayerDay, PrayerRule, Reference, ShadowRatioRule, SunriseSunsetRule,
+};
+
+use crate::common::{juneau_ak, location};
+
+// Isha at the later of 17 degrees or 80 minutes after Maghrib.
+#[derive(Debug)]
+struct LaterIsha;
+
+impl PrayerRule for LaterIsha {
+    fn hour(&self, _prayer: Prayer, day: &PrayerDay) -> Result<f64, ()> {
+        let after_maghrib = day.sunset()? + 80. / 60.;
+        Ok(match day.altitude_hours(-17.).1 {
+            Ok(angle) => angle.max(after_maghrib),
+            Err(()) => after_maghrib,
+        })
+    }
+}
+
+// Maghrib 5 minutes after sunset.
+#[derive(Debug)]
+struct LaterMaghrib;
+
+impl PrayerRule for LaterMaghrib {
+    fn hour(&self, _prayer: Prayer, day: &PrayerDay) -> Result<f64, ()> {
+        day.sunset().map(|x| x + 5. / 60.)
+    }
+}
+
+// A prayer that is always invalid.
+#[derive(Debug)]
+struct Invalid;
+
+impl PrayerRule for Invalid {
+    fn hour(&self, _prayer: Prayer, _day: &PrayerDay) -> Result<f64, ()> {
+        Err(())
+    }
+}
+
+#[test]
+fn test_rules_built_in() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::AngleBased;
+    let mut params_rules = params.clone();
+    params_rules
+        .rules
+        .insert(Prayer::Fajr, CustomRule::new(AngleRule));
+    params_rules
+        .rules
+        .insert(Prayer::Shurooq, CustomRule::new(SunriseSunsetRule));
+    params_rules
+        .rules
+        .insert(Prayer::Asr, CustomRule::new(ShadowRatioRule));
+    params_rules
+        .rules
+        .insert(Prayer::Maghrib, CustomRule::new(SunriseSunsetRule));
+    params_rules
+        .rules
+        .insert(Prayer::Isha, CustomRule::new(AngleRule));
+    let start_date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+    let date_range = DateRange::from(start_date..=end_date);
+
+    // Act
+    let pts_by_date = prayer_times_dt_rng(&params, juneau_ak(), &date_range);
+    let pts_by_date_rules = prayer_times_dt_rng(&params_rules, juneau_ak(), &date_range);
+
+    // Assert
+    assert_eq!(pts_by_date, pts_by_date_rules);
+}
+
+#[test]
+fn test_rules_later_isha_angle() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params
+        .rules
+        .insert(Prayer::Isha, CustomRule::new(LaterIsha));
+    let potomac_md = location(38.9958333, -77.1161111, 0., -5.);
+    let date = NaiveDate::from_ymd_opt(2023, 2, 6).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, potomac_md, date, None);
+
+    // Assert
+    assert_eq!(
+        NaiveTime::from_hms_opt(17, 35, 0).unwrap(),
+        pts[&Prayer::Maghrib].unwrap().time
+    );
+    assert_eq!(
+        NaiveTime::from_hms_opt(19, 0, 0).unwrap(),
+        pts[&Prayer::Isha].unwrap().time
+    );
+}
+
+#[test]
+fn test_rules_later_isha_after_maghrib() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params
+        .rules
+        .insert(Prayer::Isha, CustomRule::new(LaterIsha));
+    let london = location(51.5072, -0.1276, 0., 1.);
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, london, date, None);
+
+    // Assert
+    assert_eq!(
+        NaiveTime::from_hms_opt(21, 22, 0).unwrap(),
+        pts[&Prayer::Maghrib].unwrap().time
+    );
+    let isha = pts[&Prayer::Isha].unwrap();
+    assert_eq!(NaiveTime::from_hms_opt(22, 42, 0).unwrap(), isha.time);
+    assert_eq!(None, isha.provenance);
+}
+
+#[test]
+fn test_rules_maghrib() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params
+        .rules
+        .insert(Prayer::Maghrib, CustomRule::new(LaterMaghrib));
+    let mut params_minutes = Params::new(Method::Isna);
+    params_minutes.minutes.insert(Prayer::Maghrib, 5.);
+    let date = NaiveDate::from_ymd_opt(2022, 3, 21).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, juneau_ak(), date, None);
+    let pts_minutes = prayer_times_dt(&params_minutes, juneau_ak(), date, None);
+
+    // Assert
+    assert_eq!(pts_minutes, pts);
+}
+
+#[test]
+fn test_rules_invalid_ext_lat() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::AngleBased;
+    params.rules.insert(Prayer::Fajr, CustomRule::new(Invalid));
+    let date = NaiveDate::from_ymd_opt(2022, 1, 6).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, juneau_ak(), date, None);
+
+    // Assert
+    let fajr = pts[&Prayer::Fajr].unwrap();
+    assert_eq!(NaiveTime::from_hms_opt(4, 23, 0).unwrap(), fajr.time);
+    assert_eq!(true, fajr.extreme);
+    assert_eq!(
+        Reference::NightFraction(0.25),
+        fajr.provenance.unwrap().reference
+    );
+}
+
+// Fajr 90 minutes before sunrise.
+#[derive(Debug)]
+struct EarlierFajr;
+
+impl PrayerRule for EarlierFajr {
+    fn hour(&self, _prayer: Prayer, day: &PrayerDay) -> Result<f64, ()> {
+        day.sunrise().map(|x| x - 1.5)
+    }
+}
+
+#[test]
+fn test_rules_imsaak_before_custom_fajr() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params
+        .rules
+        .insert(Prayer::Fajr, CustomRule::new(EarlierFajr));
+    let mut params_minutes = params.clone();
+    params_minutes.intervals.insert(Prayer::Imsaak, 10.);
+    let date = NaiveDate::from_ymd_opt(2022, 3, 21).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, location(39., -77., 0., -5.), date, None);
+    let pts_minutes = prayer_times_dt(&params_minutes, location(39., -77., 0., -5.), date, None);
+
+    // Assert
+    // Imsaak is when the Sun is 1.5 degrees lower than at the Fajr of the rule, or 10 minutes
+    // before it.
+    let imsaak = pts[&Prayer::Imsaak].unwrap().time;
+    let fajr = pts[&Prayer::Fajr].unwrap().time;
+    assert_eq!(8, fajr.signed_duration_since(imsaak).num_minutes());
+    let imsaak = pts_minutes[&Prayer::Imsaak].unwrap().time;
+    assert_eq!(10, fajr.signed_duration_since(imsaak).num_minutes());
+}
+
+#[test]
+fn test_rules_custom_fajr_isha_without_intervals() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params
+        .rules
+        .insert(Prayer::Fajr, CustomRule::new(EarlierFajr));
+    params
+        .rules
+        .insert(Prayer::Isha, CustomRule::new(LaterIsha));
+    let mut params_intervals = params.clone();
+    params_intervals.intervals.insert(Prayer::Fajr, 60.);
+    params_intervals.intervals.insert(Prayer::Isha, 60.);
+    let date = NaiveDate::from_ymd_opt(2022, 3, 21).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, location(39., -77., 0., -5.), date, None);
+    let pts_intervals =
+        prayer_times_dt(&params_intervals, location(39., -77., 0., -5.), date, None);
+
+    // Assert
+    // The intervals of Fajr and Isha do not override their custom rules.
+    for prayer in [Prayer::Fajr, Prayer::Isha] {
+        assert_eq!(pts[&prayer], pts_intervals[&prayer]);
+    }
+    let maghrib = pts[&Prayer::Maghrib].unwrap().time;
+    let isha = pts[&Prayer::Isha].unwrap().time;
+    assert!(isha.signed_duration_since(maghrib).num_minutes() >= 80);
+}
@@ -0,0 +1,124 @@
+use chrono::NaiveDate;
+use islamic_prayer_times::{
+    extreme_latitude_spans, visible_sunrise_sunset, CrossingError, ExtremeLatitudeMethod,
+    HorizonProfile, Method, Params, Prayer, Provenance, Reference,
+};
+
+use crate::common::{juneau_ak, location, tromso, year_2023};
+
+#[test]
+fn test_season_juneau_ak_near_good_day() {
+    // Arrange
+    let params = Params::new(Method::Isna); // NearestGoodDayFajrIshaInvalid
+
+    // Act
+    let spans = extreme_latitude_spans(&params, juneau_ak(), &year_2023(), None);
+
+    // Assert
+    assert_eq!(2, spans.len());
+    let fallback = Some(Provenance {
+        method: ExtremeLatitudeMethod::NearestGoodDayFajrIshaInvalid,
+        reference: Reference::Date(NaiveDate::from_ymd_opt(2023, 5, 6).unwrap()),
+    });
+    for (span, prayer, largest_discontinuity) in
+        [(spans[0], Prayer::Fajr, 13.), (spans[1], Prayer::Isha, 6.)]
+    {
+        assert_eq!(prayer, span.prayer);
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2023, 5, 7).unwrap(),
+            span.start_date
+        );
+        assert_eq!(NaiveDate::from_ymd_opt(2023, 8, 6).unwrap(), span.end_date);
+        assert_eq!(fallback, span.fallback);
+        assert_eq!(Some(0.), span.start_discontinuity);
+        assert_eq!(Some(largest_discontinuity), span.largest_discontinuity);
+        assert_eq!(Some(0.), span.end_discontinuity);
+    }
+}
+
+#[test]
+fn test_season_juneau_ak_sev_night() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::SeventhOfNightFajrIshaInvalid;
+
+    // Act
+    let spans = extreme_latitude_spans(&params, juneau_ak(), &year_2023(), None);
+
+    // Assert
+    assert_eq!(2, spans.len());
+    let fallback = Some(Provenance {
+        method: ExtremeLatitudeMethod::SeventhOfNightFajrIshaInvalid,
+        reference: Reference::NightFraction(1. / 7.),
+    });
+    assert_eq!(Prayer::Fajr, spans[0].prayer);
+    assert_eq!(fallback, spans[0].fallback);
+    assert_eq!(Some(142.), spans[0].start_discontinuity);
+    assert_eq!(Some(2.), spans[0].largest_discontinuity);
+    assert_eq!(Some(-139.), spans[0].end_discontinuity);
+    assert_eq!(Prayer::Isha, spans[1].prayer);
+    assert_eq!(fallback, spans[1].fallback);
+    assert_eq!(Some(-140.), spans[1].start_discontinuity);
+    assert_eq!(Some(-2.), spans[1].largest_discontinuity);
+    assert_eq!(Some(140.), spans[1].end_discontinuity);
+}
+
+#[test]
+fn test_season_tromso_polar_day_night() {
+    // Arrange
+    let params = Params::new(Method::Isna);
+    let location = tromso();
+
+    // Act
+    let spans = extreme_latitude_spans(&params, location, &year_2023(), None);
+
+    // Assert
+    let dates = |month, day| NaiveDate::from_ymd_opt(2023, month, day).unwrap();
+    let expected = [
+        (Prayer::Shurooq, dates(1, 1), dates(1, 15)),
+        (Prayer::Asr, dates(1, 1), dates(1, 19)),
+        (Prayer::Maghrib, dates(1, 1), dates(1, 15)),
+        (Prayer::Fajr, dates(4, 4), dates(9, 9)),
+        (Prayer::Isha, dates(4, 4), dates(9, 9)),
+        (Prayer::Shurooq, dates(5, 18), dates(7, 26)),
+        (Prayer::Maghrib, dates(5, 18), dates(7, 26)),
+        (Prayer::Asr, dates(11, 24), dates(12, 31)),
+        (Prayer::Shurooq, dates(11, 28), dates(12, 31)),
+        (Prayer::Maghrib, dates(11, 28), dates(12, 31)),
+    ];
+    assert_eq!(expected.len(), spans.len());
+    for (span, (prayer, start_date, end_date)) in spans.iter().zip(expected) {
+        assert_eq!(prayer, span.prayer);
+        assert_eq!(start_date, span.start_date);
+        assert_eq!(end_date, span.end_date);
+        if prayer == Prayer::Fajr || prayer == Prayer::Isha {
+            assert!(span.fallback.is_some());
+        } else {
+            assert_eq!(None, span.fallback);
+            assert_eq!(None, span.start_discontinuity);
+            assert_eq!(None, span.end_discontinuity);
+        }
+    }
+
+    // The exact sunrise and sunset over a flat horizon show that the Sun last sets on 5/17,
+    // so the polar day starts on 5/18.
+    let horizon_profile = HorizonProfile::from_csv("0,0").unwrap();
+    let last_set = visible_sunrise_sunset(&params, location, dates(5, 17), &horizon_profile, None);
+    assert!(last_set.visible_sunrise.is_ok() && last_set.visible_sunset.is_ok());
+    let polar_day = visible_sunrise_sunset(&params, location, dates(5, 18), &horizon_profile, None);
+    assert_eq!(Err(CrossingError::AlwaysAbove), polar_day.visible_sunrise);
+    assert_eq!(Err(CrossingError::AlwaysAbove), polar_day.visible_sunset);
+}
+
+#[test]
+fn test_season_no_spans() {
+    // Arrange
+    let params = Params::new(Method::Isna);
+    let location = location(39., -77., 0., -5.);
+
+    // Act
+    let spans = extreme_latitude_spans(&params, location, &year_2023(), None);
+
+    // Assert
+    assert!(spans.is_empty());
+}
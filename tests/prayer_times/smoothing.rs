@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+
+use chrono::{NaiveDate, NaiveTime};
+use islamic_prayer_times::{
+    prayer_times_dt_rng, DateRange, ExtremeLatitudeMethod, Method, Params, Prayer, PrayerTime,
+};
+
+use crate::common::{juneau_ak, year_2023};
+
+fn to_mins(time: NaiveTime) -> i64 {
+    time.signed_duration_since(NaiveTime::MIN).num_minutes()
+}
+
+// The change in minutes between two times the shortest way around the clock.
+fn change(before: NaiveTime, after: NaiveTime) -> i64 {
+    let mins = (to_mins(after) - to_mins(before)).rem_euclid(1440);
+    if mins > 720 {
+        mins - 1440
+    } else {
+        mins
+    }
+}
+
+// The largest change in minutes of a prayer time between consecutive dates.
+fn largest_change(
+    pts_by_date: &BTreeMap<NaiveDate, BTreeMap<Prayer, Result<PrayerTime, ()>>>,
+    prayer: Prayer,
+) -> i64 {
+    let times: Vec<_> = pts_by_date
+        .values()
+        .map(|x| x[&prayer].unwrap().time)
+        .collect();
+    (1..times.len())
+        .map(|i| change(times[i - 1], times[i]).abs())
+        .max()
+        .unwrap()
+}
+
+#[test]
+fn test_smoothing_sev_night() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::SeventhOfNightFajrIshaInvalid;
+    let mut params_smooth = params.clone();
+    params_smooth.smoothing_days = 5;
+    let mut params_none = params.clone();
+    params_none.extreme_latitude_method = ExtremeLatitudeMethod::None;
+
+    // Act
+    let pts_by_date = prayer_times_dt_rng(&params, juneau_ak(), &year_2023());
+    let pts_by_date_smooth = prayer_times_dt_rng(&params_smooth, juneau_ak(), &year_2023());
+    let pts_by_date_none = prayer_times_dt_rng(&params_none, juneau_ak(), &year_2023());
+
+    // Assert
+    for prayer in [Prayer::Fajr, Prayer::Isha] {
+        assert!(largest_change(&pts_by_date, prayer) > 130);
+        assert!(largest_change(&pts_by_date_smooth, prayer) <= 25);
+    }
+
+    for (date, pts_smooth) in &pts_by_date_smooth {
+        for (prayer, pt_smooth) in pts_smooth {
+            let pt_smooth = pt_smooth.unwrap();
+            let pt = pts_by_date[date][prayer].unwrap();
+            if !pt.extreme {
+                assert_eq!(pt.time, pt_smooth.time);
+            }
+            if let Ok(pt_none) = pts_by_date_none[date][prayer] {
+                assert!(change(pt_none.time, pt_smooth.time) >= 0);
+            }
+        }
+
+        let maghrib = pts_smooth[&Prayer::Maghrib].unwrap().time;
+        let isha = pts_smooth[&Prayer::Isha].unwrap().time;
+        assert!(change(maghrib, isha) > 0);
+    }
+}
+
+#[test]
+fn test_smoothing_range_independent() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::AngleBased;
+    params.smoothing_days = 7;
+    let start_date = NaiveDate::from_ymd_opt(2023, 5, 9).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2023, 8, 1).unwrap();
+    let date_range = DateRange::from(start_date..=end_date);
+
+    // Act
+    let pts_by_date_year = prayer_times_dt_rng(&params, juneau_ak(), &year_2023());
+    let pts_by_date = prayer_times_dt_rng(&params, juneau_ak(), &date_range);
+
+    // Assert
+    assert_eq!(date_range.num_days(), pts_by_date.len());
+    for (date, pts) in &pts_by_date {
+        assert_eq!(pts_by_date_year[date], *pts);
+    }
+}
+
+#[test]
+fn test_smoothing_single_days_independent() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::SeventhOfNightFajrIshaInvalid;
+    params.smoothing_days = 7;
+    let start_date = NaiveDate::from_ymd_opt(2023, 4, 20).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2023, 8, 31).unwrap();
+
+    // Act
+    let pts_by_date_year = prayer_times_dt_rng(&params, juneau_ak(), &year_2023());
+
+    // Assert
+    // The blend of a single date does not depend on how far the adjustment continues past it.
+    for date in start_date.iter_days().take_while(|x| *x <= end_date) {
+        let date_range = DateRange::from(date..=date);
+        let pts_by_date = prayer_times_dt_rng(&params, juneau_ak(), &date_range);
+        assert_eq!(pts_by_date_year[&date], pts_by_date[&date]);
+    }
+}
+
+#[test]
+fn test_smoothing_clamped_in_order() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::MeccaAllPrayersInvalid;
+    params.smoothing_days = 10;
+
+    // Act
+    let pts_by_date = prayer_times_dt_rng(&params, juneau_ak(), &year_2023());
+
+    // Assert
+    // A blend that would pass the prayer time before or after it is clamped to it rather than
+    // dropped.
+    use Prayer::*;
+    for pts in pts_by_date.values() {
+        let times: Vec<_> = [Fajr, Shurooq, Dhuhr, Asr, Maghrib, Isha]
+            .iter()
+            .map(|x| to_mins(pts[x].unwrap().time))
+            .collect();
+        let cycle: i64 = (0..times.len())
+            .map(|i| (times[(i + 1) % times.len()] - times[i]).rem_euclid(1440))
+            .sum();
+        assert_eq!(1440, cycle);
+    }
+    for prayer in [Fajr, Shurooq, Asr, Maghrib, Isha] {
+        assert!(largest_change(&pts_by_date, prayer) <= 30);
+    }
+}
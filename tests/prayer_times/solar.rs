@@ -0,0 +1,188 @@
+use chrono::{NaiveDate, NaiveTime};
+use float_cmp::assert_approx_eq;
+use islamic_prayer_times::{
+    altitude_crossings, prayer_times_dt, prayer_times_dt_rng, prayer_times_solar_days_dt_rng,
+    solar_day, solar_days_dt_rng, CrossingError, DateRange, Method, Params, Prayer, RoundSeconds,
+    SolarDay,
+};
+
+use crate::common::{potomac_md, tromso};
+
+#[test]
+fn test_sunrise_sunset_crossings_match_shurooq_maghrib() {
+    // Arrange
+    let location = potomac_md(0.);
+    let date = NaiveDate::from_ymd_opt(2023, 1, 25).unwrap();
+    let mut params = Params::new(Method::None);
+    params.round_seconds = RoundSeconds::None;
+    // Act
+    let crossings = altitude_crossings(&Params::default(), location, date, -0.83337, None);
+    let pts = prayer_times_dt(&params, location, date, None);
+    // Assert
+    let shurooq = pts[&Prayer::Shurooq].unwrap().time;
+    let maghrib = pts[&Prayer::Maghrib].unwrap().time;
+    assert!((crossings.rising.unwrap() - shurooq).num_seconds().abs() <= 1);
+    assert!((crossings.setting.unwrap() - maghrib).num_seconds().abs() <= 1);
+}
+
+#[test]
+fn test_twilight_crossings() {
+    // Arrange
+    let location = potomac_md(0.);
+    let date = NaiveDate::from_ymd_opt(2023, 1, 25).unwrap();
+    // Act
+    let civil = altitude_crossings(&Params::default(), location, date, -6., None);
+    let nautical = altitude_crossings(&Params::default(), location, date, -12., None);
+    let astronomical = altitude_crossings(&Params::default(), location, date, -18., None);
+    // Assert
+    assert_eq!(
+        NaiveTime::from_hms_opt(6, 52, 22).unwrap(),
+        civil.rising.unwrap()
+    );
+    assert!(astronomical.rising.unwrap() < nautical.rising.unwrap());
+    assert!(nautical.rising.unwrap() < civil.rising.unwrap());
+    assert!(civil.setting.unwrap() < nautical.setting.unwrap());
+    assert!(nautical.setting.unwrap() < astronomical.setting.unwrap());
+}
+
+#[test]
+fn test_crossing_never_happens_in_polar_day() {
+    // Arrange
+    let location = tromso();
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+    // Act
+    let crossings = altitude_crossings(&Params::default(), location, date, -0.83337, None);
+    // Assert
+    assert_eq!(Err(CrossingError::AlwaysAbove), crossings.rising);
+    assert_eq!(Err(CrossingError::AlwaysAbove), crossings.setting);
+}
+
+#[test]
+fn test_crossing_never_happens_in_polar_night() {
+    // Arrange
+    let location = tromso();
+    let date = NaiveDate::from_ymd_opt(2023, 12, 21).unwrap();
+    // Act
+    let crossings = altitude_crossings(&Params::default(), location, date, -0.83337, None);
+    // Assert
+    assert_eq!(Err(CrossingError::AlwaysBelow), crossings.rising);
+    assert_eq!(Err(CrossingError::AlwaysBelow), crossings.setting);
+}
+
+#[test]
+fn test_solar_day() {
+    // Arrange
+    let location = potomac_md(0.);
+    let date = NaiveDate::from_ymd_opt(2023, 1, 25).unwrap();
+    // Act
+    let solar_day = solar_day(&Params::default(), location, date, None);
+    // Assert
+    let crossings = altitude_crossings(&Params::default(), location, date, -0.83337, None);
+    assert_eq!(crossings.rising, solar_day.sunrise);
+    assert_eq!(crossings.setting, solar_day.sunset);
+    assert_eq!(
+        NaiveTime::from_hms_opt(12, 21, 7).unwrap(),
+        solar_day.solar_noon
+    );
+    assert_approx_eq!(f64, 10.01, solar_day.day_length, epsilon = 0.01);
+    assert_approx_eq!(f64, -12.3, solar_day.equation_of_time, epsilon = 0.1);
+    assert_approx_eq!(f64, -18.9, solar_day.declination, epsilon = 0.1);
+    assert_approx_eq!(f64, 32.1, solar_day.max_altitude, epsilon = 0.1);
+    assert_eq!(
+        altitude_crossings(
+            &Params::default(),
+            location,
+            date,
+            SolarDay::CIVIL_TWILIGHT_ANGLE,
+            None
+        ),
+        solar_day.civil_twilight
+    );
+    assert_eq!(
+        altitude_crossings(
+            &Params::default(),
+            location,
+            date,
+            SolarDay::NAUTICAL_TWILIGHT_ANGLE,
+            None
+        ),
+        solar_day.nautical_twilight
+    );
+    assert_eq!(
+        altitude_crossings(
+            &Params::default(),
+            location,
+            date,
+            SolarDay::ASTRONOMICAL_TWILIGHT_ANGLE,
+            None
+        ),
+        solar_day.astronomical_twilight
+    );
+}
+
+#[test]
+fn test_solar_day_in_polar_day() {
+    // Arrange
+    let location = tromso();
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+    // Act
+    let solar_day = solar_day(&Params::default(), location, date, None);
+    // Assert
+    assert_eq!(Err(CrossingError::AlwaysAbove), solar_day.sunrise);
+    assert_eq!(24., solar_day.day_length);
+    assert_approx_eq!(f64, 23.4, solar_day.declination, epsilon = 0.1);
+}
+
+#[test]
+fn test_solar_day_in_polar_night() {
+    // Arrange
+    let location = tromso();
+    let date = NaiveDate::from_ymd_opt(2023, 12, 21).unwrap();
+    // Act
+    let solar_day = solar_day(&Params::default(), location, date, None);
+    // Assert
+    assert_eq!(Err(CrossingError::AlwaysBelow), solar_day.sunset);
+    assert_eq!(0., solar_day.day_length);
+    assert!(solar_day.max_altitude < 0.);
+    assert!(solar_day.civil_twilight.rising.is_ok());
+}
+
+#[test]
+fn test_solar_days_dt_rng() {
+    // Arrange
+    let location = potomac_md(0.);
+    let start_date = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2023, 1, 31).unwrap();
+    let date_range = DateRange::from(start_date..=end_date);
+    // Act
+    let solar_days = solar_days_dt_rng(&Params::default(), location, &date_range, None);
+    // Assert
+    assert_eq!(31, solar_days.len());
+    assert_eq!(
+        solar_day(&Params::default(), location, end_date, None),
+        solar_days[&end_date]
+    );
+}
+
+#[test]
+fn test_prayer_times_solar_days_dt_rng() {
+    // Arrange
+    let location = tromso();
+    let mut params = Params::new(Method::Isna);
+    params.smoothing_days = 3;
+    let start_date = NaiveDate::from_ymd_opt(2023, 5, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2023, 5, 31).unwrap();
+    let date_range = DateRange::from(start_date..=end_date);
+    // Act
+    let (pts_by_date, solar_days) =
+        prayer_times_solar_days_dt_rng(&params, location, &date_range, None);
+    // Assert
+    assert_eq!(
+        prayer_times_dt_rng(&params, location, &date_range),
+        pts_by_date
+    );
+    assert_eq!(
+        solar_days_dt_rng(&params, location, &date_range, None),
+        solar_days
+    );
+}
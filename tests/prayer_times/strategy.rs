@@ -0,0 +1,227 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, NaiveTime};
+use islamic_prayer_times::{
+    prayer_times_dt, prayer_times_dt_rng, CustomStrategy, DateRange, ExtremeLatitudeDay,
+    ExtremeLatitudeMethod, ExtremeLatitudeStrategy, Method, Params, Prayer, PrayerHour, Provenance,
+    Reference,
+};
+
+use crate::common::juneau_ak;
+
+fn custom_params(strategy: impl ExtremeLatitudeStrategy + 'static) -> Params {
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::Custom;
+    params.custom_strategy = Some(CustomStrategy::new(strategy));
+    params
+}
+
+// Fajr 90 minutes before Shurooq and Isha 90 minutes after Maghrib when they are invalid.
+#[derive(Debug)]
+struct NinetyMinutes;
+
+impl ExtremeLatitudeStrategy for NinetyMinutes {
+    fn adjust(
+        &self,
+        day: &ExtremeLatitudeDay,
+        hours: &HashMap<Prayer, Result<f64, ()>>,
+    ) -> HashMap<Prayer, Result<PrayerHour, ()>> {
+        let provenance = Provenance {
+            method: day.params().extreme_latitude_method,
+            reference: Reference::Minutes(90.),
+        };
+        let mut adj_hours: HashMap<_, _> = hours
+            .iter()
+            .map(|(prayer, hour)| (*prayer, hour.map(PrayerHour::new)))
+            .collect();
+        for (prayer, from, interval) in [
+            (Prayer::Fajr, Prayer::Shurooq, -1.5),
+            (Prayer::Isha, Prayer::Maghrib, 1.5),
+        ] {
+            if let (Err(()), Ok(hour)) = (hours[&prayer], hours[&from]) {
+                let adj_hour = PrayerHour::new_extreme(hour + interval, provenance);
+                adj_hours.insert(prayer, Ok(adj_hour));
+            }
+        }
+        adj_hours
+    }
+}
+
+// A built-in method used as a custom strategy.
+#[derive(Debug)]
+struct BuiltIn(ExtremeLatitudeMethod);
+
+impl ExtremeLatitudeStrategy for BuiltIn {
+    fn adjust(
+        &self,
+        day: &ExtremeLatitudeDay,
+        hours: &HashMap<Prayer, Result<f64, ()>>,
+    ) -> HashMap<Prayer, Result<PrayerHour, ()>> {
+        self.0.adjust(day, hours)
+    }
+}
+
+// Fajr and Isha of a fixed date when they are invalid.
+#[derive(Debug)]
+struct FixedDate(NaiveDate);
+
+impl ExtremeLatitudeStrategy for FixedDate {
+    fn adjust(
+        &self,
+        day: &ExtremeLatitudeDay,
+        hours: &HashMap<Prayer, Result<f64, ()>>,
+    ) -> HashMap<Prayer, Result<PrayerHour, ()>> {
+        let provenance = Provenance {
+            method: day.params().extreme_latitude_method,
+            reference: Reference::Date(self.0),
+        };
+        let fixed_hours = day.hours_on(self.0);
+        hours
+            .iter()
+            .map(|(prayer, hour)| match (prayer, hour) {
+                (Prayer::Fajr | Prayer::Isha, Err(())) => (
+                    *prayer,
+                    fixed_hours[prayer].map(|x| PrayerHour::new_extreme(x, provenance)),
+                ),
+                _ => (*prayer, hour.map(PrayerHour::new)),
+            })
+            .collect()
+    }
+}
+
+// Isha 90 minutes after Maghrib, leaving out the other prayers.
+#[derive(Debug)]
+struct IshaOnly;
+
+impl ExtremeLatitudeStrategy for IshaOnly {
+    fn adjust(
+        &self,
+        day: &ExtremeLatitudeDay,
+        hours: &HashMap<Prayer, Result<f64, ()>>,
+    ) -> HashMap<Prayer, Result<PrayerHour, ()>> {
+        let provenance = Provenance {
+            method: day.params().extreme_latitude_method,
+            reference: Reference::Minutes(90.),
+        };
+        let isha = hours[&Prayer::Maghrib].map(|x| PrayerHour::new_extreme(x + 1.5, provenance));
+        HashMap::from([(Prayer::Isha, isha)])
+    }
+}
+
+#[test]
+fn test_strategy_custom() {
+    // Arrange
+    let params = custom_params(NinetyMinutes);
+    let date = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, juneau_ak(), date, None);
+
+    // Assert
+    let expected = Some(Provenance {
+        method: ExtremeLatitudeMethod::Custom,
+        reference: Reference::Minutes(90.),
+    });
+    let maghrib = pts[&Prayer::Maghrib].unwrap();
+    assert_eq!(NaiveTime::from_hms_opt(21, 2, 0).unwrap(), maghrib.time);
+    assert_eq!(None, maghrib.provenance);
+    let isha = pts[&Prayer::Isha].unwrap();
+    assert_eq!(NaiveTime::from_hms_opt(22, 32, 0).unwrap(), isha.time);
+    assert_eq!(expected, isha.provenance);
+    assert_eq!(true, isha.extreme);
+    assert_eq!(expected, pts[&Prayer::Fajr].unwrap().provenance);
+}
+
+#[test]
+fn test_strategy_custom_none() {
+    // Arrange
+    let mut params = Params::new(Method::Isna);
+    params.extreme_latitude_method = ExtremeLatitudeMethod::Custom;
+    let date = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, juneau_ak(), date, None);
+
+    // Assert
+    assert!(pts[&Prayer::Fajr].is_err());
+    assert!(pts[&Prayer::Isha].is_err());
+}
+
+#[test]
+fn test_strategy_built_in() {
+    // Arrange
+    let start_date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+    let date_range = DateRange::from(start_date..=end_date);
+
+    for method in [
+        ExtremeLatitudeMethod::AngleBased,
+        ExtremeLatitudeMethod::NearestGoodDayFajrIshaInvalid,
+        ExtremeLatitudeMethod::SeventhOfNightFajrIshaInvalid,
+    ] {
+        let params = custom_params(BuiltIn(method));
+        let mut params_method = Params::new(Method::Isna);
+        params_method.extreme_latitude_method = method;
+
+        // Act
+        let pts_by_date = prayer_times_dt_rng(&params, juneau_ak(), &date_range);
+        let pts_by_date_method = prayer_times_dt_rng(&params_method, juneau_ak(), &date_range);
+
+        // Assert
+        assert_eq!(pts_by_date_method, pts_by_date);
+    }
+}
+
+#[test]
+fn test_strategy_day_context() {
+    // Arrange
+    let fixed_date = NaiveDate::from_ymd_opt(2022, 3, 21).unwrap();
+    let params = custom_params(FixedDate(fixed_date));
+    let params_fixed_date = Params::new(Method::Isna);
+    let date = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, juneau_ak(), date, None);
+    let pts_fixed_date = prayer_times_dt(&params_fixed_date, juneau_ak(), fixed_date, None);
+
+    // Assert
+    for prayer in [Prayer::Fajr, Prayer::Isha] {
+        let pt = pts[&prayer].unwrap();
+        assert_eq!(pts_fixed_date[&prayer].unwrap().time, pt.time);
+        assert_eq!(
+            Reference::Date(fixed_date),
+            pt.provenance.unwrap().reference
+        );
+    }
+}
+
+#[test]
+fn test_strategy_custom_missing_prayers() {
+    // Arrange
+    let params = custom_params(IshaOnly);
+    let mut params_none = Params::new(Method::Isna);
+    params_none.extreme_latitude_method = ExtremeLatitudeMethod::None;
+    let date = NaiveDate::from_ymd_opt(2022, 7, 6).unwrap();
+
+    // Act
+    let pts = prayer_times_dt(&params, juneau_ak(), date, None);
+    let pts_none = prayer_times_dt(&params_none, juneau_ak(), date, None);
+
+    // Assert
+    assert_eq!(
+        NaiveTime::from_hms_opt(22, 32, 0).unwrap(),
+        pts[&Prayer::Isha].unwrap().time
+    );
+    for prayer in [
+        Prayer::Imsaak,
+        Prayer::Fajr,
+        Prayer::Shurooq,
+        Prayer::Dhuhr,
+        Prayer::Asr,
+        Prayer::Maghrib,
+    ] {
+        assert_eq!(pts_none[&prayer], pts[&prayer]);
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use islamic_prayer_times::{
+    prayer_times_dt, prayer_times_dt_rng_block_with_weather, prayer_times_dt_rng_with_weather,
+    solar_day, solar_days_dt_rng, DateRange, Method, Params, Pressure, RefractionModel,
+    Temperature, Weather, WeatherSeries,
+};
+
+use crate::common::{potomac_md, unrounded_params};
+
+fn weather(pressure: f64, temperature: f64) -> Weather {
+    Weather {
+        pressure: Pressure::try_from(pressure).unwrap(),
+        temperature: Temperature::try_from(temperature).unwrap(),
+    }
+}
+
+#[test]
+fn test_prayer_times_dt_rng_daily_weather() {
+    // Arrange
+    let params = Params {
+        refraction_model: RefractionModel::Saemundsson,
+        ..unrounded_params(Method::Isna)
+    };
+    let location = potomac_md(0.);
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let middle_date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2024, 1, 3).unwrap();
+    let date_range = DateRange::from(start_date..=end_date);
+    let cold = weather(1030., -25.);
+    let hot = weather(990., 40.);
+    let weather_series =
+        WeatherSeries::Daily(BTreeMap::from([(start_date, cold), (middle_date, hot)]));
+    // Act
+    let pts_by_day =
+        prayer_times_dt_rng_with_weather(&params, location, &date_range, Some(&weather_series));
+    // Assert
+    assert_eq!(
+        prayer_times_dt(&params, location, start_date, Some(cold)),
+        pts_by_day[&start_date]
+    );
+    assert_eq!(
+        prayer_times_dt(&params, location, middle_date, Some(hot)),
+        pts_by_day[&middle_date]
+    );
+    assert_eq!(
+        prayer_times_dt(&params, location, end_date, None),
+        pts_by_day[&end_date]
+    );
+    assert_ne!(
+        prayer_times_dt(&params, location, start_date, None),
+        pts_by_day[&start_date]
+    );
+}
+
+#[test]
+fn test_prayer_times_dt_rng_block_monthly_weather() {
+    // Arrange
+    let params = Params {
+        refraction_model: RefractionModel::Saemundsson,
+        ..unrounded_params(Method::Isna)
+    };
+    let location = potomac_md(0.);
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+    let date_range = DateRange::from(start_date..=end_date);
+    let weathers = std::array::from_fn(|month| weather(1015., month as f64 * 2.5 - 5.));
+    let weather_series = WeatherSeries::Monthly(weathers);
+    // Act
+    let pts_by_day =
+        prayer_times_dt_rng_with_weather(&params, location, &date_range, Some(&weather_series));
+    let pll_pts_by_day = prayer_times_dt_rng_block_with_weather(
+        &params,
+        location,
+        &date_range,
+        Some(&weather_series),
+        1,
+    );
+    // Assert
+    assert_eq!(pts_by_day, pll_pts_by_day);
+    let date = NaiveDate::from_ymd_opt(2024, 7, 15).unwrap();
+    assert_eq!(
+        prayer_times_dt(&params, location, date, Some(weathers[6])),
+        pts_by_day[&date]
+    );
+}
+
+#[test]
+fn test_solar_days_dt_rng_daily_weather() {
+    // Arrange
+    let location = potomac_md(0.);
+    let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let date_range = DateRange::from(date..=date);
+    let cold = weather(1030., -25.);
+    let weather_series = WeatherSeries::Daily(BTreeMap::from([(date, cold)]));
+    // Act
+    let solar_days = solar_days_dt_rng(
+        &Params::default(),
+        location,
+        &date_range,
+        Some(&weather_series),
+    );
+    // Assert
+    assert_eq!(
+        solar_day(&Params::default(), location, date, Some(cold)),
+        solar_days[&date]
+    );
+}